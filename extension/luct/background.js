@@ -113,6 +113,25 @@ async function load_scanner() {
         })
 }
 
+// Re-check SCTs, whose inclusion could not be verified before the MMD of their log passed
+const PENDING_RECHECK_INTERVAL = 10 * 60 * 1000;
+setInterval(async () => {
+    if (scanner) {
+        let reports = await scanner.recheck_pending_scts();
+        log("Re-checked pending SCTs of " + reports.length + " certificates");
+
+        // Flag certificates in open tabs, whose SCTs were not included in time
+        for (let report of reports) {
+            let status = Scanner.is_report_safe(report) ? "safe" : "unsafe";
+            for (let [tabId, tab] of tabState.tabs) {
+                if (tab.reports.has(report.fingerprint)) {
+                    await tabState.updateTab(tabId, report, status);
+                }
+            }
+        }
+    }
+}, PENDING_RECHECK_INTERVAL);
+
 function add_listener() {
     browser.webRequest.onHeadersReceived.addListener(async (details) => {
         //log(`Got a request for ${details.url} with ID ${details.requestId}`)
@@ -0,0 +1,199 @@
+use crate::{Client, ClientError, CtClient};
+use futures::{Stream, TryStreamExt, stream};
+use luct_core::{
+    tree::{ProofValidationError, RangeProof, TreeHead},
+    v1::{LogEntryChain, MerkleTreeLeaf, SignedTreeHead},
+};
+use std::ops::Range;
+
+impl<C: Client> CtClient<C> {
+    /// Stream the entries in `range` of the log, validated against the [`SignedTreeHead`]
+    ///
+    /// The entries are fetched in pages of whatever size the log returns.
+    /// Each page is validated using a [`RangeProof`] before its entries are yielded.
+    pub fn stream_entries_v1<'a>(
+        &'a self,
+        sth: &'a SignedTreeHead,
+        range: Range<u64>,
+    ) -> impl Stream<Item = Result<(MerkleTreeLeaf, LogEntryChain), ClientError>> + 'a {
+        stream::try_unfold(range.start, move |start| async move {
+            if start >= range.end {
+                return Ok(None);
+            }
+
+            let entries = self.get_validated_entries_v1(sth, start, range.end).await?;
+            let next = start + entries.len() as u64;
+
+            Ok::<_, ClientError>(Some((stream::iter(entries.into_iter().map(Ok)), next)))
+        })
+        .try_flatten()
+    }
+
+    /// Fetch a page of entries starting at `start`, and validate them against the [`SignedTreeHead`]
+    async fn get_validated_entries_v1(
+        &self,
+        sth: &SignedTreeHead,
+        start: u64,
+        end: u64,
+    ) -> Result<Vec<(MerkleTreeLeaf, LogEntryChain)>, ClientError> {
+        if end > sth.tree_size() {
+            return Err(ClientError::AuditProofError(
+                ProofValidationError::InvalidIndex {
+                    tree_size: sth.tree_size(),
+                    index: end - 1,
+                },
+            ));
+        }
+
+        let entries = self.get_entries_v1(start, end - 1).await?;
+        let leaves = entries
+            .iter()
+            .map(|(leaf, _)| leaf.clone())
+            .collect::<Vec<_>>();
+
+        // Fetch the audit proofs of the first and last entry of the page
+        let first = self.get_audit_proof_v1(sth, &leaves[0]).await?;
+        let last = match leaves.len() {
+            1 => first.clone(),
+            _ => {
+                self.get_audit_proof_v1(sth, &leaves[leaves.len() - 1])
+                    .await?
+            }
+        };
+
+        let proof = RangeProof::new(first, last);
+        if proof.start() != start {
+            return Err(ClientError::AuditProofError(
+                ProofValidationError::InvalidIndex {
+                    tree_size: sth.tree_size(),
+                    index: proof.start(),
+                },
+            ));
+        }
+        proof
+            .validate(&TreeHead::from(sth), &leaves)
+            .map_err(ClientError::AuditProofError)?;
+
+        tracing::debug!(
+            "validated entries {} to {} against tree size {}",
+            proof.start(),
+            proof.end(),
+            sth.tree_size()
+        );
+
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replay::{Fixture, ReplayClient};
+    use base64::{Engine, prelude::BASE64_STANDARD};
+    use futures::StreamExt;
+    use luct_core::{CtLogConfig, store::Hashable, v1::responses::GetEntriesResponse};
+
+    const ARGON2025H2: &str = "{
+        \"description\": \"Google Argon\",
+        \"version\": 1,
+        \"url\": \"https://ct.googleapis.com/logs/us1/argon2025h2/\",
+        \"key\": \"MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEr+TzlCzfpie1/rJhgxnIITojqKk9VK+8MZoc08HjtsLzD8e5yjsdeWVhIiWCVk6Y6KomKTYeKGBv6xVu93zQug==\",
+        \"mmd\": 86400
+    }";
+    const ARGON2025H2_REPLAY: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../testdata/replay/argon2025h2"
+    );
+    const GOOGLE_GET_ENTRY: &str = include_str!("../../testdata/google-entry.json");
+    const GOOGLE_AUDIT_PROOF: &str = include_str!("../../testdata/google-precert-audit-proof.json");
+
+    const GET_ENTRIES_URL: &str =
+        "https://ct.googleapis.com/logs/us1/argon2025h2/ct/v1/get-entries";
+    const GET_PROOF_BY_HASH_URL: &str =
+        "https://ct.googleapis.com/logs/us1/argon2025h2/ct/v1/get-proof-by-hash";
+    const GOOGLE_ENTRY_INDEX: u64 = 912557348;
+
+    #[tokio::test]
+    async fn stream_entries() {
+        let client = get_client(ReplayClient::load(ARGON2025H2_REPLAY).unwrap());
+        let sth = client.get_sth_v1().await.unwrap();
+
+        let entries = client
+            .stream_entries_v1(&sth, GOOGLE_ENTRY_INDEX..GOOGLE_ENTRY_INDEX + 1)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+
+        let (_, chain) = &entries[0];
+        assert_eq!(
+            chain.pre_certificate().unwrap().get_subject_name(),
+            "*.google.com"
+        );
+    }
+
+    #[tokio::test]
+    async fn get_entries() {
+        let client = get_client(ReplayClient::load(ARGON2025H2_REPLAY).unwrap());
+
+        // The log returns fewer entries than requested
+        let entries = client
+            .get_entries_v1(GOOGLE_ENTRY_INDEX, GOOGLE_ENTRY_INDEX + 9)
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn reject_tampered_entry() {
+        // Change the timestamp of the leaf, such that it no longer matches the logged one
+        let mut response: serde_json::Value = serde_json::from_str(GOOGLE_GET_ENTRY).unwrap();
+        let leaf_input = &mut response["entries"][0]["leaf_input"];
+        let mut leaf_bytes = BASE64_STANDARD
+            .decode(leaf_input.as_str().unwrap())
+            .unwrap();
+        leaf_bytes[9] ^= 1;
+        *leaf_input = BASE64_STANDARD.encode(&leaf_bytes).into();
+        let response = response.to_string();
+
+        let tampered: GetEntriesResponse = serde_json::from_str(&response).unwrap();
+        let tampered = Vec::<(MerkleTreeLeaf, LogEntryChain)>::try_from(&tampered).unwrap();
+        let tampered_hash = BASE64_STANDARD.encode(tampered[0].0.hash());
+
+        // The log serves the tampered entry and the proof of the original entry under its hash
+        let index = GOOGLE_ENTRY_INDEX.to_string();
+        let client = get_client(
+            ReplayClient::load(ARGON2025H2_REPLAY)
+                .unwrap()
+                .with_fixture(Fixture::get(
+                    GET_ENTRIES_URL,
+                    &[("start", &index), ("end", &index)],
+                    200,
+                    response,
+                ))
+                .with_fixture(Fixture::get(
+                    GET_PROOF_BY_HASH_URL,
+                    &[("hash", &tampered_hash), ("tree_size", "1329315675")],
+                    200,
+                    GOOGLE_AUDIT_PROOF,
+                )),
+        );
+        let sth = client.get_sth_v1().await.unwrap();
+
+        let result = client
+            .stream_entries_v1(&sth, GOOGLE_ENTRY_INDEX..GOOGLE_ENTRY_INDEX + 1)
+            .collect::<Vec<_>>()
+            .await;
+        assert!(matches!(
+            result.as_slice(),
+            [Err(ClientError::AuditProofError(_))]
+        ));
+    }
+
+    fn get_client(client: ReplayClient) -> CtClient<ReplayClient> {
+        let config: CtLogConfig = serde_json::from_str(ARGON2025H2).unwrap();
+        CtClient::new(config, client).unwrap()
+    }
+}
@@ -1,3 +1,7 @@
+pub mod cache;
 pub mod deduplication;
+pub mod rate_limit;
+pub mod replay;
 #[cfg(feature = "reqwest")]
 pub mod reqwest;
+pub mod retry;
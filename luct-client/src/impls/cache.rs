@@ -0,0 +1,279 @@
+//! [`Client`] wrapper to persistently cache immutable resources of tiling logs
+
+use crate::{Client, ClientError};
+use luct_core::{store::Store, tiling::DataTileId};
+use sha2::{Digest, Sha256};
+use std::{fmt::Debug, sync::Arc};
+use url::Url;
+
+/// Wraps an inner [`Client`] and caches immutable responses in a [`Store`].
+///
+/// In the static-ct-api, full tiles and issuers never change once they are published.
+/// They are cached indefinitely, keyed by the hash of their [`Url`].
+/// Partial tiles, checkpoints and everything else are passed through to the inner client.
+///
+/// Only successful binary responses are cached, i.e. tiles and issuers must be fetched via
+/// [`Client::get_bin`].
+/// Before a response is cached, it is checked to match its [`Url`], such that a malformed
+/// response is not served forever. Full tiles must have the size of a full tile and decode,
+/// issuers must hash to their fingerprint.
+#[derive(Debug)]
+pub struct CachingClient<C, S> {
+    inner: C,
+    store: Arc<S>,
+}
+
+impl<C: Clone, S> Clone for CachingClient<C, S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            store: self.store.clone(),
+        }
+    }
+}
+
+impl<C, S> CachingClient<C, S> {
+    pub fn new(inner: C, store: S) -> Self {
+        Self {
+            inner,
+            store: Arc::new(store),
+        }
+    }
+}
+
+impl<C, S> Client for CachingClient<C, S>
+where
+    C: Client,
+    S: Store<Key = [u8; 32], Value = Vec<u8>> + Debug,
+{
+    async fn get(
+        &self,
+        url: &Url,
+        params: &[(&str, &str)],
+    ) -> Result<(u16, Arc<String>), ClientError> {
+        self.inner.get(url, params).await
+    }
+
+    async fn get_bin(
+        &self,
+        url: &Url,
+        params: &[(&str, &str)],
+    ) -> Result<(u16, Arc<Vec<u8>>), ClientError> {
+        let resource = match immutable_resource(url) {
+            Some(resource) if params.is_empty() => resource,
+            _ => return self.inner.get_bin(url, params).await,
+        };
+
+        let key: [u8; 32] = Sha256::digest(url.as_str()).into();
+        if let Some(data) = self.store.get(key).await {
+            tracing::trace!("Serving {} from cache", url);
+            return Ok((200, Arc::new(data)));
+        }
+
+        let (status, data) = self.inner.get_bin(url, params).await?;
+        if status == 200 {
+            if resource.matches(&data) {
+                self.store.insert(key, data.to_vec()).await;
+            } else {
+                tracing::debug!("Response of {} does not match the url. Not caching it", url);
+            }
+        }
+
+        Ok((status, data))
+    }
+
+    async fn post(&self, url: &Url, body: &str) -> Result<(u16, Arc<String>), ClientError> {
+        self.inner.post(url, body).await
+    }
+}
+
+/// An immutable resource of the static-ct-api
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ImmutableResource {
+    /// A full tile of hashes
+    Tile,
+    /// A full tile of log entries
+    DataTile,
+    /// An issuer with the hex encoded SHA-256 fingerprint
+    Issuer(String),
+}
+
+impl ImmutableResource {
+    /// Returns true, if the `data` is a valid instance of this resource
+    fn matches(&self, data: &Arc<Vec<u8>>) -> bool {
+        match self {
+            ImmutableResource::Tile => data.len() == 256 * 32,
+            ImmutableResource::DataTile => DataTileId::from_index(0, u64::MAX)
+                .unwrap()
+                .with_data(data.clone())
+                .leaves()
+                .is_ok(),
+            ImmutableResource::Issuer(fingerprint) => {
+                let hash = Sha256::digest(data.as_slice())
+                    .iter()
+                    .map(|byte| format!("{byte:02x}"))
+                    .collect::<String>();
+                fingerprint.eq_ignore_ascii_case(&hash)
+            }
+        }
+    }
+}
+
+/// Returns the [`ImmutableResource`], if the [`Url`] points to a full tile or an issuer of the
+/// static-ct-api
+///
+/// See C2SP static-ct-api and C2SP tlog-tiles
+fn immutable_resource(url: &Url) -> Option<ImmutableResource> {
+    let segments = url.path_segments()?.collect::<Vec<_>>();
+
+    // Issuers are addressed by the hex encoded SHA-256 hash of the certificate
+    if let [.., "issuer", fingerprint] = segments.as_slice() {
+        return (fingerprint.len() == 64 && fingerprint.chars().all(|c| c.is_ascii_hexdigit()))
+            .then(|| ImmutableResource::Issuer(fingerprint.to_string()));
+    }
+
+    // Tiles are addressed as tile/<L>/<N> or tile/data/<N>, where N consists of segments
+    // like x001/x234/067. Partial tiles carry an additional .p/<W> suffix.
+    let tile = segments.iter().rposition(|segment| *segment == "tile")?;
+    let [level, index @ ..] = &segments[tile + 1..] else {
+        return None;
+    };
+
+    let is_level =
+        *level == "data" || (!level.is_empty() && level.chars().all(|c| c.is_ascii_digit()));
+    let is_index_segment = |segment: &&str| {
+        let digits = segment.strip_prefix('x').unwrap_or(segment);
+        digits.len() == 3 && digits.chars().all(|c| c.is_ascii_digit())
+    };
+
+    let is_full_tile = is_level
+        && !index.is_empty()
+        && index[..index.len() - 1]
+            .iter()
+            .all(|segment| segment.starts_with('x') && is_index_segment(segment))
+        && index
+            .last()
+            .is_some_and(|segment| !segment.starts_with('x') && is_index_segment(segment));
+
+    match (is_full_tile, *level) {
+        (false, _) => None,
+        (true, "data") => Some(ImmutableResource::DataTile),
+        (true, _) => Some(ImmutableResource::Tile),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replay::{Fixture, ReplayClient};
+    use luct_core::store::{MemoryStore, StoreRead};
+
+    const TILE_URL: &str = "https://tiles.luct.dev/";
+
+    #[test]
+    fn classify_urls() {
+        for (path, immutable) in [
+            ("tile/0/000", true),
+            ("tile/1/x001/x234/067", true),
+            ("tile/data/x001/234", true),
+            ("tile/0/000.p/17", false),
+            ("tile/data/x001/234.p/5", false),
+            ("tile/0/x001", false),
+            ("tile/0/0001", false),
+            ("tile/", false),
+            ("checkpoint", false),
+            (&format!("issuer/{}", "ab".repeat(32)), true),
+            ("issuer/abcd", false),
+            ("ct/v1/get-sth", false),
+        ] {
+            let url = Url::parse(TILE_URL).unwrap().join(path).unwrap();
+            assert_eq!(immutable_resource(&url).is_some(), immutable, "{path}");
+        }
+    }
+
+    #[tokio::test]
+    async fn cache_full_tiles() {
+        let tile = format!("{TILE_URL}tile/0/000");
+        let partial_tile = format!("{TILE_URL}tile/0/001.p/5");
+        let checkpoint = format!("{TILE_URL}checkpoint");
+
+        let client = CachingClient::new(
+            ReplayClient::default()
+                .with_fixture(Fixture::get(&tile, &[], 200, vec![1; 32 * 256]))
+                .with_fixture(Fixture::get(&partial_tile, &[], 200, vec![2; 32 * 5]))
+                .with_fixture(Fixture::get(&checkpoint, &[], 200, "checkpoint"))
+                .with_fixture(Fixture::get(&format!("{TILE_URL}tile/0/002"), &[], 404, "")),
+            MemoryStore::<[u8; 32], Vec<u8>>::default(),
+        );
+
+        for url in [&tile, &partial_tile, &checkpoint] {
+            let url = Url::parse(url).unwrap();
+            assert_eq!(client.get_bin(&url, &[]).await.unwrap().0, 200);
+        }
+
+        // Errors are not cached either
+        let url = Url::parse(&format!("{TILE_URL}tile/0/002")).unwrap();
+        assert_eq!(client.get_bin(&url, &[]).await.unwrap().0, 404);
+        assert_eq!(client.store.len().await, 1);
+
+        // A fresh client with the same store serves the full tile without a request
+        let client = CachingClient {
+            inner: ReplayClient::default(),
+            store: client.store.clone(),
+        };
+        let url = Url::parse(&tile).unwrap();
+        assert_eq!(
+            client.get_bin(&url, &[]).await.unwrap(),
+            (200, Arc::new(vec![1; 32 * 256]))
+        );
+        assert!(
+            client
+                .get_bin(&Url::parse(&partial_tile).unwrap(), &[])
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn only_cache_valid_responses() {
+        const DATA_TILE: &[u8] = include_bytes!("../../../testdata/data-tile-000.p2.bin");
+
+        let issuer = vec![3; 100];
+        let fingerprint = hex::encode(Sha256::digest(&issuer));
+        let full_data_tile = DATA_TILE.repeat(128);
+
+        let responses = [
+            // Valid responses
+            ("tile/0/000".to_string(), vec![1; 32 * 256], true),
+            ("tile/data/000".to_string(), full_data_tile.clone(), true),
+            (format!("issuer/{fingerprint}"), issuer.clone(), true),
+            // Responses that do not match their url
+            ("tile/0/001".to_string(), vec![1; 32 * 255], false),
+            ("tile/data/001".to_string(), DATA_TILE.to_vec(), false),
+            ("tile/data/002".to_string(), vec![4; 1000], false),
+            (format!("issuer/{}", "ab".repeat(32)), issuer, false),
+        ];
+
+        let client = CachingClient::new(
+            responses
+                .iter()
+                .fold(ReplayClient::default(), |client, (path, data, _)| {
+                    client.with_fixture(Fixture::get(
+                        &format!("{TILE_URL}{path}"),
+                        &[],
+                        200,
+                        data.clone(),
+                    ))
+                }),
+            MemoryStore::<[u8; 32], Vec<u8>>::default(),
+        );
+
+        for (path, data, cached) in responses {
+            let url = Url::parse(TILE_URL).unwrap().join(&path).unwrap();
+            assert_eq!(client.get_bin(&url, &[]).await.unwrap().1.as_ref(), &data);
+
+            let key: [u8; 32] = Sha256::digest(url.as_str()).into();
+            assert_eq!(client.store.get(key).await.is_some(), cached, "{path}");
+        }
+    }
+}
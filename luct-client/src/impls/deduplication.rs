@@ -82,6 +82,15 @@ impl<C: Client> Client for RequestDeduplicationClient<C> {
             Response::Error(client_error) => Err(client_error),
         }
     }
+
+    /// POST requests are not idempotent and therefore never deduplicated
+    async fn post(
+        &self,
+        url: &url::Url,
+        body: &str,
+    ) -> Result<(u16, std::sync::Arc<String>), ClientError> {
+        self.inner.post(url, body).await
+    }
 }
 
 impl<C: Client> RequestDeduplicationClient<C> {
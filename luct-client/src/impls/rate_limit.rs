@@ -0,0 +1,371 @@
+//! [`Client`] wrapper to limit the rate and concurrency of requests
+
+use crate::{
+    Client, ClientError,
+    util::{Instant, sleep},
+};
+use derive_builder::Builder;
+use futures::channel::oneshot::{Receiver, Sender, channel};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+use url::Url;
+use web_time::Duration;
+
+/// Configuration of a [`RateLimitClient`]
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct RateLimitConfig {
+    /// The number of requests per second, that may be sent to a single host
+    #[builder(default = "RateLimitConfig::DEFAULT_REQUESTS_PER_SECOND")]
+    pub(crate) requests_per_second: u32,
+
+    /// The number of requests, that may be sent to a single host at once, before the rate applies
+    #[builder(default = "RateLimitConfig::DEFAULT_BURST")]
+    pub(crate) burst: u32,
+
+    /// The maximum number of requests, that may be in flight at the same time across all hosts
+    #[builder(default = "RateLimitConfig::DEFAULT_MAX_IN_FLIGHT")]
+    pub(crate) max_in_flight: usize,
+}
+
+impl RateLimitConfig {
+    /// The default number of requests per second to a single host
+    pub const DEFAULT_REQUESTS_PER_SECOND: u32 = 10;
+
+    /// The default number of requests, that may be sent to a single host at once
+    pub const DEFAULT_BURST: u32 = 20;
+
+    /// The default maximum number of requests in flight across all hosts
+    pub const DEFAULT_MAX_IN_FLIGHT: usize = 16;
+
+    pub fn builder() -> RateLimitConfigBuilder {
+        RateLimitConfigBuilder::default()
+    }
+}
+
+impl RateLimitConfigBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if self.requests_per_second == Some(0) || self.burst == Some(0) {
+            return Err("requests_per_second and burst must be at least 1".to_string());
+        }
+        if self.max_in_flight == Some(0) {
+            return Err("max_in_flight must be at least 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self::builder().build().unwrap()
+    }
+}
+
+/// Wraps an inner [`Client`] and limits the requests sent through it.
+///
+/// Requests to the same host are limited by a token bucket, that holds up to `burst` tokens and
+/// refills at `requests_per_second`.
+/// Independent of the host, at most `max_in_flight` requests are running at the same time.
+/// Requests exceeding the limits wait until they may proceed.
+#[derive(Debug, Clone)]
+pub struct RateLimitClient<C> {
+    inner: C,
+    config: RateLimitConfig,
+    buckets: Arc<Mutex<BTreeMap<String, TokenBucket>>>,
+    in_flight: Arc<Mutex<InFlight>>,
+}
+
+impl<C> RateLimitClient<C> {
+    pub fn new(inner: C, config: RateLimitConfig) -> Self {
+        Self {
+            inner,
+            config,
+            buckets: Arc::new(Mutex::new(BTreeMap::new())),
+            in_flight: Arc::new(Mutex::new(InFlight::default())),
+        }
+    }
+
+    /// Wait until a request to the host of `url` may be sent and return a permit to send it
+    async fn acquire(&self, url: &Url) -> Permit {
+        let host = url.host_str().unwrap_or_default().to_string();
+
+        // Wait for a token of the host
+        loop {
+            let wait = self
+                .buckets
+                .lock()
+                .unwrap()
+                .entry(host.clone())
+                .or_insert_with(|| TokenBucket::new(self.config.burst))
+                .try_take(&self.config);
+
+            match wait {
+                None => break,
+                Some(wait) => {
+                    tracing::trace!("Rate limit of host {} reached, waiting {:?}", host, wait);
+                    sleep(wait).await
+                }
+            }
+        }
+
+        // Wait for a free slot
+        let receiver = {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            if in_flight.running < self.config.max_in_flight {
+                in_flight.running += 1;
+                None
+            } else {
+                let (sender, receiver) = channel();
+                in_flight.waiting.push_back(sender);
+                Some(receiver)
+            }
+        };
+
+        if let Some(receiver) = receiver {
+            tracing::trace!("Too many requests in flight, waiting for a free slot");
+
+            // The slot is handed over by the request that finished
+            let mut waiting = WaitingPermit {
+                receiver,
+                in_flight: self.in_flight.clone(),
+                granted: false,
+            };
+            (&mut waiting.receiver)
+                .await
+                .expect("Waiting requests are never dropped. This is a bug");
+            waiting.granted = true;
+        }
+
+        Permit(self.in_flight.clone())
+    }
+}
+
+impl<C: Client> Client for RateLimitClient<C> {
+    async fn get(
+        &self,
+        url: &Url,
+        params: &[(&str, &str)],
+    ) -> Result<(u16, Arc<String>), ClientError> {
+        let _permit = self.acquire(url).await;
+        self.inner.get(url, params).await
+    }
+
+    async fn get_bin(
+        &self,
+        url: &Url,
+        params: &[(&str, &str)],
+    ) -> Result<(u16, Arc<Vec<u8>>), ClientError> {
+        let _permit = self.acquire(url).await;
+        self.inner.get_bin(url, params).await
+    }
+
+    async fn post(&self, url: &Url, body: &str) -> Result<(u16, Arc<String>), ClientError> {
+        let _permit = self.acquire(url).await;
+        self.inner.post(url, body).await
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(burst: u32) -> Self {
+        Self {
+            tokens: burst as f64,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Take a token from the bucket
+    ///
+    /// Returns the time to wait until the next token is available, if the bucket is empty.
+    fn try_take(&mut self, config: &RateLimitConfig) -> Option<Duration> {
+        let now = Instant::now();
+        let rate = config.requests_per_second as f64;
+
+        self.tokens =
+            (self.tokens + (now - self.last_refill).as_secs_f64() * rate).min(config.burst as f64);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            None
+        } else {
+            Some(Duration::from_secs_f64((1.0 - self.tokens) / rate))
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct InFlight {
+    running: usize,
+    waiting: VecDeque<Sender<()>>,
+}
+
+/// A slot for a running request, that is freed on drop
+struct Permit(Arc<Mutex<InFlight>>);
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        let mut in_flight = self.0.lock().unwrap();
+
+        // Hand the slot over to the next waiting request, that is still alive
+        while let Some(sender) = in_flight.waiting.pop_front() {
+            if sender.send(()).is_ok() {
+                return;
+            }
+        }
+        in_flight.running -= 1;
+    }
+}
+
+/// A request waiting for a free slot
+///
+/// If the request is cancelled after a slot was handed over to it, the slot is freed on drop.
+struct WaitingPermit {
+    receiver: Receiver<()>,
+    in_flight: Arc<Mutex<InFlight>>,
+    granted: bool,
+}
+
+impl Drop for WaitingPermit {
+    fn drop(&mut self) {
+        if self.granted {
+            return;
+        }
+
+        // After closing, no slot can be handed over anymore, but one might already be sent
+        self.receiver.close();
+        if let Ok(Some(())) = self.receiver.try_recv() {
+            drop(Permit(self.in_flight.clone()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::future::join_all;
+
+    /// A [`Client`] that takes some time to respond and tracks the requests in flight
+    #[derive(Debug, Default)]
+    struct SlowClient {
+        in_flight: Mutex<usize>,
+        max_in_flight: Mutex<usize>,
+    }
+
+    impl Client for SlowClient {
+        async fn get(
+            &self,
+            url: &Url,
+            params: &[(&str, &str)],
+        ) -> Result<(u16, Arc<String>), ClientError> {
+            let (status, data) = self.get_bin(url, params).await?;
+            Ok((status, Arc::new(String::from_utf8_lossy(&data).to_string())))
+        }
+
+        async fn get_bin(
+            &self,
+            _url: &Url,
+            _params: &[(&str, &str)],
+        ) -> Result<(u16, Arc<Vec<u8>>), ClientError> {
+            {
+                let mut in_flight = self.in_flight.lock().unwrap();
+                *in_flight += 1;
+                let mut max_in_flight = self.max_in_flight.lock().unwrap();
+                *max_in_flight = (*max_in_flight).max(*in_flight);
+            }
+            sleep(Duration::from_millis(5)).await;
+            *self.in_flight.lock().unwrap() -= 1;
+
+            Ok((200, Arc::new(vec![])))
+        }
+
+        async fn post(&self, url: &Url, _body: &str) -> Result<(u16, Arc<String>), ClientError> {
+            self.get(url, &[]).await
+        }
+    }
+
+    fn get_client(
+        requests_per_second: u32,
+        burst: u32,
+        max_in_flight: usize,
+    ) -> RateLimitClient<SlowClient> {
+        let config = RateLimitConfig::builder()
+            .requests_per_second(requests_per_second)
+            .burst(burst)
+            .max_in_flight(max_in_flight)
+            .build()
+            .unwrap();
+        RateLimitClient::new(SlowClient::default(), config)
+    }
+
+    #[tokio::test]
+    async fn cap_requests_in_flight() {
+        let client = get_client(1000, 1000, 3);
+        let urls = (0..10)
+            .map(|idx| Url::parse(&format!("https://log{idx}.luct.dev/checkpoint")).unwrap())
+            .collect::<Vec<_>>();
+
+        let responses = join_all(urls.iter().map(|url| client.get_bin(url, &[]))).await;
+        assert!(responses.iter().all(|response| response.is_ok()));
+        assert_eq!(*client.inner.max_in_flight.lock().unwrap(), 3);
+        assert_eq!(client.in_flight.lock().unwrap().running, 0);
+    }
+
+    #[tokio::test]
+    async fn free_slot_of_cancelled_request() {
+        let client = get_client(1000, 1000, 1);
+        let url = Url::parse("https://tiles.luct.dev/tile/0/000").unwrap();
+
+        let permit = client.acquire(&url).await;
+        let mut waiting = Box::pin(client.acquire(&url));
+        assert!(futures::poll!(waiting.as_mut()).is_pending());
+
+        // The slot is handed over, but the waiting request is cancelled before it runs
+        drop(permit);
+        drop(waiting);
+        assert_eq!(client.in_flight.lock().unwrap().running, 0);
+
+        // A request that is cancelled while still waiting does not take the slot either
+        let permit = client.acquire(&url).await;
+        let mut waiting = Box::pin(client.acquire(&url));
+        assert!(futures::poll!(waiting.as_mut()).is_pending());
+        drop(waiting);
+        drop(permit);
+        assert_eq!(client.in_flight.lock().unwrap().running, 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn limit_rate_per_host() {
+        let client = get_client(100, 2, 100);
+        let url = Url::parse("https://tiles.luct.dev/tile/0/000").unwrap();
+        let other_url = Url::parse("https://other.luct.dev/tile/0/000").unwrap();
+
+        // The burst is served immediately, the remaining requests at 100 per second
+        let start = Instant::now();
+        join_all((0..6).map(|_| client.get_bin(&url, &[]))).await;
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(40) && elapsed < Duration::from_millis(50));
+
+        // Other hosts have their own budget
+        let start = Instant::now();
+        join_all((0..2).map(|_| client.get_bin(&other_url, &[]))).await;
+        assert!(start.elapsed() < Duration::from_millis(10));
+    }
+
+    #[test]
+    fn reject_invalid_config() {
+        assert!(RateLimitConfig::builder().burst(0).build().is_err());
+        assert!(RateLimitConfig::builder().max_in_flight(0).build().is_err());
+        assert_eq!(
+            RateLimitConfig::default().max_in_flight,
+            RateLimitConfig::DEFAULT_MAX_IN_FLIGHT
+        );
+    }
+}
@@ -0,0 +1,421 @@
+//! [`Client`] implementations to record and replay requests for offline testing
+//!
+//! The fixtures are stored in a directory, one JSON file per request.
+//! Each file contains the method, url and query parameters of the request, as well as the
+//! status code and body of the response.
+//! Text bodies are stored in `body`, binary bodies are stored base64 encoded in `body_base64`.
+
+use crate::{Client, ClientError};
+use base64::{Engine, prelude::BASE64_STANDARD};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use url::Url;
+
+/// The HTTP method of a recorded request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Method {
+    Get,
+    Post,
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Method::Get => write!(f, "GET"),
+            Method::Post => write!(f, "POST"),
+        }
+    }
+}
+
+/// A recorded request together with its response
+///
+/// The body of POST requests is not part of the fixture, i.e. all POST requests to the same
+/// url are answered with the same response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fixture {
+    method: Method,
+    url: String,
+    params: Vec<(String, String)>,
+    status: u16,
+    body: Vec<u8>,
+}
+
+impl Fixture {
+    /// Create a [`Fixture`] answering a GET request to `url` with `params`
+    pub fn get(url: &str, params: &[(&str, &str)], status: u16, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            method: Method::Get,
+            url: url.to_string(),
+            params: params
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            status,
+            body: body.into(),
+        }
+    }
+
+    /// Create a [`Fixture`] answering a POST request to `url`
+    pub fn post(url: &str, status: u16, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            method: Method::Post,
+            url: url.to_string(),
+            params: vec![],
+            status,
+            body: body.into(),
+        }
+    }
+
+    /// Load a [`Fixture`] from a file
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file: FixtureFile = serde_json::from_slice(&fs::read(path)?)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        file.try_into()
+    }
+
+    /// Store this [`Fixture`] in the directory `dir`
+    ///
+    /// The file name is derived from the request, such that the same request overwrites the previous recording.
+    pub fn save(&self, dir: impl AsRef<Path>) -> io::Result<PathBuf> {
+        let path = dir.as_ref().join(self.file_name());
+        let file = serde_json::to_vec_pretty(&FixtureFile::from(self))
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+        fs::create_dir_all(dir)?;
+        fs::write(&path, file)?;
+
+        Ok(path)
+    }
+
+    fn key(&self) -> RequestKey {
+        RequestKey {
+            method: self.method,
+            url: self.url.clone(),
+            params: self.params.clone(),
+        }
+    }
+
+    /// A readable file name, with a hash of the request to avoid collisions
+    fn file_name(&self) -> String {
+        let key = self.key().to_string();
+        let readable = key
+            .chars()
+            .skip(key.find("://").map(|idx| idx + 3).unwrap_or(0))
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .take(120)
+            .collect::<String>();
+
+        // FNV-1a, since the hash needs to be stable across platforms and versions
+        let hash = key.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+
+        format!("{}_{readable}_{hash:016x}.json", self.method)
+    }
+}
+
+/// The serialized form of a [`Fixture`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FixtureFile {
+    method: Method,
+    url: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    params: Vec<(String, String)>,
+    status: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body_base64: Option<String>,
+}
+
+impl From<&Fixture> for FixtureFile {
+    fn from(fixture: &Fixture) -> Self {
+        let (body, body_base64) = match String::from_utf8(fixture.body.clone()) {
+            Ok(body) => (Some(body), None),
+            Err(_) => (None, Some(BASE64_STANDARD.encode(&fixture.body))),
+        };
+
+        Self {
+            method: fixture.method,
+            url: fixture.url.clone(),
+            params: fixture.params.clone(),
+            status: fixture.status,
+            body,
+            body_base64,
+        }
+    }
+}
+
+impl TryFrom<FixtureFile> for Fixture {
+    type Error = io::Error;
+
+    fn try_from(file: FixtureFile) -> Result<Self, Self::Error> {
+        let body = match (file.body, file.body_base64) {
+            (Some(body), None) => body.into_bytes(),
+            (None, Some(body)) => BASE64_STANDARD
+                .decode(body)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?,
+            (None, None) => vec![],
+            (Some(_), Some(_)) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "fixture contains both body and body_base64",
+                ));
+            }
+        };
+
+        Ok(Self {
+            method: file.method,
+            url: file.url,
+            params: file.params,
+            status: file.status,
+            body,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct RequestKey {
+    method: Method,
+    url: String,
+    params: Vec<(String, String)>,
+}
+
+impl RequestKey {
+    fn new(method: Method, url: &Url, params: &[(&str, &str)]) -> Self {
+        Self {
+            method,
+            url: url.to_string(),
+            params: params
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for RequestKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.method, self.url)?;
+        for (idx, (k, v)) in self.params.iter().enumerate() {
+            let separator = if idx == 0 { '?' } else { '&' };
+            write!(f, "{separator}{k}={v}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A [`Client`] answering requests from recorded [`Fixtures`](Fixture)
+///
+/// Requests without a matching fixture fail with a [`ClientError::ConnectionErrorStd`].
+#[derive(Debug, Clone, Default)]
+pub struct ReplayClient {
+    fixtures: BTreeMap<RequestKey, Fixture>,
+}
+
+impl ReplayClient {
+    /// Load all fixtures from the directory `dir`
+    pub fn load(dir: impl AsRef<Path>) -> io::Result<Self> {
+        let mut client = Self::default();
+
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                client = client.with_fixture(Fixture::load(path)?);
+            }
+        }
+
+        Ok(client)
+    }
+
+    /// Add a [`Fixture`], replacing any previous fixture of the same request
+    pub fn with_fixture(mut self, fixture: Fixture) -> Self {
+        self.fixtures.insert(fixture.key(), fixture);
+        self
+    }
+
+    fn replay(&self, key: RequestKey) -> Result<(u16, &[u8]), ClientError> {
+        match self.fixtures.get(&key) {
+            Some(fixture) => {
+                tracing::trace!("Replaying {}", key);
+                Ok((fixture.status, &fixture.body))
+            }
+            None => Err(ClientError::ConnectionErrorStd(Arc::new(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No fixture recorded for {key}"),
+            )))),
+        }
+    }
+}
+
+impl Client for ReplayClient {
+    async fn get(
+        &self,
+        url: &Url,
+        params: &[(&str, &str)],
+    ) -> Result<(u16, Arc<String>), ClientError> {
+        let (status, body) = self.replay(RequestKey::new(Method::Get, url, params))?;
+        Ok((status, Arc::new(String::from_utf8_lossy(body).to_string())))
+    }
+
+    async fn get_bin(
+        &self,
+        url: &Url,
+        params: &[(&str, &str)],
+    ) -> Result<(u16, Arc<Vec<u8>>), ClientError> {
+        let (status, body) = self.replay(RequestKey::new(Method::Get, url, params))?;
+        Ok((status, Arc::new(body.to_vec())))
+    }
+
+    async fn post(&self, url: &Url, _body: &str) -> Result<(u16, Arc<String>), ClientError> {
+        let (status, body) = self.replay(RequestKey::new(Method::Post, url, &[]))?;
+        Ok((status, Arc::new(String::from_utf8_lossy(body).to_string())))
+    }
+}
+
+/// Wraps an inner [`Client`] and records all responses as [`Fixtures`](Fixture) into a directory
+///
+/// The directory can then be loaded with [`ReplayClient::load`].
+/// Requests that fail without a response are not recorded.
+#[derive(Debug, Clone)]
+pub struct RecordingClient<C> {
+    inner: C,
+    dir: PathBuf,
+}
+
+impl<C> RecordingClient<C> {
+    pub fn new(inner: C, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            inner,
+            dir: dir.into(),
+        }
+    }
+
+    fn record(&self, fixture: Fixture) {
+        match fixture.save(&self.dir) {
+            Ok(path) => tracing::debug!("Recorded {} into {:?}", fixture.key(), path),
+            Err(err) => tracing::error!("Failed to record {}: {}", fixture.key(), err),
+        }
+    }
+}
+
+impl<C: Client> Client for RecordingClient<C> {
+    async fn get(
+        &self,
+        url: &Url,
+        params: &[(&str, &str)],
+    ) -> Result<(u16, Arc<String>), ClientError> {
+        let (status, body) = self.inner.get(url, params).await?;
+        self.record(Fixture::get(url.as_str(), params, status, body.as_bytes()));
+        Ok((status, body))
+    }
+
+    async fn get_bin(
+        &self,
+        url: &Url,
+        params: &[(&str, &str)],
+    ) -> Result<(u16, Arc<Vec<u8>>), ClientError> {
+        let (status, body) = self.inner.get_bin(url, params).await?;
+        self.record(Fixture::get(url.as_str(), params, status, body.as_slice()));
+        Ok((status, body))
+    }
+
+    async fn post(&self, url: &Url, body: &str) -> Result<(u16, Arc<String>), ClientError> {
+        let (status, response) = self.inner.post(url, body).await?;
+        self.record(Fixture::post(url.as_str(), status, response.as_bytes()));
+        Ok((status, response))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn record_and_replay() {
+        let url = Url::parse("https://log.luct.dev/ct/v1/get-entries").unwrap();
+        let tile_url = Url::parse("https://tiles.luct.dev/tile/0/000").unwrap();
+        let params = [("start", "0"), ("end", "9")];
+
+        let inner = ReplayClient::default()
+            .with_fixture(Fixture::get(url.as_str(), &params, 200, "{}"))
+            .with_fixture(Fixture::get(tile_url.as_str(), &[], 200, vec![0xff, 0x00]))
+            .with_fixture(Fixture::post(url.as_str(), 400, "bad request"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let recorder = RecordingClient::new(inner, dir.path());
+        recorder.get(&url, &params).await.unwrap();
+        recorder.get_bin(&tile_url, &[]).await.unwrap();
+        recorder.post(&url, "{}").await.unwrap();
+
+        // Requests without recording are not recorded
+        assert!(recorder.get(&url, &[("start", "1")]).await.is_err());
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 3);
+
+        let replay = ReplayClient::load(dir.path()).unwrap();
+        assert_eq!(
+            replay.get(&url, &params).await.unwrap(),
+            (200, Arc::new("{}".to_string()))
+        );
+        assert_eq!(
+            replay.get_bin(&tile_url, &[]).await.unwrap(),
+            (200, Arc::new(vec![0xff, 0x00]))
+        );
+        assert_eq!(
+            replay.post(&url, "{\"chain\": []}").await.unwrap(),
+            (400, Arc::new("bad request".to_string()))
+        );
+
+        // The query parameters are part of the request
+        assert!(replay.get(&url, &[]).await.is_err());
+        assert!(
+            replay
+                .get(&url, &[("end", "9"), ("start", "0")])
+                .await
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn fixture_file_format() {
+        let fixture = Fixture::get("https://tiles.luct.dev/tile/0/000", &[], 200, vec![0xff]);
+        let file = serde_json::to_value(FixtureFile::from(&fixture)).unwrap();
+        assert_eq!(
+            file,
+            serde_json::json!({
+                "method": "GET",
+                "url": "https://tiles.luct.dev/tile/0/000",
+                "status": 200,
+                "body_base64": "/w=="
+            })
+        );
+
+        let fixture = Fixture::get(
+            "https://log.luct.dev/ct/v1/get-sth-consistency",
+            &[("first", "1"), ("second", "2")],
+            200,
+            "{}",
+        );
+        assert!(
+            fixture
+                .file_name()
+                .starts_with("GET_log_luct_dev_ct_v1_get_sth_consistency_first_1_second_2_")
+        );
+        assert_eq!(
+            Fixture::try_from(
+                serde_json::from_value::<FixtureFile>(
+                    serde_json::to_value(FixtureFile::from(&fixture)).unwrap()
+                )
+                .unwrap()
+            )
+            .unwrap(),
+            fixture
+        );
+    }
+}
@@ -1,7 +1,7 @@
 //! Implementation of the [`Client`] trait using [`reqwest`]
 
 use crate::{Client, ClientError};
-use reqwest::Response;
+use reqwest::{Response, header::RETRY_AFTER};
 use std::sync::Arc;
 use url::Url;
 
@@ -54,15 +54,47 @@ impl Client for ReqwestClient {
 
         Ok((status, Arc::new(data.to_vec())))
     }
+
+    async fn post(&self, url: &Url, body: &str) -> Result<(u16, Arc<String>), ClientError> {
+        let response = self
+            .client
+            .post(url.clone())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.to_string())
+            .send()
+            .await
+            .map_err(|err| ClientError::ConnectionErrorStd(Arc::new(err)))?;
+        check_retry_after(&response)?;
+        let status = response.status().as_u16();
+        let data = response
+            .text()
+            .await
+            .map_err(|err| ClientError::ConnectionErrorStd(Arc::new(err)))?;
+
+        Ok((status, Arc::new(data)))
+    }
 }
 
 impl ReqwestClient {
     async fn request(&self, url: &Url, params: &[(&str, &str)]) -> Result<Response, ClientError> {
-        self.client
+        let response = self
+            .client
             .get(url.clone())
             .query(params)
             .send()
             .await
-            .map_err(|err| ClientError::ConnectionErrorStd(Arc::new(err)))
+            .map_err(|err| ClientError::ConnectionErrorStd(Arc::new(err)))?;
+        check_retry_after(&response)?;
+
+        Ok(response)
     }
 }
+
+fn check_retry_after(response: &Response) -> Result<(), ClientError> {
+    let retry_after = response
+        .headers()
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok());
+
+    ClientError::check_retry_after(response.url(), response.status().as_u16(), retry_after)
+}
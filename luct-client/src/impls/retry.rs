@@ -0,0 +1,360 @@
+//! [`Client`] wrapper to retry requests that failed due to transient errors
+
+use crate::{Client, ClientError, util::sleep};
+use std::{sync::Arc, time::Duration};
+use url::Url;
+
+/// The default maximum number of attempts per request, see [`RetryClient::with_max_attempts`]
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
+/// Status codes, after which a request may succeed when retried
+const RETRYABLE_STATUS: [u16; 7] = [408, 425, 429, 500, 502, 503, 504];
+
+/// Wraps an inner [`Client`] and retries failed requests with exponential backoff.
+///
+/// Connection errors and responses with a transient status code (see [`ClientError::is_retryable`])
+/// are retried up to `max_attempts` times in total.
+/// The delay before the `n`th retry is `base_delay * 2^(n-1)`, capped at `max_delay`,
+/// of which a random half is dropped, such that many clients do not retry in lockstep.
+///
+/// If the server responded with [`ClientError::RetryAfter`], the requested delay is used instead.
+/// If that delay exceeds `max_delay`, the request is not retried.
+///
+/// POST requests are never retried, since submissions are not idempotent.
+#[derive(Debug, Clone)]
+pub struct RetryClient<C> {
+    inner: C,
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+}
+
+impl<C> RetryClient<C> {
+    pub fn new(inner: C) -> Self {
+        Self {
+            inner,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+        }
+    }
+
+    /// Set the maximum number of attempts per request, including the first one
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Set the delay before the first retry
+    pub fn with_base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Set the maximum delay between two attempts
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Compute the delay before retrying the `attempt`th attempt
+    ///
+    /// Returns `None`, if the request should not be retried.
+    fn delay(&self, attempt: u32, url: &Url, error: &ClientError) -> Option<Duration> {
+        if attempt >= self.max_attempts || !error.is_retryable() {
+            return None;
+        }
+
+        let delay = match error {
+            ClientError::RetryAfter { retry_after, .. } if retry_after > &self.max_delay => {
+                tracing::warn!(
+                    "Not retrying request to {}, server asked to wait for {:?}",
+                    url,
+                    retry_after
+                );
+                return None;
+            }
+            ClientError::RetryAfter { retry_after, .. } => *retry_after,
+            _ => {
+                let delay = self
+                    .base_delay
+                    .saturating_mul(2u32.saturating_pow(attempt - 1))
+                    .min(self.max_delay);
+                delay.mul_f64(0.5 + rand::random::<f64>() / 2.0)
+            }
+        };
+
+        tracing::debug!(
+            "Attempt {} of request to {} failed: {}, retrying in {:?}",
+            attempt,
+            url,
+            error,
+            delay
+        );
+
+        Some(delay)
+    }
+
+    async fn retry<T, F>(&self, url: &Url, request: impl Fn() -> F) -> Result<(u16, T), ClientError>
+    where
+        F: Future<Output = Result<(u16, T), ClientError>>,
+    {
+        let mut attempt = 1;
+        loop {
+            let error = match request().await {
+                Ok((status, data)) if !RETRYABLE_STATUS.contains(&status) => {
+                    return Ok((status, data));
+                }
+                Ok((status, data)) => {
+                    let error = ClientError::ResponseError {
+                        url: url.to_string(),
+                        code: status,
+                        msg: String::new(),
+                    };
+
+                    // On the last attempt, the response is returned as is
+                    if attempt >= self.max_attempts {
+                        return Ok((status, data));
+                    }
+                    error
+                }
+                Err(err) => err,
+            };
+
+            let Some(delay) = self.delay(attempt, url, &error) else {
+                return Err(error);
+            };
+            sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
+
+impl<C: Client> Client for RetryClient<C> {
+    async fn get(
+        &self,
+        url: &Url,
+        params: &[(&str, &str)],
+    ) -> Result<(u16, Arc<String>), ClientError> {
+        self.retry(url, || self.inner.get(url, params)).await
+    }
+
+    async fn get_bin(
+        &self,
+        url: &Url,
+        params: &[(&str, &str)],
+    ) -> Result<(u16, Arc<Vec<u8>>), ClientError> {
+        self.retry(url, || self.inner.get_bin(url, params)).await
+    }
+
+    async fn post(&self, url: &Url, body: &str) -> Result<(u16, Arc<String>), ClientError> {
+        self.inner.post(url, body).await
+    }
+}
+
+impl ClientError {
+    /// Returns true, if the error is transient and the request may succeed when retried
+    ///
+    /// These are connection errors and responses with status codes 408, 425, 429, 500, 502, 503 and 504.
+    /// Errors that result from validating the response are never retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ClientError::ConnectionError(_)
+            | ClientError::ConnectionErrorStd(_)
+            | ClientError::RetryAfter { .. } => true,
+            ClientError::ResponseError { code, .. } => RETRYABLE_STATUS.contains(code),
+            _ => false,
+        }
+    }
+
+    /// Turn 429 and 503 responses with a `Retry-After` header into [`ClientError::RetryAfter`]
+    ///
+    /// This is meant to be called by [`Client`] implementations, with the value of the header
+    /// if it was present.
+    /// Only the delay-seconds form of the header is supported, HTTP dates are ignored.
+    pub fn check_retry_after(
+        url: &Url,
+        status: u16,
+        retry_after: Option<&str>,
+    ) -> Result<(), ClientError> {
+        if status != 429 && status != 503 {
+            return Ok(());
+        }
+
+        match retry_after.and_then(|value| value.trim().parse::<u64>().ok()) {
+            Some(retry_after) => Err(ClientError::RetryAfter {
+                url: url.to_string(),
+                code: status,
+                retry_after: Duration::from_secs(retry_after),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{collections::VecDeque, sync::Mutex};
+
+    const URL: &str = "https://tiles.luct.dev/tile/0/000";
+
+    /// A [`Client`] returning a fixed sequence of responses
+    #[derive(Debug, Default)]
+    struct FlakyClient {
+        responses: Mutex<VecDeque<Result<u16, ClientError>>>,
+        requests: Mutex<u32>,
+    }
+
+    impl FlakyClient {
+        fn new(responses: impl IntoIterator<Item = Result<u16, ClientError>>) -> Self {
+            Self {
+                responses: Mutex::new(responses.into_iter().collect()),
+                requests: Mutex::new(0),
+            }
+        }
+
+        fn requests(&self) -> u32 {
+            *self.requests.lock().unwrap()
+        }
+    }
+
+    impl Client for FlakyClient {
+        async fn get(
+            &self,
+            url: &Url,
+            params: &[(&str, &str)],
+        ) -> Result<(u16, Arc<String>), ClientError> {
+            let (status, data) = self.get_bin(url, params).await?;
+            Ok((status, Arc::new(String::from_utf8_lossy(&data).to_string())))
+        }
+
+        async fn get_bin(
+            &self,
+            _url: &Url,
+            _params: &[(&str, &str)],
+        ) -> Result<(u16, Arc<Vec<u8>>), ClientError> {
+            *self.requests.lock().unwrap() += 1;
+            let response = self.responses.lock().unwrap().pop_front().unwrap();
+            response.map(|status| (status, Arc::new(vec![])))
+        }
+
+        async fn post(&self, url: &Url, _body: &str) -> Result<(u16, Arc<String>), ClientError> {
+            self.get(url, &[]).await
+        }
+    }
+
+    fn connection_error() -> ClientError {
+        ClientError::ConnectionError("connection reset".to_string())
+    }
+
+    fn get_client(
+        responses: impl IntoIterator<Item = Result<u16, ClientError>>,
+    ) -> RetryClient<FlakyClient> {
+        RetryClient::new(FlakyClient::new(responses))
+            .with_base_delay(Duration::from_millis(1))
+            .with_max_delay(Duration::from_millis(10))
+    }
+
+    #[tokio::test]
+    async fn retry_transient_errors() {
+        let url = Url::parse(URL).unwrap();
+        let client = get_client([Err(connection_error()), Ok(503), Ok(200)]);
+
+        assert_eq!(client.get_bin(&url, &[]).await.unwrap().0, 200);
+        assert_eq!(client.inner.requests(), 3);
+    }
+
+    #[tokio::test]
+    async fn give_up_after_max_attempts() {
+        let url = Url::parse(URL).unwrap();
+
+        let client = get_client([Err(connection_error()), Err(connection_error()), Ok(200)])
+            .with_max_attempts(2);
+        assert!(matches!(
+            client.get_bin(&url, &[]).await,
+            Err(ClientError::ConnectionError(_))
+        ));
+        assert_eq!(client.inner.requests(), 2);
+
+        // The status of the last attempt is returned to the caller
+        let client = get_client([Ok(502), Ok(502), Ok(502), Ok(200)]);
+        assert_eq!(client.get(&url, &[]).await.unwrap().0, 502);
+        assert_eq!(client.inner.requests(), 3);
+    }
+
+    #[tokio::test]
+    async fn do_not_retry_permanent_errors() {
+        let url = Url::parse(URL).unwrap();
+
+        let client = get_client([Ok(404), Ok(200)]);
+        assert_eq!(client.get_bin(&url, &[]).await.unwrap().0, 404);
+        assert_eq!(client.inner.requests(), 1);
+
+        let client = get_client([Err(ClientError::SthError), Ok(200)]);
+        assert!(client.get_bin(&url, &[]).await.is_err());
+        assert_eq!(client.inner.requests(), 1);
+
+        // Submissions are not retried
+        let client = get_client([Err(connection_error()), Ok(200)]);
+        assert!(client.post(&url, "{}").await.is_err());
+        assert_eq!(client.inner.requests(), 1);
+    }
+
+    #[tokio::test]
+    async fn respect_retry_after() {
+        let url = Url::parse(URL).unwrap();
+        let retry_after = |retry_after| ClientError::RetryAfter {
+            url: URL.to_string(),
+            code: 429,
+            retry_after,
+        };
+
+        let client = get_client([Err(retry_after(Duration::from_millis(5))), Ok(200)]);
+        assert_eq!(client.get_bin(&url, &[]).await.unwrap().0, 200);
+        assert_eq!(client.inner.requests(), 2);
+
+        // The server asks to wait longer than the maximum delay
+        let client = get_client([Err(retry_after(Duration::from_secs(60))), Ok(200)]);
+        assert!(matches!(
+            client.get_bin(&url, &[]).await,
+            Err(ClientError::RetryAfter { code: 429, .. })
+        ));
+        assert_eq!(client.inner.requests(), 1);
+    }
+
+    #[test]
+    fn parse_retry_after() {
+        let url = Url::parse(URL).unwrap();
+
+        assert!(matches!(
+            ClientError::check_retry_after(&url, 503, Some(" 5 ")),
+            Err(ClientError::RetryAfter { code: 503, retry_after, .. })
+                if retry_after == Duration::from_secs(5)
+        ));
+
+        // Other status codes, missing headers and HTTP dates are ignored
+        assert!(ClientError::check_retry_after(&url, 500, Some("5")).is_ok());
+        assert!(ClientError::check_retry_after(&url, 429, None).is_ok());
+        assert!(
+            ClientError::check_retry_after(&url, 429, Some("Wed, 21 Oct 2015 07:28:00 GMT"))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn backoff_is_capped() {
+        let url = Url::parse(URL).unwrap();
+        let client = RetryClient::new(())
+            .with_max_attempts(10)
+            .with_base_delay(Duration::from_millis(100))
+            .with_max_delay(Duration::from_millis(1000));
+
+        for (attempt, expected) in [(1, 100), (2, 200), (3, 400), (4, 800), (5, 1000), (9, 1000)] {
+            let delay = client.delay(attempt, &url, &connection_error()).unwrap();
+            let expected = Duration::from_millis(expected);
+            assert!(delay >= expected / 2 && delay <= expected, "{delay:?}");
+        }
+        assert_eq!(client.delay(10, &url, &connection_error()), None);
+    }
+}
@@ -1,40 +1,58 @@
 #![forbid(unsafe_code)]
 
+use chrono::{DateTime, Utc};
 use luct_core::{
-    CtLog, CtLogConfig, SignatureValidationError,
-    tiling::{ParseCheckpointError, TilingError},
+    CodecError, CtLog, CtLogConfig, CtLogConfigError, SignatureValidationError,
+    tiling::{ParseCheckpointError, TilingError, WitnessPolicy},
     tree::ProofValidationError,
 };
-use std::{error::Error, fmt::Debug, sync::Arc};
+use std::{error::Error, fmt::Debug, sync::Arc, time::Duration};
 use thiserror::Error;
 use url::Url;
 
 pub use impls::*;
+pub use log_list::{GOOGLE_LOG_LIST_SIGNATURE_URL, GOOGLE_LOG_LIST_URL, LogListUpdater};
+pub use node::NodeSthClient;
 pub use tiling::TileFetchStore;
 
+mod entries;
 mod impls;
+mod log_list;
+mod node;
 mod request;
 mod tiling;
 mod util;
 
-// TODO: Fetch entries API
-// TODO: Tests with a mock client
-
 /// Wrapper around [`Client`], that implements fetching and validation logic
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CtClient<C> {
     log: CtLog,
     client: C,
+    witness_policy: Option<WitnessPolicy>,
 }
 
 impl<C> CtClient<C> {
-    pub fn new(config: CtLogConfig, client: C) -> Self {
+    pub fn new(config: CtLogConfig, client: C) -> Result<Self, CtLogConfigError> {
+        Ok(Self::from_log(CtLog::new(config)?, client))
+    }
+
+    /// Create a client for a [`CtLog`], keeping its [`LogMetadata`](luct_core::log_list::LogMetadata)
+    pub fn from_log(log: CtLog, client: C) -> Self {
         Self {
-            log: CtLog::new(config),
+            log,
             client,
+            witness_policy: None,
         }
     }
 
+    /// Require checkpoints fetched by this client to satisfy the [`WitnessPolicy`]
+    ///
+    /// The policy only applies to tiling logs, since only checkpoints carry cosignatures.
+    pub fn with_witness_policy(mut self, witness_policy: Option<WitnessPolicy>) -> Self {
+        self.witness_policy = witness_policy;
+        self
+    }
+
     pub fn log(&self) -> &CtLog {
         &self.log
     }
@@ -78,7 +96,22 @@ pub trait Client: Debug {
         params: &[(&str, &str)],
     ) -> impl Future<Output = Result<(u16, Arc<Vec<u8>>), ClientError>>;
 
-    // TODO(Submission support): Post calls for submission support
+    /// Make a POST request with a JSON body
+    ///
+    /// # Arguments
+    /// - `url`: the [`Url`] to connect to
+    /// - `body`: The JSON encoded body of the request
+    ///
+    /// # Returns
+    /// - **On success**:
+    ///     - The HTTP status code
+    ///     - The data as a [`String`]
+    /// - **On failure**: The [`ClientError`] describing what went wrong
+    fn post(
+        &self,
+        url: &Url,
+        body: &str,
+    ) -> impl Future<Output = Result<(u16, Arc<String>), ClientError>>;
 }
 
 /// Error returned by [`Client`] implementation when a request fails
@@ -104,6 +137,14 @@ pub enum ClientError {
     #[error("Failed to validate an audit path: {0}")]
     AuditProofError(ProofValidationError),
 
+    /// A structure returned by the log could not be decoded
+    #[error("Failed to decode {0}: {1}")]
+    DecodingError(&'static str, CodecError),
+
+    /// The log returned no entries or more entries than requested
+    #[error("The log returned {received} entries (expected at most {expected})")]
+    UnexpectedEntryCount { expected: u64, received: u64 },
+
     // TODO: Remove
     /// The connection failed
     #[error("Failed to connect to host: {0}")]
@@ -117,6 +158,21 @@ pub enum ClientError {
     #[error("Request to {url} returned error: {code}: {msg}")]
     ResponseError { url: String, code: u16, msg: String },
 
+    /// The server returned 429 or 503 and asked to retry the request after some time
+    #[error("Request to {url} returned error: {code}, retry after {retry_after:?}")]
+    RetryAfter {
+        url: String,
+        code: u16,
+        retry_after: Duration,
+    },
+
+    /// The fetched log list was published before the log list that is currently used
+    #[error("The fetched log list from {received} is older than the current one from {current}")]
+    OutdatedLogList {
+        current: DateTime<Utc>,
+        received: DateTime<Utc>,
+    },
+
     /// Failed to parse a checkpoint note
     #[error("Failed parsing checkpoint: {0}")]
     Checkpoint(#[from] ParseCheckpointError),
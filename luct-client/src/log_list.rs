@@ -0,0 +1,180 @@
+use crate::{Client, ClientError};
+use luct_core::log_list::{LogListKey, v3::LogList};
+use url::Url;
+
+/// The [`Url`] at which Google publishes the log list used by Chrome
+pub const GOOGLE_LOG_LIST_URL: &str = "https://www.gstatic.com/ct/log_list/v3/log_list.json";
+
+/// The [`Url`] at which Google publishes the signature of the log list used by Chrome
+pub const GOOGLE_LOG_LIST_SIGNATURE_URL: &str =
+    "https://www.gstatic.com/ct/log_list/v3/log_list.sig";
+
+/// Fetches a [`LogList`] and verifies its detached signature
+///
+/// Any [`Client`] can be used, e.g. to fetch the list through an oblivious TLS proxy.
+#[derive(Debug, Clone)]
+pub struct LogListUpdater<C> {
+    client: C,
+    key: LogListKey,
+    url: Url,
+    signature_url: Url,
+}
+
+// TODO: Pin Google's log_list_pubkey.pem as the default key and test against a real
+// log_list.json/log_list.sig pair, once they are added to the testdata
+impl<C: Client> LogListUpdater<C> {
+    /// Create an updater for the log list published by Google, signed by `key`
+    pub fn new(client: C, key: LogListKey) -> Self {
+        Self {
+            client,
+            key,
+            url: Url::parse(GOOGLE_LOG_LIST_URL).unwrap(),
+            signature_url: Url::parse(GOOGLE_LOG_LIST_SIGNATURE_URL).unwrap(),
+        }
+    }
+
+    /// Fetch the log list and its signature from different [`Urls`](Url)
+    pub fn with_urls(mut self, url: Url, signature_url: Url) -> Self {
+        self.url = url;
+        self.signature_url = signature_url;
+        self
+    }
+
+    /// Fetch the log list and validate its signature
+    ///
+    /// Returns the parsed [`LogList`] together with the raw bytes, over which the signature was made,
+    /// and the signature itself, such that the list can be validated again after storing it.
+    pub async fn fetch(&self) -> Result<(LogList, Vec<u8>, Vec<u8>), ClientError> {
+        let (list, signature) = futures::future::try_join(
+            self.fetch_url(&self.url),
+            self.fetch_url(&self.signature_url),
+        )
+        .await?;
+
+        self.key
+            .validate(&list, &signature)
+            .map_err(|err| ClientError::SignatureValidationFailed("log list", err))?;
+
+        let log_list = serde_json::from_slice(&list)?;
+        Ok((log_list, list, signature))
+    }
+
+    /// Fetch the log list, if it is newer than the `current` one
+    ///
+    /// Returns `None`, if the fetched list was published at the same time as `current`.
+    /// A list that is older than `current` is rejected with [`ClientError::OutdatedLogList`],
+    /// such that an attacker can not roll back to a list with logs that have since been retired.
+    pub async fn update(
+        &self,
+        current: &LogList,
+    ) -> Result<Option<(LogList, Vec<u8>, Vec<u8>)>, ClientError> {
+        let (log_list, data, signature) = self.fetch().await?;
+
+        if log_list.timestamp() < current.timestamp() {
+            return Err(ClientError::OutdatedLogList {
+                current: current.timestamp(),
+                received: log_list.timestamp(),
+            });
+        }
+        if log_list.timestamp() == current.timestamp() {
+            tracing::debug!(
+                "Log list published at {} is up to date",
+                current.timestamp()
+            );
+            return Ok(None);
+        }
+
+        tracing::info!(
+            "Updated log list from {} to {}",
+            current.timestamp(),
+            log_list.timestamp()
+        );
+        Ok(Some((log_list, data, signature)))
+    }
+
+    async fn fetch_url(&self, url: &Url) -> Result<Vec<u8>, ClientError> {
+        let (status, data) = self.client.get_bin(url, &[]).await?;
+        if status != 200 {
+            return Err(ClientError::ResponseError {
+                url: url.to_string(),
+                code: status,
+                msg: String::from_utf8_lossy(&data).to_string(),
+            });
+        }
+
+        Ok(data.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replay::{Fixture, ReplayClient};
+    use luct_core::{SignatureValidationError, Signer};
+    use p256::ecdsa::SigningKey;
+
+    const LOG_LIST: &str = include_str!("../../testdata/all_logs_list.json");
+    const URL: &str = "https://log-list.luct.dev/log_list.json";
+    const SIGNATURE_URL: &str = "https://log-list.luct.dev/log_list.sig";
+
+    fn get_updater(list: &str, signature: Vec<u8>) -> LogListUpdater<ReplayClient> {
+        let signer = SigningKey::from_slice(&[0x42; 32]).unwrap();
+        let client = ReplayClient::default()
+            .with_fixture(Fixture::get(URL, &[], 200, list))
+            .with_fixture(Fixture::get(SIGNATURE_URL, &[], 200, signature));
+
+        LogListUpdater::new(client, LogListKey::from_der(signer.public_key()).unwrap())
+            .with_urls(Url::parse(URL).unwrap(), Url::parse(SIGNATURE_URL).unwrap())
+    }
+
+    fn sign(list: &str) -> Vec<u8> {
+        SigningKey::from_slice(&[0x42; 32])
+            .unwrap()
+            .sign(list.as_bytes())
+    }
+
+    /// Return the log list with its timestamp replaced by `timestamp`
+    fn with_timestamp(timestamp: &str) -> String {
+        LOG_LIST.replace("2025-12-13T12:54:29Z", timestamp)
+    }
+
+    #[tokio::test]
+    async fn fetch_signed_log_list() {
+        let updater = get_updater(LOG_LIST, sign(LOG_LIST));
+        let (log_list, data, signature) = updater.fetch().await.unwrap();
+        assert_eq!(data, LOG_LIST.as_bytes());
+        updater.key.validate(&data, &signature).unwrap();
+        assert_eq!(log_list.all_logs().len(), 247);
+
+        // A list that does not match the signature is rejected
+        let tampered = LOG_LIST.replace("Sycamore2026h1", "Sycamore2026h3");
+        let updater = get_updater(&tampered, sign(LOG_LIST));
+        assert!(matches!(
+            updater.fetch().await,
+            Err(ClientError::SignatureValidationFailed(
+                _,
+                SignatureValidationError::InvalidSignature
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn reject_older_log_list() {
+        let current: LogList = serde_json::from_str(LOG_LIST).unwrap();
+
+        let newer = with_timestamp("2026-01-01T00:00:00Z");
+        let updater = get_updater(&newer, sign(&newer));
+        let (log_list, _, _) = updater.update(&current).await.unwrap().unwrap();
+        assert!(log_list.timestamp() > current.timestamp());
+
+        let updater = get_updater(LOG_LIST, sign(LOG_LIST));
+        assert_eq!(updater.update(&current).await.unwrap(), None);
+
+        let older = with_timestamp("2025-01-01T00:00:00Z");
+        let updater = get_updater(&older, sign(&older));
+        assert!(matches!(
+            updater.update(&current).await,
+            Err(ClientError::OutdatedLogList { .. })
+        ));
+    }
+}
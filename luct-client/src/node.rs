@@ -0,0 +1,141 @@
+use crate::{Client, ClientError};
+use luct_core::{CtLog, SignedTreeHead};
+use serde::de::DeserializeOwned;
+use url::Url;
+
+/// Fetches the [`SignedTreeHeads`](SignedTreeHead) of logs, that a `luct-node` has collected
+///
+/// The node serves the latest STH of a log at `url?log_id=<log id>` and the history of all
+/// STHs at `url/history?log_id=<log id>`.
+/// All STHs are validated against the key of the log, such that the node does not need to be trusted.
+#[derive(Debug, Clone)]
+pub struct NodeSthClient<C> {
+    client: C,
+    url: Url,
+}
+
+impl<C: Client> NodeSthClient<C> {
+    /// Create a client for the STH endpoint of a node at `url`
+    pub fn new(client: C, url: Url) -> Self {
+        Self { client, url }
+    }
+
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Fetch the latest STH of `log`, that the node has observed
+    pub async fn get_sth(&self, log: &CtLog) -> Result<SignedTreeHead, ClientError> {
+        let sth: SignedTreeHead = self.fetch(&self.url, log).await?;
+        log.validate_sth(&sth)
+            .map_err(|err| ClientError::SignatureValidationFailed("STH", err))?;
+
+        Ok(sth)
+    }
+
+    /// Fetch all STHs of `log`, that the node has observed, ordered by their tree size
+    pub async fn get_sth_history(&self, log: &CtLog) -> Result<Vec<SignedTreeHead>, ClientError> {
+        let url = self.history_url()?;
+        let sths: Vec<SignedTreeHead> = self.fetch(&url, log).await?;
+        for sth in &sths {
+            log.validate_sth(sth)
+                .map_err(|err| ClientError::SignatureValidationFailed("STH", err))?;
+        }
+
+        Ok(sths)
+    }
+
+    fn history_url(&self) -> Result<Url, ClientError> {
+        let mut url = self.url.clone();
+        url.path_segments_mut()
+            .map_err(|_| ClientError::ConnectionError(format!("Invalid node url {}", self.url)))?
+            .pop_if_empty()
+            .push("history");
+        Ok(url)
+    }
+
+    async fn fetch<T: DeserializeOwned>(&self, url: &Url, log: &CtLog) -> Result<T, ClientError> {
+        let log_id = log.log_id().to_string();
+        let (status, response) = self.client.get(url, &[("log_id", &log_id)]).await?;
+        if status != 200 {
+            return Err(ClientError::ResponseError {
+                url: url.to_string(),
+                code: status,
+                msg: response.to_string(),
+            });
+        }
+
+        Ok(serde_json::from_str(&response)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replay::{Fixture, ReplayClient};
+    use luct_core::{
+        SignatureValidationError,
+        v1::{self, responses::GetSthResponse},
+    };
+
+    const ARGON2025H2: &str = r#"{
+        "description": "Google Argon",
+        "url": "https://ct.googleapis.com/logs/us1/argon2025h2/",
+        "key": "MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEr+TzlCzfpie1/rJhgxnIITojqKk9VK+8MZoc08HjtsLzD8e5yjsdeWVhIiWCVk6Y6KomKTYeKGBv6xVu93zQug==",
+        "mmd": 86400
+    }"#;
+    const ARGON2025H2_STH: &str = r#"{
+        "tree_size": 1329315675,
+        "timestamp": 1751738269891,
+        "sha256_root_hash": "NEFqldTJt2+wE/aaaQuXeADdWVV8IGbwhLublI7QaMY=",
+        "tree_head_signature": "BAMARjBEAiA9rna9/avaKTald7hHrldq8FfB4FDAaNyB44pplv71agIgeD0jj2AhLnvlaWavfFZ3BdUglauz36rFpGLYuLBs/O8="
+    }"#;
+    const NODE_URL: &str = "https://node.luct.dev/sth";
+
+    fn get_sth() -> SignedTreeHead {
+        let response: GetSthResponse = serde_json::from_str(ARGON2025H2_STH).unwrap();
+        v1::SignedTreeHead::try_from(response).unwrap().into()
+    }
+
+    fn get_client(log: &CtLog, url: &str, body: &str) -> NodeSthClient<ReplayClient> {
+        let log_id = log.log_id().to_string();
+        let client = ReplayClient::default().with_fixture(Fixture::get(
+            url,
+            &[("log_id", &log_id)],
+            200,
+            body,
+        ));
+        NodeSthClient::new(client, Url::parse(NODE_URL).unwrap())
+    }
+
+    #[tokio::test]
+    async fn fetch_sth_from_node() {
+        let log = CtLog::new(serde_json::from_str(ARGON2025H2).unwrap()).unwrap();
+        let sth = get_sth();
+
+        let body = serde_json::to_string(&sth).unwrap();
+        let client = get_client(&log, NODE_URL, &body);
+        assert_eq!(client.get_sth(&log).await.unwrap(), sth);
+
+        let body = serde_json::to_string(&[&sth]).unwrap();
+        let client = get_client(&log, &format!("{NODE_URL}/history"), &body);
+        assert_eq!(client.get_sth_history(&log).await.unwrap(), vec![sth]);
+    }
+
+    #[tokio::test]
+    async fn reject_forged_sth_from_node() {
+        let log = CtLog::new(serde_json::from_str(ARGON2025H2).unwrap()).unwrap();
+        let sth = serde_json::to_string(&get_sth())
+            .unwrap()
+            .replace("1329315675", "1329315676");
+
+        let client = get_client(&log, NODE_URL, &sth);
+        assert!(matches!(
+            client.get_sth(&log).await,
+            Err(ClientError::SignatureValidationFailed(
+                _,
+                SignatureValidationError::InvalidSignature
+            ))
+        ));
+    }
+}
@@ -1,2 +1,100 @@
+use crate::{Client, ClientError, CtClient};
+use luct_core::{
+    MerkleTreeLeaf, SignedCertificateTimestamp, SignedTreeHead, Version,
+    tree::{AuditProof, ConsistencyProof},
+};
+
 mod tiling;
 mod v1;
+mod v2;
+
+/// Version agnostic calls
+///
+/// These calls dispatch to the call of the version of the log.
+/// Passing in structures of a different version than the log results in [`ClientError::UnsupportedVersion`].
+impl<C: Client> CtClient<C> {
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_sth(&self) -> Result<SignedTreeHead, ClientError> {
+        match self.log().config().version() {
+            Version::V1 => Ok(self.get_sth_v1().await?.into()),
+            Version::V2 => Ok(self.get_sth_v2().await?.into()),
+        }
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn check_consistency(
+        &self,
+        first: &SignedTreeHead,
+        second: &SignedTreeHead,
+    ) -> Result<(), ClientError> {
+        match (first, second) {
+            (SignedTreeHead::V1(first), SignedTreeHead::V1(second)) => {
+                self.check_consistency_v1(first, second).await
+            }
+            (SignedTreeHead::V2(first), SignedTreeHead::V2(second)) => {
+                self.check_consistency_v2(first, second).await
+            }
+            _ => Err(ClientError::UnsupportedVersion),
+        }
+    }
+
+    /// Fetch the consistency proof between two tree heads without validating it
+    ///
+    /// `first` must be the smaller tree head.
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_consistency_proof(
+        &self,
+        first: &SignedTreeHead,
+        second: &SignedTreeHead,
+    ) -> Result<ConsistencyProof, ClientError> {
+        match (first, second) {
+            (SignedTreeHead::V1(first), SignedTreeHead::V1(second)) => {
+                self.get_consistency_proof_v1(first, second).await
+            }
+            (SignedTreeHead::V2(first), SignedTreeHead::V2(second)) => {
+                self.get_consistency_proof_v2(first, second).await
+            }
+            _ => Err(ClientError::UnsupportedVersion),
+        }
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn check_sct_inclusion(
+        &self,
+        sct: &SignedCertificateTimestamp,
+        sth: &SignedTreeHead,
+        leaf: &MerkleTreeLeaf,
+    ) -> Result<u64, ClientError> {
+        match (sct, sth, leaf) {
+            (
+                SignedCertificateTimestamp::V1(sct),
+                SignedTreeHead::V1(sth),
+                MerkleTreeLeaf::V1(leaf),
+            ) => self.check_sct_inclusion_v1(sct, sth, leaf).await,
+            (
+                SignedCertificateTimestamp::V2(sct),
+                SignedTreeHead::V2(sth),
+                MerkleTreeLeaf::V2(leaf),
+            ) => self.check_sct_inclusion_v2(sct, sth, leaf).await,
+            _ => Err(ClientError::UnsupportedVersion),
+        }
+    }
+
+    /// Fetch the inclusion proof of `leaf` against `sth` without validating it
+    #[tracing::instrument(level = "trace")]
+    pub async fn fetch_audit_proof(
+        &self,
+        sth: &SignedTreeHead,
+        leaf: &MerkleTreeLeaf,
+    ) -> Result<AuditProof, ClientError> {
+        match (sth, leaf) {
+            (SignedTreeHead::V1(sth), MerkleTreeLeaf::V1(leaf)) => {
+                self.fetch_audit_proof_v1(sth, leaf).await
+            }
+            (SignedTreeHead::V2(sth), MerkleTreeLeaf::V2(leaf)) => {
+                self.fetch_audit_proof_v2(sth, leaf).await
+            }
+            _ => Err(ClientError::UnsupportedVersion),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{Client, ClientError, CtClient};
 use luct_core::{
-    tiling::{Checkpoint, Tile, TileId, TilingError},
+    Certificate, Fingerprint,
+    tiling::{Checkpoint, DataTile, DataTileId, Tile, TileId, TilingError, issuer_url},
     v1::SignedTreeHead,
 };
 use url::Url;
@@ -22,6 +23,13 @@ impl<C: Client> CtClient<C> {
             .validate_checkpoint(&checkpoint)
             .map_err(|err| ClientError::SignatureValidationFailed("checkpoint STH", err))?;
 
+        // Validate the witness cosignatures
+        if let Some(witness_policy) = &self.witness_policy {
+            witness_policy.validate(&checkpoint).map_err(|err| {
+                ClientError::SignatureValidationFailed("checkpoint cosignatures", err)
+            })?;
+        }
+
         tracing::debug!(
             "fetched and validated checkpoint: {:?} from url {}",
             sth,
@@ -34,14 +42,14 @@ impl<C: Client> CtClient<C> {
     #[tracing::instrument(level = "trace")]
     pub async fn get_tile(&self, mut tile_id: TileId) -> Result<Tile, ClientError> {
         self.assert_v1()?;
-        let url = self.get_url(&tile_id.as_url())?;
+        let mut url = self.get_url(&tile_id.as_url())?;
 
         let (mut status, mut response) = self.client.get_bin(&url, &[]).await?;
 
         // If the partial tile can't be found, we retry with the full tile
         if status == 404 && tile_id.is_partial() {
             tile_id = tile_id.into_unpartial();
-            let url = self.get_url(&tile_id.as_url())?;
+            url = self.get_url(&tile_id.as_url())?;
             (status, response) = self.client.get_bin(&url, &[]).await?;
         };
 
@@ -52,8 +60,47 @@ impl<C: Client> CtClient<C> {
         Ok(tile_id.with_data(response)?)
     }
 
-    // TODO: Get Data tile
-    // TODO: Get issuer
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_data_tile(&self, mut tile_id: DataTileId) -> Result<DataTile, ClientError> {
+        self.assert_v1()?;
+        let mut url = self.get_url(&tile_id.as_url())?;
+
+        let (mut status, mut response) = self.client.get_bin(&url, &[]).await?;
+
+        // If the partial tile can't be found, we retry with the full tile
+        if status == 404 && tile_id.is_partial() {
+            tile_id = tile_id.into_unpartial();
+            url = self.get_url(&tile_id.as_url())?;
+            (status, response) = self.client.get_bin(&url, &[]).await?;
+        };
+
+        self.check_status_binary(&url, status, &response)?;
+
+        tracing::trace!("fetched data tile {:?}, from url: {}", tile_id, url);
+
+        Ok(tile_id.with_data(response))
+    }
+
+    /// Fetch the issuer certificate with the given [`Fingerprint`] from the log
+    ///
+    /// Fails, if the certificate returned by the log does not hash to the fingerprint.
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_issuer(&self, fingerprint: &Fingerprint) -> Result<Certificate, ClientError> {
+        self.assert_v1()?;
+        let url = self.get_url(&issuer_url(fingerprint))?;
+
+        let (status, response) = self.client.get_bin(&url, &[]).await?;
+        self.check_status_binary(&url, status, &response)?;
+
+        let issuer = Certificate::from_der(&response).map_err(|_| TilingError::MalformedIssuer)?;
+        if &issuer.fingerprint_sha256() != fingerprint {
+            return Err(TilingError::IssuerMismatch(fingerprint.clone()).into());
+        }
+
+        tracing::trace!("fetched issuer {}, from url: {}", fingerprint, url);
+
+        Ok(issuer)
+    }
 
     fn get_url(&self, path: &str) -> Result<Url, ClientError> {
         let url = self
@@ -65,12 +112,23 @@ impl<C: Client> CtClient<C> {
         Ok(url.join(path).map_err(|_| TilingError::NonTilingLog)?)
     }
 }
-#[cfg(all(test, feature = "reqwest"))]
+
+#[cfg(test)]
 mod tests {
     use super::*;
-    use crate::reqwest::ReqwestClient;
-    use luct_core::{CtLogConfig, tree::NodeKey};
+    use crate::replay::{Fixture, ReplayClient};
+    use luct_core::{CtLogConfig, store::Hashable, tree::NodeKey};
+    use std::sync::Arc;
+
+    const DATA_TILE: &[u8] = include_bytes!("../../../testdata/data-tile-000.p2.bin");
+    const GOOGLE_ISSUER: &[u8] = include_bytes!("../../../testdata/google-issuer.der");
+    const GOOGLE_ISSUER_FINGERPRINT: &str =
+        "e6fe22bf45e4f0d3b85c59e02c0f495418e1eb8d3210f788d48cd5e1cb547cd4";
 
+    const ARCHE2026H1_CHECKPOINT: &str =
+        include_str!("../../../testdata/arche2026h1-signed-note.txt");
+
+    const TILE_URL: &str = "https://storage.googleapis.com/static-ct-staging-arche2026h1-bucket/";
     const ARCHE2026H1: &str = "{
           \"description\": \"Google 'Arche2026h1' log\",
           \"key\": \"MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEZ+3YKoZTMruov4cmlImbk4MckBNzEdCyMuHlwGgJ8BUrzFLlR5U0619xDDXIXespkpBgCNVQAkhMTTXakM6KMg==\",
@@ -81,7 +139,7 @@ mod tests {
 
     #[test]
     fn get_url() {
-        let client = get_client();
+        let client = get_client(ReplayClient::default());
         let url = client
             .get_url(
                 &TileId::from_node_key(&NodeKey::leaf(1), 1000)
@@ -90,33 +148,204 @@ mod tests {
             )
             .unwrap();
 
-        assert_eq!(
-            url.to_string(),
-            "https://storage.googleapis.com/static-ct-staging-arche2026h1-bucket/tile/0/000"
-        )
+        assert_eq!(url.to_string(), format!("{TILE_URL}tile/0/000"))
     }
 
     #[tokio::test]
-    #[ignore = "Makes an HTTP call, for manual testing only"]
     async fn get_checkpoint() {
-        let client = get_client();
-        let _ = client.get_checkpoint().await.unwrap();
+        let client = get_client(ReplayClient::default().with_fixture(Fixture::get(
+            &format!("{TILE_URL}checkpoint"),
+            &[],
+            200,
+            ARCHE2026H1_CHECKPOINT,
+        )));
+
+        let sth = client.get_checkpoint().await.unwrap();
+        assert_eq!(sth.tree_size(), 1822167730);
     }
 
     #[tokio::test]
-    #[ignore = "Makes an HTTP call, for manual testing only"]
     async fn get_tile() {
-        let client = get_client();
+        // The level 0 tile contains the hashes of the leaves of the data tile
+        let tile = DataTileId::from_index(0, 2)
+            .unwrap()
+            .with_data(Arc::new(DATA_TILE.to_vec()))
+            .leaves()
+            .unwrap()
+            .iter()
+            .flat_map(|leaf| leaf.as_merkle_tree_leaf().hash())
+            .collect::<Vec<_>>();
+        let client = get_client(ReplayClient::default().with_fixture(Fixture::get(
+            &format!("{TILE_URL}tile/0/000.p/2"),
+            &[],
+            200,
+            tile,
+        )));
+
+        let tile = client
+            .get_tile(TileId::from_node_key(&NodeKey::leaf(1), 2).unwrap())
+            .await
+            .unwrap();
+        assert!(tile.id().is_partial());
+        assert_eq!(tile.recompute_node_keys().len(), 3);
+    }
 
-        let _ = client
-            .get_tile(TileId::from_node_key(&NodeKey::leaf(1), 1000).unwrap())
+    #[tokio::test]
+    async fn get_data_tile() {
+        let client = get_client(ReplayClient::default().with_fixture(Fixture::get(
+            &format!("{TILE_URL}tile/data/000.p/2"),
+            &[],
+            200,
+            DATA_TILE,
+        )));
+        let data_tile = client
+            .get_data_tile(DataTileId::from_index(0, 2).unwrap())
             .await
             .unwrap();
+
+        assert!(data_tile.id().is_partial());
+        let leaves = data_tile.leaves().unwrap();
+        assert_eq!(leaves.len(), 2);
+        assert_eq!(leaves[1].leaf_index(), Some(1));
+    }
+
+    #[tokio::test]
+    async fn get_data_tile_fallback() {
+        // The partial tile is gone, since the log has grown in the meantime
+        let partial_tile = Fixture::get(&format!("{TILE_URL}tile/data/000.p/2"), &[], 404, "");
+        let client = get_client(
+            ReplayClient::default()
+                .with_fixture(partial_tile.clone())
+                .with_fixture(Fixture::get(
+                    &format!("{TILE_URL}tile/data/000"),
+                    &[],
+                    200,
+                    DATA_TILE,
+                )),
+        );
+        let data_tile = client
+            .get_data_tile(DataTileId::from_index(0, 2).unwrap())
+            .await
+            .unwrap();
+        assert!(!data_tile.id().is_partial());
+
+        let client = get_client(
+            ReplayClient::default()
+                .with_fixture(partial_tile)
+                .with_fixture(Fixture::get(
+                    &format!("{TILE_URL}tile/data/000"),
+                    &[],
+                    404,
+                    "",
+                )),
+        );
+        // The error reports the url of the full tile, which was requested last
+        assert!(matches!(
+            client
+                .get_data_tile(DataTileId::from_index(0, 2).unwrap())
+                .await,
+            Err(ClientError::ResponseError { code: 404, url, .. })
+                if url == format!("{TILE_URL}tile/data/000")
+        ));
+    }
+
+    #[tokio::test]
+    async fn get_issuer() {
+        let path = format!("{TILE_URL}issuer/{GOOGLE_ISSUER_FINGERPRINT}");
+        let client = get_client(ReplayClient::default().with_fixture(Fixture::get(
+            &path,
+            &[],
+            200,
+            GOOGLE_ISSUER,
+        )));
+
+        let fingerprint = get_fingerprint(GOOGLE_ISSUER_FINGERPRINT);
+        let issuer = client.get_issuer(&fingerprint).await.unwrap();
+        assert_eq!(issuer.fingerprint_sha256(), fingerprint);
+        assert_eq!(issuer.get_subject_name(), "WR2");
+
+        // The leaves of the data tile reference the issuer
+        let data_tile = DataTileId::from_index(0, 2)
+            .unwrap()
+            .with_data(Arc::new(DATA_TILE.to_vec()));
+        assert_eq!(
+            data_tile.leaves().unwrap()[1].certificate_chain(),
+            &[fingerprint]
+        );
+    }
+
+    #[tokio::test]
+    async fn reject_mismatching_issuer() {
+        let other_fingerprint = get_fingerprint(&"ab".repeat(32));
+        let path = format!("{TILE_URL}issuer/{}", "ab".repeat(32));
+
+        let client = get_client(ReplayClient::default().with_fixture(Fixture::get(
+            &path,
+            &[],
+            200,
+            GOOGLE_ISSUER,
+        )));
+        assert!(matches!(
+            client.get_issuer(&other_fingerprint).await,
+            Err(ClientError::TilingError(TilingError::IssuerMismatch(fingerprint)))
+                if fingerprint == other_fingerprint
+        ));
+
+        let client = get_client(ReplayClient::default().with_fixture(Fixture::get(
+            &path,
+            &[],
+            200,
+            &GOOGLE_ISSUER[1..],
+        )));
+        assert!(matches!(
+            client.get_issuer(&other_fingerprint).await,
+            Err(ClientError::TilingError(TilingError::MalformedIssuer))
+        ));
+    }
+
+    #[tokio::test]
+    async fn submit_to_tiling_log() {
+        const GOUDA2026H1: &str = "{
+            \"description\": \"IPng Networks 'Gouda2026h1'\",
+            \"key\": \"MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAER6wvqVwhf5isuCtwSfNjTOrqwZg0vZuIMP7xk8fPmJfaFZCte1ptQiqNhRMCtqIgJvDcJyjkGVI8i44vxL877A==\",
+            \"url\": \"https://gouda2026h1.log.ct.ipng.ch/\",
+            \"tile_url\": \"https://gouda2026h1.mon.ct.ipng.ch/\",
+            \"mmd\": 60
+        }";
+        const ADD_PRE_CHAIN_RESPONSE: &str =
+            include_str!("../../../testdata/gouda2026h1-add-pre-chain.json");
+        const CERT_CHAIN_GOOGLE_COM: &str = include_str!("../../../testdata/google-chain.pem");
+
+        // Submissions go to the submission url, not the tile url.
+        // The log answers with the SCT it issued for the precertificate of geomys.org
+        let config: CtLogConfig = serde_json::from_str(GOUDA2026H1).unwrap();
+        let client = CtClient::new(
+            config,
+            ReplayClient::default().with_fixture(Fixture::post(
+                "https://gouda2026h1.log.ct.ipng.ch/ct/v1/add-chain",
+                200,
+                ADD_PRE_CHAIN_RESPONSE,
+            )),
+        )
+        .unwrap();
+        let chain = luct_core::CertificateChain::from_pem_chain(CERT_CHAIN_GOOGLE_COM).unwrap();
+
+        // The SCT is issued by this log, but does not cover the submitted chain
+        assert!(matches!(
+            client.add_chain_v1(&chain).await,
+            Err(ClientError::SignatureValidationFailed(
+                "SCT",
+                luct_core::SignatureValidationError::InvalidSignature
+            ))
+        ));
+    }
+
+    fn get_fingerprint(fingerprint: &str) -> Fingerprint {
+        Fingerprint(hex::decode(fingerprint).unwrap().try_into().unwrap())
     }
 
-    fn get_client() -> CtClient<ReqwestClient> {
+    fn get_client(client: ReplayClient) -> CtClient<ReplayClient> {
         let config: CtLogConfig = serde_json::from_str(ARCHE2026H1).unwrap();
-        let client = ReqwestClient::new("luct-test");
-        CtClient::new(config, client)
+        CtClient::new(config, client).unwrap()
     }
 }
@@ -6,13 +6,14 @@
 use crate::{Client, ClientError, CtClient};
 use base64::{Engine, prelude::BASE64_STANDARD};
 use luct_core::{
-    Certificate, Version,
+    Certificate, CertificateChain, SignatureValidationError, Version,
     store::Hashable,
     tree::{AuditProof, ConsistencyProof, TreeHead},
     v1::{
-        MerkleTreeLeaf, SignedCertificateTimestamp, SignedTreeHead,
+        LogEntryChain, MerkleTreeLeaf, SignedCertificateTimestamp, SignedTreeHead,
         responses::{
-            GetProofByHashResponse, GetRootsResponse, GetSthConsistencyResponse, GetSthResponse,
+            AddChainRequest, AddChainResponse, GetEntriesResponse, GetProofByHashResponse,
+            GetRootsResponse, GetSthConsistencyResponse, GetSthResponse,
         },
     },
 };
@@ -77,10 +78,40 @@ impl<C: Client> CtClient<C> {
             Ordering::Greater => (second, first),
         };
 
+        let proof = self.get_consistency_proof_v1(first, second).await?;
+
+        let first = TreeHead::from(first);
+        let second = TreeHead::from(second);
+
+        // Validate consistency proof
+        proof
+            .validate(&first, &second)
+            .map_err(ClientError::ConsistencyProofError)?;
+
+        tracing::debug!(
+            "fetched and validated consistency proof for tree sizes {} to {}",
+            first.tree_size(),
+            second.tree_size()
+        );
+
+        Ok(())
+    }
+
+    /// Fetch the consistency proof from `first` to `second` without validating it
+    ///
+    /// `first` must be the smaller tree head.
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_consistency_proof_v1(
+        &self,
+        first: &SignedTreeHead,
+        second: &SignedTreeHead,
+    ) -> Result<ConsistencyProof, ClientError> {
+        self.assert_v1()?;
+
         let first_idx = first.tree_size().to_string();
         let second_idx = second.tree_size().to_string();
 
-        // Fetch and parse inclusion proof
+        // Fetch and parse consistency proof
         let url = self.get_full_v1_url().join("get-sth-consistency").unwrap();
         let (status, response) = self
             .client
@@ -92,30 +123,51 @@ impl<C: Client> CtClient<C> {
         let proof =
             ConsistencyProof::try_from(response).map_err(ClientError::ConsistencyProofError)?;
 
-        let first = TreeHead::from(first);
-        let second = TreeHead::from(second);
+        Ok(proof)
+    }
 
-        // Validate inclusion proof
-        proof
-            .validate(&first, &second)
-            .map_err(ClientError::ConsistencyProofError)?;
+    #[tracing::instrument(level = "trace")]
+    pub async fn check_sct_inclusion_v1(
+        &self,
+        sct: &SignedCertificateTimestamp,
+        sth: &SignedTreeHead,
+        leaf: &MerkleTreeLeaf,
+    ) -> Result<u64, ClientError> {
+        let proof = self.get_audit_proof_v1(sth, leaf).await?;
 
         tracing::debug!(
-            "fetched and validated consistency proof for tree sizes {} to {}",
-            first.tree_size(),
-            second.tree_size()
+            "fetched and validated embedded SCT {:?} for tree size {}",
+            sct,
+            sth.tree_size()
         );
 
-        Ok(())
+        Ok(proof.index())
     }
 
     #[tracing::instrument(level = "trace")]
-    pub async fn check_sct_inclusion_v1(
+    pub async fn get_audit_proof_v1(
         &self,
-        sct: &SignedCertificateTimestamp,
         sth: &SignedTreeHead,
         leaf: &MerkleTreeLeaf,
-    ) -> Result<u64, ClientError> {
+    ) -> Result<AuditProof, ClientError> {
+        let proof = self.fetch_audit_proof_v1(sth, leaf).await?;
+        let tree_head = TreeHead::from(sth);
+
+        // Validate inclusion proof
+        proof
+            .validate(&tree_head, leaf)
+            .map_err(ClientError::AuditProofError)?;
+
+        Ok(proof)
+    }
+
+    /// Fetch the inclusion proof of `leaf` against `sth` without validating it
+    #[tracing::instrument(level = "trace")]
+    pub async fn fetch_audit_proof_v1(
+        &self,
+        sth: &SignedTreeHead,
+        leaf: &MerkleTreeLeaf,
+    ) -> Result<AuditProof, ClientError> {
         self.assert_v1()?;
 
         let leaf_hash = leaf.hash();
@@ -132,21 +184,44 @@ impl<C: Client> CtClient<C> {
         self.check_status(&url, status, &response)?;
 
         let response: GetProofByHashResponse = serde_json::from_str(&response)?;
-        let proof = AuditProof::try_from(response).map_err(ClientError::AuditProofError)?;
-        let tree_head = TreeHead::from(sth);
+        AuditProof::try_from(response).map_err(ClientError::AuditProofError)
+    }
 
-        // Validate inclusion proof
-        proof
-            .validate(&tree_head, leaf)
-            .map_err(ClientError::AuditProofError)?;
+    /// Fetch the entries `start..=end` of the log
+    ///
+    /// Logs may return fewer entries than requested, so the result can be shorter than the range.
+    /// It contains at least one entry though.
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_entries_v1(
+        &self,
+        start: u64,
+        end: u64,
+    ) -> Result<Vec<(MerkleTreeLeaf, LogEntryChain)>, ClientError> {
+        self.assert_v1()?;
 
-        tracing::debug!(
-            "fetched and validated embedded SCT {:?} for tree size {}",
-            sct,
-            sth.tree_size()
-        );
+        let expected = end.saturating_sub(start) + 1;
+        let start = start.to_string();
+        let end = end.to_string();
 
-        Ok(proof.index())
+        let url = self.get_full_v1_url().join("get-entries").unwrap();
+        let (status, response) = self
+            .client
+            .get(&url, &[("start", &start), ("end", &end)])
+            .await?;
+        self.check_status(&url, status, &response)?;
+
+        let response: GetEntriesResponse = serde_json::from_str(&response)?;
+        let entries = Vec::<(MerkleTreeLeaf, LogEntryChain)>::try_from(&response)
+            .map_err(|err| ClientError::DecodingError("entries", err))?;
+
+        let received = entries.len() as u64;
+        if received == 0 || received > expected {
+            return Err(ClientError::UnexpectedEntryCount { expected, received });
+        }
+
+        tracing::debug!("fetched {} entries from url {}", received, url);
+
+        Ok(entries)
     }
 
     #[tracing::instrument(level = "trace")]
@@ -164,6 +239,72 @@ impl<C: Client> CtClient<C> {
         Ok((&response).into())
     }
 
+    /// Submit a [`CertificateChain`] to the log
+    ///
+    /// The returned [SCT](SignedCertificateTimestamp) is validated against the chain.
+    #[tracing::instrument(level = "trace")]
+    pub async fn add_chain_v1(
+        &self,
+        chain: &CertificateChain,
+    ) -> Result<SignedCertificateTimestamp, ClientError> {
+        self.submit_chain_v1("add-chain", chain, false).await
+    }
+
+    /// Submit a [`CertificateChain`] starting with a precertificate to the log
+    ///
+    /// The returned [SCT](SignedCertificateTimestamp) is validated against the chain.
+    #[tracing::instrument(level = "trace")]
+    pub async fn add_pre_chain_v1(
+        &self,
+        chain: &CertificateChain,
+    ) -> Result<SignedCertificateTimestamp, ClientError> {
+        self.submit_chain_v1("add-pre-chain", chain, true).await
+    }
+
+    async fn submit_chain_v1(
+        &self,
+        endpoint: &str,
+        chain: &CertificateChain,
+        as_precert: bool,
+    ) -> Result<SignedCertificateTimestamp, ClientError> {
+        self.assert_v1()?;
+
+        let url = self
+            .log()
+            .config()
+            .submission_url()
+            .join("ct/v1/")
+            .and_then(|url| url.join(endpoint))
+            .unwrap();
+        let request = serde_json::to_string(&AddChainRequest::from(chain))?;
+
+        // Submit the chain and parse the SCT
+        let (status, response) = self.client.post(&url, &request).await?;
+        self.check_status(&url, status, &response)?;
+        let response: AddChainResponse = serde_json::from_str(&response)?;
+        let sct = SignedCertificateTimestamp::try_from(&response)
+            .map_err(|err| ClientError::DecodingError("SCT", err))?;
+
+        // Validate the SCT against the submitted chain
+        if &sct.log_id() != self.log.log_id() {
+            return Err(ClientError::SignatureValidationFailed(
+                "SCT",
+                SignatureValidationError::LogIdMismatch,
+            ));
+        }
+        self.log
+            .validate_sct_v1(chain, &sct, as_precert)
+            .map_err(|err| ClientError::SignatureValidationFailed("SCT", err))?;
+
+        tracing::debug!(
+            "submitted chain and validated SCT {:?} from url {}",
+            sct,
+            url
+        );
+
+        Ok(sct)
+    }
+
     fn get_full_v1_url(&self) -> Url {
         let base_url = self.log().config().fetch_url();
         base_url.join("ct/v1/").unwrap()
@@ -172,23 +313,25 @@ impl<C: Client> CtClient<C> {
     pub(crate) fn assert_v1(&self) -> Result<(), ClientError> {
         match self.log().config().version() {
             Version::V1 => Ok(()),
-            #[allow(unreachable_patterns)]
             _ => Err(ClientError::UnsupportedVersion),
         }
     }
 }
 
-// TODO: Low level get entries call
-
-#[cfg(all(test, feature = "reqwest"))]
+#[cfg(test)]
 mod tests {
     use super::*;
-    use crate::reqwest::ReqwestClient;
-    use luct_core::{
-        CertificateChain, CtLogConfig,
-        v1::{SignedTreeHead, responses::GetSthResponse},
-    };
+    use crate::replay::{Fixture, ReplayClient};
+    use luct_core::{CtLogConfig, v1::responses::GetSthResponse};
 
+    const ARGON2025H1: &str = "{
+        \"description\": \"Google Argon\",
+        \"version\": 1,
+        \"url\": \"https://ct.googleapis.com/logs/us1/argon2025h1/\",
+        \"key\": \"MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEIIKh+WdoqOTblJji4WiH5AltIDUzODyvFKrXCBjw/Rab0/98J4LUh7dOJEY7+66+yCNSICuqRAX+VPnV8R1Fmg==\",
+        \"mmd\": 86400
+        }
+    ";
     const ARGON2025H2: &str = "{
         \"description\": \"Google Argon\",
         \"version\": 1,
@@ -197,54 +340,126 @@ mod tests {
         \"mmd\": 86400
         }
     ";
-
-    const ARGON2025H2_STH_0506: &str = "{
-        \"tree_size\":1329315675,
-        \"timestamp\":1751738269891,
-        \"sha256_root_hash\":\"NEFqldTJt2+wE/aaaQuXeADdWVV8IGbwhLublI7QaMY=\",
-        \"tree_head_signature\":\"BAMARjBEAiA9rna9/avaKTald7hHrldq8FfB4FDAaNyB44pplv71agIgeD0jj2AhLnvlaWavfFZ3BdUglauz36rFpGLYuLBs/O8=\"
+    const ARGON2025H1_REPLAY: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../testdata/replay/argon2025h1"
+    );
+    const ARGON2025H2_REPLAY: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../testdata/replay/argon2025h2"
+    );
+
+    const ARGON2025H1_STH2806: &str = "{
+        \"tree_size\":1425614114,
+        \"timestamp\":1751114416696,
+        \"sha256_root_hash\":\"LHtW79pwJohJF5Yn/tyozEroOnho4u3JAGn7WeHSR54=\",
+        \"tree_head_signature\":\"BAMARzBFAiEAg4w8LlTFKd3KL6lo5Zde9OupHYNN0DDk8U54PenirI4CIHL8ucpkJw5zFLh8UvLA+Zf+f8Ms+tLsVtzHuqnO0qjm\"
     }";
+
+    const GET_ROOTS_URL: &str = "https://ct.googleapis.com/logs/us1/argon2025h2/ct/v1/get-roots";
+    const GET_ROOTS_RESPONSE: &str = include_str!("../../../testdata/argon2025h2-get-roots.json");
+    const ADD_PRE_CHAIN_URL: &str =
+        "https://ct.googleapis.com/logs/us1/argon2025h2/ct/v1/add-pre-chain";
+    const ADD_PRE_CHAIN_RESPONSE: &str =
+        include_str!("../../../testdata/argon2025h2-add-pre-chain.json");
     const CERT_CHAIN_GOOGLE_COM: &str = include_str!("../../../testdata/google-chain.pem");
+    const PRECERT_CHAIN_GOOGLE_COM: &str =
+        include_str!("../../../testdata/google-precert-chain.pem");
 
     #[tokio::test]
-    #[ignore = "Makes an HTTP call, for manual testing only"]
     async fn sth_consistency() {
-        let client = get_client();
+        let client = get_client(ARGON2025H1, ReplayClient::load(ARGON2025H1_REPLAY).unwrap());
 
-        let old_sth: GetSthResponse = serde_json::from_str(ARGON2025H2_STH_0506).unwrap();
+        let old_sth: GetSthResponse = serde_json::from_str(ARGON2025H1_STH2806).unwrap();
         let old_sth = SignedTreeHead::try_from(old_sth).unwrap();
 
-        client.update_sth_v1(Some(&old_sth)).await.unwrap();
+        let new_sth = client.update_sth_v1(Some(&old_sth)).await.unwrap();
+        assert_eq!(new_sth.tree_size(), 1425633154);
     }
 
     #[tokio::test]
-    #[ignore = "Makes an HTTP call, for manual testing only"]
     async fn sct_inclusion() {
-        let client = get_client();
+        let client = get_client(ARGON2025H2, ReplayClient::load(ARGON2025H2_REPLAY).unwrap());
 
         let cert = CertificateChain::from_pem_chain(CERT_CHAIN_GOOGLE_COM).unwrap();
         cert.verify_chain().unwrap();
         let scts = cert.cert().extract_scts_v1().unwrap();
 
         let sth = client.get_sth_v1().await.unwrap();
-        client
+        let index = client
             .check_sct_inclusion_v1(&scts[0], &sth, &cert.as_leaf_v1(&scts[0], true).unwrap())
             .await
             .unwrap();
-    }
-
-    fn get_client() -> CtClient<ReqwestClient> {
-        let config: CtLogConfig = serde_json::from_str(ARGON2025H2).unwrap();
-        let client = ReqwestClient::new("luct-test");
-        CtClient::new(config, client)
+        assert_eq!(index, 912557348);
+
+        // The final certificate is not included in the log
+        assert!(
+            client
+                .check_sct_inclusion_v1(&scts[0], &sth, &cert.as_leaf_v1(&scts[0], false).unwrap())
+                .await
+                .is_err()
+        );
     }
 
     #[tokio::test]
-    #[ignore = "Makes an HTTP call, for manual testing only"]
     async fn get_roots() {
-        let client = get_client();
+        let client = get_client(
+            ARGON2025H2,
+            ReplayClient::default().with_fixture(Fixture::get(
+                GET_ROOTS_URL,
+                &[],
+                200,
+                GET_ROOTS_RESPONSE,
+            )),
+        );
 
         let roots = client.get_roots_v1().await.unwrap();
-        assert!(!roots.is_empty())
+        assert_eq!(roots.len(), 663);
+    }
+
+    #[tokio::test]
+    async fn add_pre_chain() {
+        let client = get_client(
+            ARGON2025H2,
+            ReplayClient::default().with_fixture(Fixture::post(
+                ADD_PRE_CHAIN_URL,
+                200,
+                ADD_PRE_CHAIN_RESPONSE,
+            )),
+        );
+        let chain = CertificateChain::from_pem_chain(PRECERT_CHAIN_GOOGLE_COM).unwrap();
+
+        let sct = client.add_pre_chain_v1(&chain).await.unwrap();
+        assert_eq!(&sct.log_id(), client.log().log_id());
+        assert_eq!(sct.timestamp(), 1748856933195);
+    }
+
+    #[tokio::test]
+    async fn reject_sct_of_other_chain() {
+        // The log answers a submission of the final certificate with the SCT of its precertificate
+        let url = ADD_PRE_CHAIN_URL.replace("add-pre-chain", "add-chain");
+        let client = get_client(
+            ARGON2025H2,
+            ReplayClient::default().with_fixture(Fixture::post(&url, 200, ADD_PRE_CHAIN_RESPONSE)),
+        );
+        let chain = CertificateChain::from_pem_chain(CERT_CHAIN_GOOGLE_COM).unwrap();
+
+        // The SCT is the one embedded in the final certificate
+        let sct = &chain.cert().extract_scts_v1().unwrap()[0];
+        assert_eq!(&sct.log_id(), client.log().log_id());
+        assert_eq!(sct.timestamp(), 1748856933195);
+
+        assert!(matches!(
+            client.add_chain_v1(&chain).await,
+            Err(ClientError::SignatureValidationFailed(
+                "SCT",
+                SignatureValidationError::InvalidSignature
+            ))
+        ));
+    }
+
+    fn get_client(config: &str, client: ReplayClient) -> CtClient<ReplayClient> {
+        let config: CtLogConfig = serde_json::from_str(config).unwrap();
+        CtClient::new(config, client).unwrap()
     }
 }
@@ -0,0 +1,181 @@
+use crate::{Client, ClientError, CtClient};
+use base64::{Engine, prelude::BASE64_STANDARD};
+use luct_core::{
+    Certificate, Version,
+    store::Hashable,
+    tree::{AuditProof, ConsistencyProof, TreeHead},
+    v2::{
+        MerkleTreeLeaf, SignedCertificateTimestamp, SignedTreeHead,
+        responses::{
+            GetAnchorsResponse, GetProofByHashResponse, GetSthConsistencyResponse, GetSthResponse,
+        },
+    },
+};
+use std::cmp::Ordering;
+use url::Url;
+
+impl<C: Client> CtClient<C> {
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_sth_v2(&self) -> Result<SignedTreeHead, ClientError> {
+        self.assert_v2()?;
+        let url = self.get_full_v2_url().join("get-sth").unwrap();
+
+        // Fetch and parse the signed tree head
+        let (status, response) = self.client.get(&url, &[]).await?;
+        self.check_status(&url, status, &response)?;
+        let response: GetSthResponse = serde_json::from_str(&response)?;
+        let response = SignedTreeHead::try_from(response).map_err(|_| ClientError::SthError)?;
+
+        // Validate tree head signature against key
+        self.log
+            .validate_sth_v2(&response)
+            .map_err(|err| ClientError::SignatureValidationFailed("STH", err))?;
+
+        tracing::debug!("fetched and validated STH {:?} from url {}", response, url);
+
+        Ok(response)
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn check_consistency_v2(
+        &self,
+        first: &SignedTreeHead,
+        second: &SignedTreeHead,
+    ) -> Result<(), ClientError> {
+        self.assert_v2()?;
+
+        // Swap first and second if second < first
+        let (first, second) = match first.tree_size().cmp(&second.tree_size()) {
+            Ordering::Less => (first, second),
+            Ordering::Equal => return Ok(()),
+            Ordering::Greater => (second, first),
+        };
+
+        let proof = self.get_consistency_proof_v2(first, second).await?;
+
+        let first = TreeHead::from(first);
+        let second = TreeHead::from(second);
+
+        // Validate consistency proof
+        proof
+            .validate(&first, &second)
+            .map_err(ClientError::ConsistencyProofError)?;
+
+        tracing::debug!(
+            "fetched and validated consistency proof for tree sizes {} to {}",
+            first.tree_size(),
+            second.tree_size()
+        );
+
+        Ok(())
+    }
+
+    /// Fetch the consistency proof from `first` to `second` without validating it
+    ///
+    /// `first` must be the smaller tree head.
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_consistency_proof_v2(
+        &self,
+        first: &SignedTreeHead,
+        second: &SignedTreeHead,
+    ) -> Result<ConsistencyProof, ClientError> {
+        self.assert_v2()?;
+
+        let first_idx = first.tree_size().to_string();
+        let second_idx = second.tree_size().to_string();
+
+        // Fetch and parse consistency proof
+        let url = self.get_full_v2_url().join("get-sth-consistency").unwrap();
+        let (status, response) = self
+            .client
+            .get(&url, &[("first", &first_idx), ("second", &second_idx)])
+            .await?;
+        self.check_status(&url, status, &response)?;
+
+        let response: GetSthConsistencyResponse = serde_json::from_str(&response)?;
+        let proof = response
+            .into_proof(first, second)
+            .map_err(ClientError::ConsistencyProofError)?;
+
+        Ok(proof)
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn check_sct_inclusion_v2(
+        &self,
+        sct: &SignedCertificateTimestamp,
+        sth: &SignedTreeHead,
+        leaf: &MerkleTreeLeaf,
+    ) -> Result<u64, ClientError> {
+        let proof = self.fetch_audit_proof_v2(sth, leaf).await?;
+        let tree_head = TreeHead::from(sth);
+
+        // Validate inclusion proof
+        proof
+            .validate(&tree_head, leaf)
+            .map_err(ClientError::AuditProofError)?;
+
+        tracing::debug!(
+            "fetched and validated SCT {:?} for tree size {}",
+            sct,
+            sth.tree_size()
+        );
+
+        Ok(proof.index())
+    }
+
+    /// Fetch the inclusion proof of `leaf` against `sth` without validating it
+    #[tracing::instrument(level = "trace")]
+    pub async fn fetch_audit_proof_v2(
+        &self,
+        sth: &SignedTreeHead,
+        leaf: &MerkleTreeLeaf,
+    ) -> Result<AuditProof, ClientError> {
+        self.assert_v2()?;
+
+        let leaf_hash = leaf.hash();
+        let leaf_hash: String = BASE64_STANDARD.encode(leaf_hash);
+
+        let tree_size = sth.tree_size().to_string();
+
+        // Fetch and parse inclusion proof
+        let url = self.get_full_v2_url().join("get-proof-by-hash").unwrap();
+        let (status, response) = self
+            .client
+            .get(&url, &[("hash", &leaf_hash), ("tree_size", &tree_size)])
+            .await?;
+        self.check_status(&url, status, &response)?;
+
+        let response: GetProofByHashResponse = serde_json::from_str(&response)?;
+        response
+            .into_proof(sth)
+            .map_err(ClientError::AuditProofError)
+    }
+
+    #[tracing::instrument(level = "trace")]
+    pub async fn get_anchors_v2(&self) -> Result<Vec<Certificate>, ClientError> {
+        self.assert_v2()?;
+
+        let url = self.get_full_v2_url().join("get-anchors").unwrap();
+        let (status, response) = self.client.get(&url, &[]).await?;
+        self.check_status(&url, status, &response)?;
+
+        let response: GetAnchorsResponse = serde_json::from_str(&response)?;
+
+        tracing::debug!("fetched anchors from url {}", url);
+
+        Ok((&response).into())
+    }
+
+    fn get_full_v2_url(&self) -> Url {
+        let base_url = self.log().config().fetch_url();
+        base_url.join("ct/v2/").unwrap()
+    }
+
+    pub(crate) fn assert_v2(&self) -> Result<(), ClientError> {
+        match self.log().config().version() {
+            Version::V2 => Ok(()),
+            _ => Err(ClientError::UnsupportedVersion),
+        }
+    }
+}
@@ -140,3 +140,54 @@ impl<C: Client> TileFetchStore<C> {
         Some(nodes)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replay::{Fixture, ReplayClient};
+    use luct_core::{CtLogConfig, tiling::DataTileId};
+    use std::sync::Arc;
+
+    const DATA_TILE: &[u8] = include_bytes!("../../testdata/data-tile-000.p2.bin");
+    const ARCHE2026H1: &str = "{
+          \"description\": \"Google 'Arche2026h1' log\",
+          \"key\": \"MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEZ+3YKoZTMruov4cmlImbk4MckBNzEdCyMuHlwGgJ8BUrzFLlR5U0619xDDXIXespkpBgCNVQAkhMTTXakM6KMg==\",
+          \"url\": \"https://arche2026h1.staging.ct.transparency.dev/\",
+          \"tile_url\": \"https://storage.googleapis.com/static-ct-staging-arche2026h1-bucket/\",
+          \"mmd\": 60
+        }";
+
+    #[tokio::test]
+    async fn fetch_leaves_from_tiles() {
+        let leaves = DataTileId::from_index(0, 2)
+            .unwrap()
+            .with_data(Arc::new(DATA_TILE.to_vec()))
+            .leaves()
+            .unwrap();
+        let tile = leaves
+            .iter()
+            .flat_map(|leaf| leaf.as_merkle_tree_leaf().hash())
+            .collect::<Vec<_>>();
+
+        let client = ReplayClient::default().with_fixture(Fixture::get(
+            "https://storage.googleapis.com/static-ct-staging-arche2026h1-bucket/tile/0/000.p/2",
+            &[],
+            200,
+            tile,
+        ));
+        let config: CtLogConfig = serde_json::from_str(ARCHE2026H1).unwrap();
+        let store = TileFetchStore::new("test".to_string(), CtClient::new(config, client).unwrap());
+
+        // The store refuses to fetch anything before the tree size is known
+        assert_eq!(store.get(NodeKey::leaf(0)).await, None);
+
+        store.set_tree_size(2);
+        for (idx, leaf) in leaves.iter().enumerate() {
+            assert_eq!(
+                store.get(NodeKey::leaf(idx as u64)).await,
+                Some(leaf.as_merkle_tree_leaf().hash())
+            );
+        }
+        assert_eq!(store.len().await, 2);
+    }
+}
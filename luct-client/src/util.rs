@@ -1,5 +1,5 @@
 use crate::{ClientError, CtClient};
-use std::fmt::Debug;
+use std::{fmt::Debug, time::Duration};
 use url::Url;
 
 impl<C: Debug> CtClient<C> {
@@ -32,3 +32,38 @@ impl<C: Debug> CtClient<C> {
         self.check_status(url, status, &String::from_utf8_lossy(response))
     }
 }
+
+/// The clock used to measure time between requests
+///
+/// On native targets, this is the clock of the tokio runtime, such that it can be paused in tests.
+#[cfg(not(any(target_arch = "wasm32", target_arch = "wasm64")))]
+pub(crate) use tokio::time::Instant;
+#[cfg(any(target_arch = "wasm32", target_arch = "wasm64"))]
+pub(crate) use web_time::Instant;
+
+/// Wait for `duration` without blocking the executor
+#[cfg(not(any(target_arch = "wasm32", target_arch = "wasm64")))]
+pub(crate) async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await
+}
+
+/// Wait for `duration` without blocking the executor
+///
+/// Uses `setTimeout` of the global scope, which exists in windows as well as in workers.
+#[cfg(any(target_arch = "wasm32", target_arch = "wasm64"))]
+pub(crate) async fn sleep(duration: Duration) {
+    use wasm_bindgen::{JsCast, JsValue};
+
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        let set_timeout = js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("setTimeout"))
+            .expect("setTimeout is not available in this scope")
+            .unchecked_into::<js_sys::Function>();
+        let _ = set_timeout.call2(
+            &JsValue::NULL,
+            &resolve,
+            &JsValue::from_f64(duration.as_millis() as f64),
+        );
+    });
+
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
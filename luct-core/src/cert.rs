@@ -1,9 +1,10 @@
 use crate::{
+    SignedCertificateTimestamp,
     utils::{
         codec::{CodecError, Decode},
         extract_oid_from_rdn, hex_with_colons,
     },
-    v1,
+    v1, v2,
 };
 use chrono::{DateTime, Utc};
 use const_oid::db::rfc4519::{CN, COMMON_NAME, O, ORGANIZATION, ORGANIZATION_NAME};
@@ -17,14 +18,28 @@ use thiserror::Error;
 use x509_cert::{
     Certificate as Cert,
     der::{Decode as CertDecode, DecodePem, Encode as CertEncode, EncodePem, asn1::OctetString},
-    ext::pkix::{AuthorityKeyIdentifier, SubjectKeyIdentifier},
+    ext::pkix::{AuthorityKeyIdentifier, ExtendedKeyUsage, SubjectKeyIdentifier},
 };
+use x509_ocsp::{BasicOcspResponse, OcspResponse};
 
 pub(crate) const SCT_V1: ObjectIdentifier = const_oid::db::rfc6962::CT_PRECERT_SCTS;
 pub(crate) const CT_POISON: ObjectIdentifier = const_oid::db::rfc6962::CT_PRECERT_POISON;
+pub(crate) const PRECERT_SIGNING_CERT: ObjectIdentifier =
+    const_oid::db::rfc6962::CT_PRECERT_SIGNING_CERT;
+/// The transparency information extension
+///
+/// See RFC 9162 7.1
+pub(crate) const SCT_V2: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.101.75");
+/// The OCSP single response extension containing SCTs
+///
+/// See RFC 6962 3.3
+pub(crate) const OCSP_SCT_V1: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.6.1.4.1.11129.2.4.5");
+pub(crate) const OCSP_BASIC: ObjectIdentifier = const_oid::db::rfc6960::ID_PKIX_OCSP_BASIC;
 
 pub(crate) const SUBJECT_KEY_ID: ObjectIdentifier =
     const_oid::db::rfc5280::ID_CE_SUBJECT_KEY_IDENTIFIER;
+pub(crate) const EXT_KEY_USAGE: ObjectIdentifier = const_oid::db::rfc5280::ID_CE_EXT_KEY_USAGE;
 pub(crate) const AUTH_KEY_ID: ObjectIdentifier =
     const_oid::db::rfc5280::ID_CE_AUTHORITY_KEY_IDENTIFIER;
 
@@ -59,11 +74,7 @@ impl Certificate {
             .iter()
             .filter(|extension| extension.extn_id == SCT_V1)
             .map(|sct| &sct.extn_value)
-            .map(|sct| {
-                let sct = OctetString::from_der(sct.as_bytes()).unwrap();
-                let mut reader = Cursor::new(sct.as_bytes());
-                v1::SctList::decode(&mut reader)
-            })
+            .map(|sct| decode_sct_list(sct.as_bytes()))
             .collect::<Result<Vec<_>, _>>()?;
 
         let scts = sct_lists
@@ -74,6 +85,101 @@ impl Certificate {
         Ok(scts)
     }
 
+    /// Extract the [SCTs](v2::SignedCertificateTimestamp) embedded into this [`Certificate`]
+    ///
+    /// Other items of the transparency information, such as inclusion proofs, are ignored.
+    pub fn extract_scts_v2(&self) -> Result<Vec<v2::SignedCertificateTimestamp>, CertificateError> {
+        let Some(extensions) = &self.0.tbs_certificate.extensions else {
+            return Ok(vec![]);
+        };
+
+        let item_lists = extensions
+            .iter()
+            .filter(|extension| extension.extn_id == SCT_V2)
+            .map(|items| &items.extn_value)
+            .map(|items| {
+                let items =
+                    OctetString::from_der(items.as_bytes()).map_err(CodecError::DerError)?;
+                let mut reader = Cursor::new(items.as_bytes());
+                v2::TransItemList::decode(&mut reader)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let scts = item_lists
+            .into_iter()
+            .flat_map(|list| list.into_inner())
+            .filter_map(|item| v2::SignedCertificateTimestamp::try_from(item).ok())
+            .collect();
+
+        Ok(scts)
+    }
+
+    /// Extract the [SCTs](SignedCertificateTimestamp) of all versions embedded into this [`Certificate`]
+    pub fn extract_scts(&self) -> Result<Vec<SignedCertificateTimestamp>, CertificateError> {
+        let scts_v1 = self
+            .extract_scts_v1()?
+            .into_iter()
+            .map(SignedCertificateTimestamp::from);
+        let scts_v2 = self
+            .extract_scts_v2()?
+            .into_iter()
+            .map(SignedCertificateTimestamp::from);
+
+        Ok(scts_v1.chain(scts_v2).collect())
+    }
+
+    /// Extract the [SCTs](SignedCertificateTimestamp) for this [`Certificate`] from a stapled OCSP response
+    ///
+    /// Only single responses for the serial number of this certificate are considered.
+    /// The signature of the OCSP response is not validated, since the SCTs are signed by the logs.
+    pub fn extract_ocsp_scts(
+        &self,
+        ocsp_response: &[u8],
+    ) -> Result<Vec<SignedCertificateTimestamp>, CertificateError> {
+        let response = OcspResponse::from_der(ocsp_response).map_err(CodecError::DerError)?;
+        let Some(response) = response
+            .response_bytes
+            .filter(|bytes| bytes.response_type == OCSP_BASIC)
+        else {
+            return Ok(vec![]);
+        };
+        let response = BasicOcspResponse::from_der(response.response.as_bytes())
+            .map_err(CodecError::DerError)?;
+
+        let serial_number = &self.0.tbs_certificate.serial_number;
+        let extensions = response
+            .tbs_response_data
+            .responses
+            .iter()
+            .filter(|response| &response.cert_id.serial_number == serial_number)
+            .filter_map(|response| response.single_extensions.as_ref())
+            .flatten();
+
+        let mut scts = vec![];
+        for extension in extensions {
+            if extension.extn_id == OCSP_SCT_V1 {
+                let list = decode_sct_list(extension.extn_value.as_bytes())?;
+                scts.extend(
+                    list.into_inner()
+                        .into_iter()
+                        .map(SignedCertificateTimestamp::from),
+                );
+            } else if extension.extn_id == SCT_V2 {
+                let items = OctetString::from_der(extension.extn_value.as_bytes())
+                    .map_err(CodecError::DerError)?;
+                let list = v2::TransItemList::decode(Cursor::new(items.as_bytes()))?;
+                scts.extend(
+                    list.into_inner()
+                        .into_iter()
+                        .filter_map(|item| v2::SignedCertificateTimestamp::try_from(item).ok())
+                        .map(SignedCertificateTimestamp::from),
+                );
+            }
+        }
+
+        Ok(scts)
+    }
+
     pub fn is_precert(&self) -> Result<bool, CertificateError> {
         let Some(extensions) = &self.0.tbs_certificate.extensions else {
             return Ok(false);
@@ -81,7 +187,7 @@ impl Certificate {
 
         let scts = extensions
             .iter()
-            .filter(|extension| extension.extn_id == SCT_V1)
+            .filter(|extension| extension.extn_id == SCT_V1 || extension.extn_id == SCT_V2)
             .count();
 
         let poisons = extensions
@@ -97,6 +203,25 @@ impl Certificate {
         }
     }
 
+    /// Returns `true`, if this certificate is a precertificate signing certificate
+    ///
+    /// See RFC 6962 3.1
+    pub(crate) fn is_precert_signing_cert(&self) -> Result<bool, CodecError> {
+        let Some(extensions) = &self.0.tbs_certificate.extensions else {
+            return Ok(false);
+        };
+
+        let Some(extension) = extensions
+            .iter()
+            .find(|extension| extension.extn_id == EXT_KEY_USAGE)
+        else {
+            return Ok(false);
+        };
+
+        let key_usage = ExtendedKeyUsage::from_der(extension.extn_value.as_bytes())?;
+        Ok(key_usage.0.contains(&PRECERT_SIGNING_CERT))
+    }
+
     pub fn fingerprint_sha256(&self) -> Fingerprint {
         let mut cert_bytes = vec![];
         self.0.encode_to_vec(&mut cert_bytes).unwrap();
@@ -166,6 +291,12 @@ impl Display for Fingerprint {
     }
 }
 
+/// Decode a [`SctList`](v1::SctList), that is wrapped into a DER encoded octet string
+fn decode_sct_list(value: &[u8]) -> Result<v1::SctList, CodecError> {
+    let list = OctetString::from_der(value).map_err(CodecError::DerError)?;
+    v1::SctList::decode(Cursor::new(list.as_bytes()))
+}
+
 /// Error returned when parsing a [`Certificate`] or [`CertificateChain`](crate::cert_chain::CertificateChain)
 #[derive(Debug, Clone, PartialEq, Eq, Error)]
 pub enum CertificateError {
@@ -193,14 +324,90 @@ mod tests {
     use super::*;
     use crate::{
         CertificateChain,
-        tests::{CERT_CHAIN_GOOGLE_COM, get_log_argon2025h2},
-        utils::codec::Encode,
+        signature::SignatureValidationError,
+        tests::{
+            CERT_CHAIN_GOOGLE_COM, CERT_CHAIN_V2, get_log_argon2025h2, get_log_v2_ecdsa,
+            get_log_v2_ed25519,
+        },
+        utils::{
+            base64::Base64,
+            codec::{Codec, Encode},
+        },
     };
 
     const CERT_GOOGLE_COM: &str = include_str!("../../testdata/google-cert.pem");
     const PRE_CERT_GOOGLE_COM: &str = include_str!("../../testdata/google-precert.pem");
     const GOOGLE_COM_FINGERPRINT: &str = "4B:4F:46:F8:E1:78:B4:08:F9:A7:AF:2B:CE:31:0A:6A:9F:BD:59:37:BD:F8:5B:C5:9B:45:D6:3C:81:61:73:67";
 
+    /// An `x509_sct_v2` over the leaf of [`CERT_CHAIN_V2`], issued by the Ed25519 test log
+    const V2_X509_SCT: &str = "AAMEK2XAAQAAAZfHkzHQAAAAQCYPexcqzuGBy4ZiVFR1txsyiSsNL6+2DYazNLOd3c5Ltqf4YVqjkl89nUQCynNj3KFi1zM+laj3KE7+YPb44QQ=";
+
+    /// Build a stapled OCSP response for `cert`, containing `scts` in a single response extension
+    fn ocsp_response(cert: &Certificate, scts: Vec<v1::SignedCertificateTimestamp>) -> Vec<u8> {
+        use x509_cert::{
+            der::{
+                DateTime,
+                asn1::{BitString, GeneralizedTime, Null},
+            },
+            ext::Extension,
+            spki::AlgorithmIdentifierOwned,
+        };
+        use x509_ocsp::{
+            CertId, CertStatus, OcspGeneralizedTime, OcspResponseStatus, ResponderId,
+            ResponseBytes, ResponseData, SingleResponse, Version,
+        };
+
+        let mut sct_list = Cursor::new(vec![]);
+        v1::SctList::new(scts).encode(&mut sct_list).unwrap();
+        let sct_list = OctetString::new(sct_list.into_inner()).unwrap();
+
+        let time = OcspGeneralizedTime(GeneralizedTime::from_date_time(
+            DateTime::new(2025, 10, 1, 0, 0, 0).unwrap(),
+        ));
+        let algorithm = AlgorithmIdentifierOwned {
+            oid: const_oid::db::rfc5912::ID_SHA_1,
+            parameters: None,
+        };
+        let single_response = SingleResponse {
+            cert_id: CertId {
+                hash_algorithm: algorithm.clone(),
+                issuer_name_hash: OctetString::new(vec![0; 20]).unwrap(),
+                issuer_key_hash: OctetString::new(vec![0; 20]).unwrap(),
+                serial_number: cert.0.tbs_certificate.serial_number.clone(),
+            },
+            cert_status: CertStatus::Good(Null),
+            this_update: time,
+            next_update: None,
+            single_extensions: Some(vec![Extension {
+                extn_id: OCSP_SCT_V1,
+                critical: false,
+                extn_value: OctetString::new(sct_list.to_der().unwrap()).unwrap(),
+            }]),
+        };
+        let basic_response = BasicOcspResponse {
+            tbs_response_data: ResponseData {
+                version: Version::V1,
+                responder_id: ResponderId::ByKey(OctetString::new(vec![0; 20]).unwrap()),
+                produced_at: time,
+                responses: vec![single_response],
+                response_extensions: None,
+            },
+            signature_algorithm: algorithm,
+            signature: BitString::from_bytes(&[]).unwrap(),
+            certs: None,
+        };
+
+        OcspResponse {
+            response_status: OcspResponseStatus::Successful,
+            response_bytes: Some(ResponseBytes {
+                response_type: OCSP_BASIC,
+                response: OctetString::new(basic_response.to_der().unwrap()).unwrap(),
+            }),
+        }
+        .to_der()
+        .unwrap()
+    }
+
     // This certificate contains an sct with leaf index
     const CERT_GEOMYS_ORG: &str = include_str!("../../testdata/geomys-org.pem");
 
@@ -260,4 +467,91 @@ mod tests {
         assert!(scts[0].extensions.leaf_index().is_none());
         assert!(scts[1].extensions.leaf_index().is_some());
     }
+
+    #[test]
+    fn validate_scts_v2() {
+        let cert = CertificateChain::from_pem_chain(CERT_CHAIN_V2).unwrap();
+        cert.verify_chain().unwrap();
+        assert!(cert.cert().extract_scts_v1().unwrap().is_empty());
+
+        let scts = cert.cert().extract_scts_v2().unwrap();
+        assert_eq!(scts.len(), 2);
+        assert!(scts.iter().all(|sct| sct.is_precert()));
+
+        get_log_v2_ecdsa().validate_sct_v2(&cert, &scts[0]).unwrap();
+        get_log_v2_ed25519()
+            .validate_sct_v2(&cert, &scts[1])
+            .unwrap();
+        assert_eq!(
+            get_log_v2_ecdsa().validate_sct_v2(&cert, &scts[1]),
+            Err(SignatureValidationError::LogIdMismatch)
+        );
+    }
+
+    #[test]
+    fn validate_x509_sct_v2() {
+        let cert = CertificateChain::from_pem_chain(CERT_CHAIN_V2).unwrap();
+        let sct: Base64<Codec<v2::SignedCertificateTimestamp>> =
+            serde_json::from_str(&format!("\"{V2_X509_SCT}\"")).unwrap();
+        let sct = sct.0.0;
+        assert!(!sct.is_precert());
+
+        let log = get_log_v2_ed25519();
+        log.validate_sct_v2(&cert, &sct).unwrap();
+        log.validate_sct(&cert, &sct.clone().into(), false).unwrap();
+        assert_eq!(
+            get_log_argon2025h2().validate_sct(&cert, &sct.into(), false),
+            Err(SignatureValidationError::VersionMismatch {
+                expected: crate::Version::V1,
+                received: crate::Version::V2
+            })
+        );
+    }
+
+    #[test]
+    fn extract_mixed_scts() {
+        let cert = CertificateChain::from_pem_chain(CERT_CHAIN_V2).unwrap();
+        let scts = cert.cert().extract_scts().unwrap();
+
+        assert_eq!(scts.len(), 2);
+        assert!(scts.iter().all(|sct| sct.version() == crate::Version::V2));
+
+        let cert = CertificateChain::from_pem_chain(CERT_CHAIN_GOOGLE_COM).unwrap();
+        let scts = cert.cert().extract_scts().unwrap();
+        assert!(scts.iter().all(|sct| sct.version() == crate::Version::V1));
+    }
+
+    #[test]
+    fn extract_tls_extension_scts() {
+        let cert = CertificateChain::from_pem_chain(CERT_CHAIN_GOOGLE_COM).unwrap();
+        let scts = cert.cert().extract_scts_v1().unwrap();
+
+        let mut data = Cursor::new(vec![]);
+        v1::SctList::new(scts.clone()).encode(&mut data).unwrap();
+        let parsed = SignedCertificateTimestamp::from_tls_extension(&data.into_inner()).unwrap();
+
+        assert_eq!(
+            parsed,
+            scts.into_iter()
+                .map(SignedCertificateTimestamp::from)
+                .collect::<Vec<_>>()
+        );
+        assert!(SignedCertificateTimestamp::from_tls_extension(&[0, 4, 0, 2, 9, 9]).is_err());
+    }
+
+    #[test]
+    fn extract_ocsp_scts() {
+        let cert = CertificateChain::from_pem_chain(CERT_CHAIN_GOOGLE_COM).unwrap();
+        let scts = cert.cert().extract_scts_v1().unwrap();
+        let response = ocsp_response(cert.cert(), scts.clone());
+
+        let parsed = cert.cert().extract_ocsp_scts(&response).unwrap();
+        assert_eq!(parsed.len(), scts.len());
+        assert_eq!(parsed[0], SignedCertificateTimestamp::from(scts[0].clone()));
+
+        // Responses for other certificates are ignored
+        let other = Certificate::from_pem(include_str!("../../testdata/geomys-org.pem")).unwrap();
+        assert!(other.extract_ocsp_scts(&response).unwrap().is_empty());
+        assert!(cert.cert().extract_ocsp_scts(&[0x30, 0x00]).is_err());
+    }
 }
@@ -1,13 +1,14 @@
 use crate::{
-    Certificate, CertificateError,
-    cert::{CT_POISON, SCT_V1},
+    Certificate, CertificateError, MerkleTreeLeaf, SignedCertificateTimestamp,
+    cert::{AUTH_KEY_ID, CT_POISON, SCT_V1, SCT_V2},
     utils::codec::CodecError,
-    v1,
+    v1, v2,
 };
 use itertools::Itertools;
 use sha2::{Digest, Sha256};
 use x509_cert::{
     Certificate as Cert,
+    certificate::{Rfc5280, TbsCertificateInner},
     der::{Decode, Encode},
 };
 use x509_verify::VerifyingKey;
@@ -37,6 +38,7 @@ impl CertificateChain {
         }
 
         let chain = Self(chain.into_iter().map(Certificate).collect());
+        chain.check_precert_signing_cert()?;
         Ok(chain)
     }
 
@@ -44,6 +46,17 @@ impl CertificateChain {
         self.0.iter().map(|cert| cert.as_pem()).join("")
     }
 
+    pub fn as_der_chain(&self) -> Vec<Vec<u8>> {
+        self.0
+            .iter()
+            .map(|cert| {
+                cert.0
+                    .to_der()
+                    .expect("Failed to encode parsed certificate")
+            })
+            .collect()
+    }
+
     pub fn from_der_chain(input: &[Vec<u8>]) -> Result<Self, CertificateError> {
         let chain = input
             .iter()
@@ -58,9 +71,20 @@ impl CertificateChain {
         }
 
         let chain = Self(chain.into_iter().map(Certificate).collect());
+        chain.check_precert_signing_cert()?;
         Ok(chain)
     }
 
+    /// If the leaf was issued by a precertificate signing certificate, the chain also needs
+    /// to contain the issuer of the precertificate signing certificate
+    fn check_precert_signing_cert(&self) -> Result<(), CertificateError> {
+        if self.0[1].is_precert_signing_cert()? && self.0.len() < 3 {
+            return Err(CertificateError::InvalidChain);
+        }
+
+        Ok(())
+    }
+
     pub fn verify_chain(&self) -> Result<(), CertificateError> {
         self.verify_chain_inner(None)
     }
@@ -96,32 +120,87 @@ impl CertificateChain {
             return Ok(v1::LogEntry::X509(self.cert().0.clone()));
         }
 
-        // Get the hash of the issuers subject public key info
+        Ok(v1::LogEntry::PreCert(v1::PreCert {
+            issuer_key_hash: self.issuer_key_hash()?,
+            tbs_certificate: self.precert_tbs_certificate()?,
+        }))
+    }
+
+    /// Returns the [`Certificate`] that issued the precertificate signing certificate,
+    /// if the leaf was issued by one
+    ///
+    /// Fails, if the chain does not contain the issuer of the precertificate signing certificate.
+    ///
+    /// See RFC 6962 3.1
+    fn precert_signing_issuer(&self) -> Result<Option<&Certificate>, CodecError> {
+        if self.0[1].is_precert_signing_cert()? {
+            self.0
+                .get(2)
+                .map(Some)
+                .ok_or(CodecError::MissingPrecertSigningIssuer)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Get the hash of the issuers subject public key info
+    ///
+    /// If the leaf was issued by a precertificate signing certificate, the issuer is the
+    /// certificate that issued the precertificate signing certificate.
+    fn issuer_key_hash(&self) -> Result<[u8; 32], CodecError> {
+        let issuer = self.precert_signing_issuer()?.unwrap_or(&self.0[1]);
+
         let mut subject_public_key_bytes = vec![];
-        // TODO: Support precert signing certificate
-        // If the parrent of the precert is a precert signing certificate,
-        // the issuer should be the subject public key info of that certificates parent
-        self.0[1]
+        issuer
             .0
             .tbs_certificate
             .subject_public_key_info
             .encode_to_vec(&mut subject_public_key_bytes)
             .map_err(CodecError::DerError)?;
-        let issuer_key_hash: [u8; 32] = Sha256::digest(&subject_public_key_bytes).into();
 
+        Ok(Sha256::digest(&subject_public_key_bytes).into())
+    }
+
+    /// Reconstruct the `TbsCertificate` of the precertificate from the certificate
+    ///
+    /// If the leaf was issued by a precertificate signing certificate, the issuer and
+    /// authority key identifier are replaced by the ones of the final issuer.
+    fn precert_tbs_certificate(&self) -> Result<TbsCertificateInner<Rfc5280>, CodecError> {
         let mut tbs_certificate = self.cert().0.tbs_certificate.clone();
         tbs_certificate.extensions = tbs_certificate.extensions.map(|extensions| {
             extensions
                 .into_iter()
                 // NOTE: We need to remove all SCT and POISON extensions
-                .filter(|extension| extension.extn_id != SCT_V1 && extension.extn_id != CT_POISON)
+                .filter(|extension| {
+                    extension.extn_id != SCT_V1
+                        && extension.extn_id != SCT_V2
+                        && extension.extn_id != CT_POISON
+                })
                 .collect::<Vec<_>>()
         });
 
-        Ok(v1::LogEntry::PreCert(v1::PreCert {
-            issuer_key_hash,
-            tbs_certificate,
-        }))
+        if self.precert_signing_issuer()?.is_some() {
+            let signing_cert = &self.0[1].0.tbs_certificate;
+            tbs_certificate.issuer = signing_cert.issuer.clone();
+
+            // The authority key identifier of the precertificate signing certificate
+            // identifies the final issuer
+            let auth_key_id = signing_cert
+                .extensions
+                .iter()
+                .flatten()
+                .find(|extension| extension.extn_id == AUTH_KEY_ID);
+            if let (Some(auth_key_id), Some(extensions)) =
+                (auth_key_id, &mut tbs_certificate.extensions)
+            {
+                extensions
+                    .iter_mut()
+                    .filter(|extension| extension.extn_id == AUTH_KEY_ID)
+                    .for_each(|extension| extension.extn_value = auth_key_id.extn_value.clone());
+            }
+        }
+
+        Ok(tbs_certificate)
     }
 
     /// Return the [leaf](v1::MerkleTreeLeaf) of the [SCT](v1::SignedCertificateTimestamp)
@@ -148,4 +227,128 @@ impl CertificateChain {
             }),
         })
     }
+
+    /// Return the [leaf](v2::MerkleTreeLeaf) of the [SCT](v2::SignedCertificateTimestamp)
+    ///
+    /// Whether the leaf contains a precertificate entry is determined by the type of the
+    /// [SCT](v2::SignedCertificateTimestamp).
+    pub fn as_leaf_v2(
+        &self,
+        sct: &v2::SignedCertificateTimestamp,
+    ) -> Result<v2::MerkleTreeLeaf, CodecError> {
+        let entry = v2::TimestampedCertificateEntryDataV2 {
+            timestamp: sct.data.timestamp,
+            issuer_key_hash: v2::NodeHash(self.issuer_key_hash()?),
+            tbs_certificate: match sct.precert {
+                true => self.precert_tbs_certificate()?,
+                false => self.cert().0.tbs_certificate.clone(),
+            },
+            sct_extensions: sct.data.sct_extensions.clone(),
+        };
+
+        Ok(v2::MerkleTreeLeaf(match sct.precert {
+            true => v2::TransItem::PrecertEntryV2(entry),
+            false => v2::TransItem::X509EntryV2(entry),
+        }))
+    }
+
+    /// Return the [leaf](MerkleTreeLeaf) of the [SCT](SignedCertificateTimestamp)
+    ///
+    /// `as_precert` is only considered for v1 SCTs, see [`CertificateChain::as_leaf_v1`].
+    pub fn as_leaf(
+        &self,
+        sct: &SignedCertificateTimestamp,
+        as_precert: bool,
+    ) -> Result<MerkleTreeLeaf, CodecError> {
+        match sct {
+            SignedCertificateTimestamp::V1(sct) => {
+                Ok(MerkleTreeLeaf::V1(self.as_leaf_v1(sct, as_precert)?))
+            }
+            SignedCertificateTimestamp::V2(sct) => Ok(MerkleTreeLeaf::V2(self.as_leaf_v2(sct)?)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CtLog;
+
+    /// A precertificate, issued by a precertificate signing certificate
+    const PRECERT_SIGNING_CHAIN: &str = include_str!("../../testdata/precert-signing-chain.pem");
+    /// The final certificate of [`PRECERT_SIGNING_CHAIN`], containing an SCT issued for the precertificate
+    const PRECERT_SIGNING_FINAL_CHAIN: &str =
+        include_str!("../../testdata/precert-signing-final-chain.pem");
+
+    const TEST_LOG: &str = "{
+        \"description\": \"luCT test log\",
+        \"version\": 1,
+        \"url\": \"https://test.luct.dev/\",
+        \"key\": \"MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEtnNjkhk+ic1XNcGG5XwJuur3CnH+dm4lkdu2WEgBycKs9auksRagBYQYx10ho+1mw4QpGrE1uDgnJNuBI466ig==\",
+        \"mmd\": 86400
+        }
+    ";
+
+    fn get_test_log() -> CtLog {
+        CtLog::new(serde_json::from_str(TEST_LOG).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn detect_precert_signing_cert() {
+        let chain = CertificateChain::from_pem_chain(PRECERT_SIGNING_CHAIN).unwrap();
+        chain.verify_chain().unwrap();
+
+        assert!(chain.cert().is_precert().unwrap());
+        assert!(!chain.0[0].is_precert_signing_cert().unwrap());
+        assert!(chain.0[1].is_precert_signing_cert().unwrap());
+        assert!(!chain.0[2].is_precert_signing_cert().unwrap());
+
+        // The issuer of the precertificate signing certificate must be part of the chain
+        let short_chain = chain.0[..2]
+            .iter()
+            .map(|cert| cert.as_pem())
+            .collect::<String>();
+        assert_eq!(
+            CertificateChain::from_pem_chain(&short_chain),
+            Err(CertificateError::InvalidChain)
+        );
+
+        // Chains built without that check must not hash the key of the wrong issuer
+        let short_chain = CertificateChain::from(chain.0[..2].to_vec());
+        assert_eq!(
+            short_chain.as_log_entry_v1(true),
+            Err(CodecError::MissingPrecertSigningIssuer)
+        );
+    }
+
+    #[test]
+    fn precert_signing_log_entry() {
+        let precert_chain = CertificateChain::from_pem_chain(PRECERT_SIGNING_CHAIN).unwrap();
+        let final_chain = CertificateChain::from_pem_chain(PRECERT_SIGNING_FINAL_CHAIN).unwrap();
+        final_chain.verify_chain().unwrap();
+
+        assert_eq!(
+            precert_chain.as_log_entry_v1(true).unwrap(),
+            final_chain.as_log_entry_v1(true).unwrap()
+        );
+
+        let scts = final_chain.cert().extract_scts_v1().unwrap();
+        assert_eq!(
+            precert_chain.as_leaf_v1(&scts[0], true).unwrap(),
+            final_chain.as_leaf_v1(&scts[0], true).unwrap()
+        );
+    }
+
+    #[test]
+    fn validate_precert_signing_sct() {
+        let precert_chain = CertificateChain::from_pem_chain(PRECERT_SIGNING_CHAIN).unwrap();
+        let final_chain = CertificateChain::from_pem_chain(PRECERT_SIGNING_FINAL_CHAIN).unwrap();
+        let scts = final_chain.cert().extract_scts_v1().unwrap();
+
+        let log = get_test_log();
+        assert_eq!(log.log_id(), &scts[0].log_id());
+
+        log.validate_sct_v1(&final_chain, &scts[0], true).unwrap();
+        log.validate_sct_v1(&precert_chain, &scts[0], true).unwrap();
+    }
 }
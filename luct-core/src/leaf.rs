@@ -0,0 +1,29 @@
+use crate::{store::Hashable, tree::HashOutput, v1, v2};
+
+/// A leaf of a log of either version
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MerkleTreeLeaf {
+    V1(v1::MerkleTreeLeaf),
+    V2(v2::MerkleTreeLeaf),
+}
+
+impl Hashable for MerkleTreeLeaf {
+    fn hash(&self) -> HashOutput {
+        match self {
+            MerkleTreeLeaf::V1(leaf) => leaf.hash(),
+            MerkleTreeLeaf::V2(leaf) => leaf.hash(),
+        }
+    }
+}
+
+impl From<v1::MerkleTreeLeaf> for MerkleTreeLeaf {
+    fn from(value: v1::MerkleTreeLeaf) -> Self {
+        Self::V1(value)
+    }
+}
+
+impl From<v2::MerkleTreeLeaf> for MerkleTreeLeaf {
+    fn from(value: v2::MerkleTreeLeaf) -> Self {
+        Self::V2(value)
+    }
+}
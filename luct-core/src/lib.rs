@@ -1,43 +1,63 @@
 #![forbid(unsafe_code)]
 
-use crate::utils::base64::Base64;
+use crate::{log_list::LogMetadata, utils::base64::Base64};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::fmt::{self, Display};
+use thiserror::Error;
 use url::Url;
 
 mod cert;
 mod cert_chain;
+mod leaf;
 pub mod log_list;
+mod sct;
 pub(crate) mod signature;
+mod sth;
 pub mod store;
 pub mod tiling;
 pub mod tree;
 pub(crate) mod utils;
 pub mod v1;
+pub mod v2;
 mod version;
 
 pub use cert::{Certificate, CertificateError, Fingerprint};
 pub use cert_chain::CertificateChain;
-pub use signature::{HashAlgorithm, SignatureAlgorithm, SignatureValidationError};
+pub use leaf::MerkleTreeLeaf;
+pub use sct::SignedCertificateTimestamp;
+pub use signature::{HashAlgorithm, SignatureAlgorithm, SignatureValidationError, Signer};
+pub use sth::SignedTreeHead;
+pub use utils::codec::CodecError;
 pub use version::Version;
 
 // TODO: Introduce a Timestamp type and use it
-// TODO: Introduce toplevel types that wrap the inner v1 types to make version agnostic API
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CtLog {
     config: CtLogConfig,
     log_id: LogId,
+    metadata: Option<LogMetadata>,
 }
 
 impl CtLog {
-    pub fn new(config: CtLogConfig) -> Self {
-        let log_id = match config.version() {
-            Version::V1 => LogId::V1(v1::LogId(Sha256::digest(&config.key.0).into())),
-        };
+    pub fn new(config: CtLogConfig) -> Result<Self, CtLogConfigError> {
+        let log_id = config.derive_log_id()?;
+
+        // Logs, that are not part of a log list, may still name their operator
+        let metadata = config.operator.clone().map(LogMetadata::new);
 
-        Self { config, log_id }
+        Ok(Self {
+            config,
+            log_id,
+            metadata,
+        })
+    }
+
+    /// Attach [`LogMetadata`] to this log
+    pub fn with_metadata(mut self, metadata: LogMetadata) -> Self {
+        self.metadata = Some(metadata);
+        self
     }
 
     pub fn log_id(&self) -> &LogId {
@@ -51,10 +71,17 @@ impl CtLog {
     pub fn description(&self) -> &str {
         &self.config.description
     }
+
+    /// Return the [`LogMetadata`], if the log was taken from a log list or its config names an
+    /// operator
+    pub fn metadata(&self) -> Option<&LogMetadata> {
+        self.metadata.as_ref()
+    }
 }
 
 /// Configuration of a [`CtLog`]
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 pub struct CtLogConfig {
     /// Short description of the log
     description: String,
@@ -63,6 +90,12 @@ pub struct CtLogConfig {
     /// The [`Version`] of this log
     version: Version,
 
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// The OID of the log, required for [`Version::V2`] logs
+    ///
+    /// The log id of [`Version::V1`] logs is derived from the key instead.
+    log_id: Option<v2::LogId>,
+
     /// The [`Url`] at which the log operates
     ///
     /// In case of an RFC 6962 log, this is the url at which the endpoint lives.
@@ -77,9 +110,48 @@ pub struct CtLogConfig {
 
     /// The [`Url`], used by tiling logs to fetch tiles
     tile_url: Option<Url>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Name of the operator of the log
+    ///
+    /// Logs taken from a log list get their operator from the list instead.
+    operator: Option<String>,
+}
+
+impl Serialize for CtLogConfig {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        Self::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CtLogConfig {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let config = Self::deserialize(deserializer)?;
+        config.derive_log_id().map_err(serde::de::Error::custom)?;
+
+        Ok(config)
+    }
+}
+
+/// Error returned when a [`CtLogConfig`] does not describe a valid [`CtLog`]
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CtLogConfigError {
+    #[error("Logs of version 2 must specify a log_id")]
+    MissingLogId,
 }
 
 impl CtLogConfig {
+    /// Return the human readable description of this log
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
     /// Return the [`Url`] of this log
     pub fn url(&self) -> &Url {
         &self.url
@@ -90,6 +162,13 @@ impl CtLogConfig {
         self.url()
     }
 
+    /// Return the submission [`Url`] for this log
+    ///
+    /// Both RFC 6962 and tiling logs accept submissions at `ct/v1/add-chain` below this url.
+    pub fn submission_url(&self) -> &Url {
+        self.url()
+    }
+
     /// Return the tile [`Url`] for this log
     pub fn tile_url(&self) -> &Option<Url> {
         &self.tile_url
@@ -104,27 +183,78 @@ impl CtLogConfig {
     pub fn version(&self) -> &Version {
         &self.version
     }
+
+    /// Return the maximum merge delay of this log in seconds
+    pub fn mmd(&self) -> u64 {
+        self.mmd
+    }
+
+    /// Return the [`LogId`] of this log
+    ///
+    /// The log id of [`Version::V1`] logs is the hash of their key,
+    /// while [`Version::V2`] logs must specify it explicitly.
+    fn derive_log_id(&self) -> Result<LogId, CtLogConfigError> {
+        match (&self.version, &self.log_id) {
+            (Version::V1, _) => Ok(LogId::V1(v1::LogId(Sha256::digest(&self.key.0).into()))),
+            (Version::V2, Some(log_id)) => Ok(LogId::V2(log_id.clone())),
+            (Version::V2, None) => Err(CtLogConfigError::MissingLogId),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum LogId {
     V1(v1::LogId),
+    V2(v2::LogId),
 }
 
 impl Display for LogId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             LogId::V1(log_id) => write!(f, "{log_id}"),
+            LogId::V2(log_id) => write!(f, "{log_id}"),
+        }
+    }
+}
+
+impl LogId {
+    /// Returns the log id in a form, that can be used as a file name
+    ///
+    /// V1 log ids are hex encoded, since their base64 encoding may contain `/`.
+    /// V2 log ids are OIDs in dotted notation.
+    pub fn to_file_name(&self) -> String {
+        match self {
+            LogId::V1(log_id) => hex::encode(log_id.0),
+            LogId::V2(log_id) => log_id.to_string(),
         }
     }
 }
 
+/// Parse a [`LogId`] from its [`Display`] representation
+///
+/// V1 log ids are base64 encoded, while v2 log ids are OIDs in dotted notation.
+impl TryFrom<&str> for LogId {
+    type Error = ();
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        v1::LogId::try_from(value)
+            .map(LogId::V1)
+            .or_else(|_| v2::LogId::try_from(value).map(LogId::V2))
+    }
+}
+
 impl From<v1::LogId> for LogId {
     fn from(value: v1::LogId) -> Self {
         Self::V1(value)
     }
 }
 
+impl From<v2::LogId> for LogId {
+    fn from(value: v2::LogId) -> Self {
+        Self::V2(value)
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -164,14 +294,55 @@ mod tests {
 
     pub(crate) const CERT_CHAIN_GOOGLE_COM: &str = include_str!("../../testdata/google-chain.pem");
 
+    const V2_ECDSA_LOG: &str = "{
+        \"description\": \"luCT v2 test log (ECDSA)\",
+        \"version\": 2,
+        \"log_id\": \"1.3.101.8192\",
+        \"url\": \"https://ecdsa.v2.luct.dev/\",
+        \"key\": \"MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEALRXEHHbtNpM7tHk+En7jLQU3XHC2+LcdYm+lTNZj+C9IrPISLeYxZB+xIM/oOWGr+BWP796fY8GkxIH7AKcbg==\",
+        \"mmd\": 86400
+        }
+    ";
+
+    const V2_ED25519_LOG: &str = "{
+        \"description\": \"luCT v2 test log (Ed25519)\",
+        \"version\": 2,
+        \"log_id\": \"1.3.101.8193\",
+        \"url\": \"https://ed25519.v2.luct.dev/\",
+        \"key\": \"MCowBQYDK2VwAyEAJATlMOgkQuPdBde9Oguq92AV21GDQ9hGd4sFAQc4WTs=\",
+        \"mmd\": 86400
+        }
+    ";
+
+    pub(crate) const V2_ECDSA_STH: &str = "{
+    \"sth\":\"AAUEK2XAAAAAAZfHyhiAAAAAAAAAAAIgISgTI15UMrhmTBfSoa+f7bJTMf34/vF581Be4TM9ps4AAABHMEUCIQCmc6E3JkUQXvL4e2xf0GsG8g7S2ANySmP37PYYmaDsEgIgKHnKmwp6XsHjFlytqsuE68BTHP28IhMxjJKi7Gkjxe0=\"
+    }";
+
+    pub(crate) const V2_ED25519_STH: &str = "{
+    \"sth\":\"AAUEK2XAAQAAAZfHyhiAAAAAAAAAAAIgISgTI15UMrhmTBfSoa+f7bJTMf34/vF581Be4TM9ps4AAABAC69z91W1wmqF13qcu97yaGlGfJlboNrOQhQ+Te9amzSWaJr3VMmgQSiwPZZF5iNeYJ+HpBpNzRxbK5WDsXfzDw==\"
+    }";
+
+    /// A certificate chain with two embedded v2 SCTs, one from each v2 test log
+    pub(crate) const CERT_CHAIN_V2: &str = include_str!("../../testdata/v2-chain.pem");
+
     pub(crate) fn get_log_argon2025h1() -> CtLog {
         let config = serde_json::from_str(ARGON2025H1).unwrap();
-        CtLog::new(config)
+        CtLog::new(config).unwrap()
     }
 
     pub(crate) fn get_log_argon2025h2() -> CtLog {
         let config = serde_json::from_str(ARGON2025H2).unwrap();
-        CtLog::new(config)
+        CtLog::new(config).unwrap()
+    }
+
+    pub(crate) fn get_log_v2_ecdsa() -> CtLog {
+        let config = serde_json::from_str(V2_ECDSA_LOG).unwrap();
+        CtLog::new(config).unwrap()
+    }
+
+    pub(crate) fn get_log_v2_ed25519() -> CtLog {
+        let config = serde_json::from_str(V2_ED25519_LOG).unwrap();
+        CtLog::new(config).unwrap()
     }
 
     #[test]
@@ -182,7 +353,60 @@ mod tests {
             .decode("TnWjJ1yaEMM4W2zU3z9S6x3w4I4bjWnAsfpksWKaOd8=")
             .unwrap();
 
-        let LogId::V1(log_id) = log.log_id();
+        let LogId::V1(log_id) = log.log_id() else {
+            panic!("Expected a v1 log id");
+        };
         assert_eq!(log_id.0.to_vec(), test_log_id)
     }
+
+    #[test]
+    fn ct_log_v2_requires_log_id() {
+        let config = "{
+            \"description\": \"V2 log\",
+            \"version\": 2,
+            \"url\": \"https://ct.example.com/\",
+            \"key\": \"MCowBQYDK2VwAyEAHqE4u7nZr3Vm2cq3GbHfF/tGmTb8y2LfrZ4hHLS20pI=\",
+            \"mmd\": 86400
+        }";
+        assert!(serde_json::from_str::<CtLogConfig>(config).is_err());
+
+        let config = config.replace(
+            "\"version\": 2,",
+            "\"version\": 2, \"log_id\": \"1.3.101.8192\",",
+        );
+        let config: CtLogConfig = serde_json::from_str(&config).unwrap();
+        let log = CtLog::new(config.clone()).unwrap();
+        assert_eq!(log.log_id().to_string(), "1.3.101.8192");
+
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(serde_json::from_str::<CtLogConfig>(&json).unwrap(), config);
+
+        // Configs built in code are checked as well
+        let config = CtLogConfig {
+            log_id: None,
+            ..config
+        };
+        assert_eq!(CtLog::new(config), Err(CtLogConfigError::MissingLogId));
+    }
+
+    #[test]
+    fn log_id_string_roundtrip() {
+        let v1_log_id = get_log_argon2025h1().log_id().clone();
+        let v2_log_id = LogId::V2(v2::LogId::try_from("1.3.101.8192").unwrap());
+
+        for log_id in [v1_log_id, v2_log_id] {
+            assert_eq!(LogId::try_from(log_id.to_string().as_str()), Ok(log_id));
+        }
+    }
+
+    #[test]
+    fn log_id_file_name() {
+        assert_eq!(
+            get_log_argon2025h1().log_id().to_file_name(),
+            "4e75a3275c9a10c3385b6cd4df3f52eb1df0e08e1b8d69c0b1fa64b1629a39df"
+        );
+
+        let v2_log_id = LogId::V2(v2::LogId::try_from("1.3.101.8192").unwrap());
+        assert_eq!(v2_log_id.to_file_name(), "1.3.101.8192");
+    }
 }
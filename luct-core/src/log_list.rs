@@ -1,5 +1,474 @@
+use crate::{
+    CtLog, CtLogConfig, CtLogConfigError, LogId, SignatureValidationError, Version,
+    signature::{SignatureAndHashAlgorithm, validate_with_algorithm},
+    utils::base64::Base64,
+    v1,
+};
+use base64::{Engine, prelude::BASE64_STANDARD};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use thiserror::Error;
+use url::Url;
+
+pub mod apple;
 pub mod v3;
 
 // TODO: Factor out log list management Winto separate crate
-// TODO: Instead of parsing CtLog structs, parse another structure that wraps CtLog
-// but has additinonal data, such as log operator or log status
+
+/// Public key, that signs a log list
+///
+/// Log lists are published together with a detached signature over the exact bytes of the list.
+/// RSA keys use RSASSA-PKCS1-v1_5 with SHA-256, ECDSA keys use P-256 with SHA-256.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogListKey(Vec<u8>);
+
+impl LogListKey {
+    /// Parse a DER encoded subject public key info
+    pub fn from_der(key: Vec<u8>) -> Result<Self, SignatureValidationError> {
+        SignatureAndHashAlgorithm::from_key(&key)?;
+        Ok(Self(key))
+    }
+
+    /// Parse a PEM encoded public key
+    pub fn from_pem(key: &str) -> Result<Self, SignatureValidationError> {
+        let key = key
+            .trim()
+            .strip_prefix("-----BEGIN PUBLIC KEY-----")
+            .and_then(|key| key.strip_suffix("-----END PUBLIC KEY-----"))
+            .ok_or(SignatureValidationError::MalformedKey)?
+            .split_whitespace()
+            .collect::<String>();
+        let key = BASE64_STANDARD
+            .decode(key)
+            .map_err(|_| SignatureValidationError::MalformedKey)?;
+
+        Self::from_der(key)
+    }
+
+    /// Validate the detached `signature` over the log list `data`
+    pub fn validate(&self, data: &[u8], signature: &[u8]) -> Result<(), SignatureValidationError> {
+        let algorithm = SignatureAndHashAlgorithm::from_key(&self.0)?;
+        validate_with_algorithm(&algorithm, data, signature, &self.0)
+    }
+}
+
+/// Merge the logs of multiple log lists, deduplicated by [`LogId`]
+///
+/// If a log appears in multiple lists, the entry of the first list is kept.
+/// Its [`LogMetadata`] therefore reflects the first list, so the list of the policy, that the logs
+/// are evaluated against, should come first.
+pub fn merge_logs(lists: impl IntoIterator<Item = Vec<CtLog>>) -> Vec<CtLog> {
+    let mut seen = BTreeSet::new();
+    lists
+        .into_iter()
+        .flatten()
+        .filter(|log| seen.insert(log.log_id().clone()))
+        .collect()
+}
+
+/// Merge the active logs of the log list of the policy with the active logs of other lists
+///
+/// Works like [`merge_logs`] with `policy_active` first, but drops the logs, that are part of
+/// `policy_all` without being active. The list of the policy decides over the state of its logs,
+/// such that a log it no longer considers active is not used, because another list still does.
+pub fn merge_active_logs(
+    policy_active: Vec<CtLog>,
+    policy_all: &[CtLog],
+    others_active: impl IntoIterator<Item = Vec<CtLog>>,
+) -> Vec<CtLog> {
+    let active = policy_active
+        .iter()
+        .map(CtLog::log_id)
+        .cloned()
+        .collect::<BTreeSet<_>>();
+    let inactive = policy_all
+        .iter()
+        .map(CtLog::log_id)
+        .filter(|log_id| !active.contains(*log_id))
+        .cloned()
+        .collect::<BTreeSet<_>>();
+
+    merge_logs(std::iter::once(policy_active).chain(others_active))
+        .into_iter()
+        .filter(|log| !inactive.contains(log.log_id()))
+        .collect()
+}
+
+/// Add custom logs, that are not part of a public log list, to the `public` logs
+///
+/// If `exclude_public` is set, only the custom logs are returned.
+/// A custom log must name its operator and must not share its [`LogId`] with another custom log
+/// or any log in `known`, which should contain all logs of every loaded log list.
+pub fn with_custom_logs(
+    public: Vec<CtLog>,
+    known: &[CtLog],
+    custom: &[CtLogConfig],
+    exclude_public: bool,
+) -> Result<Vec<CtLog>, LogListError> {
+    let mut logs = if exclude_public { vec![] } else { public };
+    let mut custom_logs: Vec<CtLog> = vec![];
+
+    for config in custom {
+        let log = CtLog::new(config.clone())?;
+        if log.metadata().is_none() {
+            return Err(LogListError::MissingOperator(log.description().to_string()));
+        }
+        if let Some(existing) = known
+            .iter()
+            .chain(custom_logs.iter())
+            .find(|other| other.log_id() == log.log_id())
+        {
+            return Err(LogListError::LogIdCollision {
+                custom: log.description().to_string(),
+                existing: existing.description().to_string(),
+                log_id: log.log_id().clone(),
+            });
+        }
+        custom_logs.push(log);
+    }
+
+    logs.extend(custom_logs);
+    Ok(logs)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum LogListError {
+    #[error(
+        "The custom log \"{custom}\" has the log id {log_id}, which is already used by \"{existing}\""
+    )]
+    LogIdCollision {
+        custom: String,
+        existing: String,
+        log_id: LogId,
+    },
+
+    #[error("The custom log \"{0}\" does not name its operator")]
+    MissingOperator(String),
+
+    #[error("Invalid custom log config: {0}")]
+    InvalidConfig(#[from] CtLogConfigError),
+}
+
+/// The endpoints of a log in a log list
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum LogUrl {
+    Log {
+        url: Url,
+    },
+    TiledLog {
+        submission_url: Url,
+        monitoring_url: Url,
+    },
+}
+
+/// A log, as it is listed in a log list
+pub(crate) struct ListedLog<'a> {
+    pub(crate) description: &'a str,
+    pub(crate) log_id: &'a [u8],
+    pub(crate) key: &'a Base64<Vec<u8>>,
+    pub(crate) mmd: u64,
+    pub(crate) url: &'a LogUrl,
+}
+
+impl ListedLog<'_> {
+    pub(crate) fn log_id(&self) -> Option<LogId> {
+        let log_id = self.log_id.try_into().ok()?;
+        Some(LogId::V1(v1::LogId(log_id)))
+    }
+
+    /// Build the [`CtLog`] with the attached [`LogMetadata`]
+    ///
+    /// Returns `None`, if the listed log id does not match the one derived from the key.
+    pub(crate) fn ct_log(&self, metadata: LogMetadata) -> Option<CtLog> {
+        let config = CtLogConfig {
+            description: self.description.to_string(),
+            version: Version::V1,
+            log_id: None,
+            url: match self.url {
+                LogUrl::Log { url } => url.clone(),
+                LogUrl::TiledLog { submission_url, .. } => submission_url.clone(),
+            },
+            tile_url: match self.url {
+                LogUrl::Log { .. } => None,
+                LogUrl::TiledLog { monitoring_url, .. } => Some(monitoring_url.clone()),
+            },
+            key: self.key.clone(),
+            mmd: self.mmd,
+            operator: None,
+        };
+        let ct_log = CtLog::new(config).ok()?.with_metadata(metadata);
+
+        (self.log_id().as_ref() == Some(ct_log.log_id())).then_some(ct_log)
+    }
+}
+
+/// Metadata about a [`CtLog`](crate::CtLog), as published in a log list
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogMetadata {
+    /// Name of the operator of the log
+    pub(crate) operator: String,
+
+    /// The current [`LogState`] of the log
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) state: Option<LogState>,
+
+    /// The [`TemporalInterval`] of certificate expiry dates, that the log accepts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) temporal_interval: Option<TemporalInterval>,
+
+    /// Operators, that operated the log before the current operator
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) previous_owners: Vec<PreviousOwner>,
+}
+
+impl LogMetadata {
+    pub fn new(operator: String) -> Self {
+        Self {
+            operator,
+            state: None,
+            temporal_interval: None,
+            previous_owners: vec![],
+        }
+    }
+
+    pub fn operator(&self) -> &str {
+        &self.operator
+    }
+
+    pub fn state(&self) -> Option<&LogState> {
+        self.state.as_ref()
+    }
+
+    /// Returns the time, at which the log entered its current state
+    pub fn state_timestamp(&self) -> Option<DateTime<Utc>> {
+        self.state.as_ref().map(LogState::timestamp)
+    }
+
+    pub fn temporal_interval(&self) -> Option<&TemporalInterval> {
+        self.temporal_interval.as_ref()
+    }
+
+    /// Returns the [`FinalTreeHead`] of a log, that has been made read-only
+    pub fn final_tree_head(&self) -> Option<&FinalTreeHead> {
+        match &self.state {
+            Some(LogState::Readonly {
+                final_tree_head, ..
+            }) => final_tree_head.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn previous_owners(&self) -> &[PreviousOwner] {
+        &self.previous_owners
+    }
+}
+
+/// The state of a log in the log list
+///
+/// See https://googlechrome.github.io/CertificateTransparency/log_states.html
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogState {
+    Pending {
+        timestamp: DateTime<Utc>,
+    },
+    Qualified {
+        timestamp: DateTime<Utc>,
+    },
+    Usable {
+        timestamp: DateTime<Utc>,
+    },
+    Readonly {
+        timestamp: DateTime<Utc>,
+        /// Not included in Apple's log list
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        final_tree_head: Option<FinalTreeHead>,
+    },
+    Retired {
+        timestamp: DateTime<Utc>,
+    },
+    Rejected {
+        timestamp: DateTime<Utc>,
+    },
+}
+
+impl LogState {
+    /// Returns the time, at which the log entered this state
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        match self {
+            LogState::Pending { timestamp }
+            | LogState::Qualified { timestamp }
+            | LogState::Usable { timestamp }
+            | LogState::Readonly { timestamp, .. }
+            | LogState::Retired { timestamp }
+            | LogState::Rejected { timestamp } => *timestamp,
+        }
+    }
+}
+
+/// The last tree head of a log, before it was made read-only
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FinalTreeHead {
+    sha256_root_hash: Base64<Vec<u8>>,
+    tree_size: u64,
+}
+
+impl FinalTreeHead {
+    pub fn sha256_root_hash(&self) -> &[u8] {
+        &self.sha256_root_hash.0
+    }
+
+    pub fn tree_size(&self) -> u64 {
+        self.tree_size
+    }
+}
+
+/// The range of expiry dates of certificates, that a log accepts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemporalInterval {
+    pub(crate) start_inclusive: DateTime<Utc>,
+    pub(crate) end_exclusive: DateTime<Utc>,
+}
+
+impl TemporalInterval {
+    pub fn start_inclusive(&self) -> DateTime<Utc> {
+        self.start_inclusive
+    }
+
+    pub fn end_exclusive(&self) -> DateTime<Utc> {
+        self.end_exclusive
+    }
+
+    /// Returns `true`, if a certificate expiring at `not_after` may be logged in this interval
+    pub fn contains(&self, not_after: DateTime<Utc>) -> bool {
+        self.start_inclusive <= not_after && not_after < self.end_exclusive
+    }
+}
+
+/// A former operator of a log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreviousOwner {
+    name: String,
+    end_time: DateTime<Utc>,
+}
+
+impl PreviousOwner {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The time, at which this owner stopped operating the log
+    pub fn end_time(&self) -> DateTime<Utc> {
+        self.end_time
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Signer;
+    use crate::log_list::v3::LogList;
+    use p256::ecdsa::SigningKey;
+
+    const ALL_LOG_LIST: &str = include_str!("../../testdata/all_logs_list.json");
+    const RSA_KEY: &str = "MIIBIjANBgkqhkiG9w0BAQEFAAOCAQ8AMIIBCgKCAQEAx6oWwChRUlt+HMwGItja0QXerqgosKP+V9KPt8ZDq110rjtvcJcytr64xtxMebc/fSjvUlUazTQx24H1jhN3ThZShRTru9ymB1ygyJVIrIvgrFvN/omZuWx8O7as5G0T+scoTr3p3i62ZlNiIH34xvwMXOl4Mk4LfAP75O3R6GL8aKau53byGQUHPAStc/PDO0WI9zyNwysnznO9BsFg9szJaa05DvqZ5FHsvT/CAi3srV4mJ8KwAEcoOKEuaA3nZsKnK9BJfO/YvXRx2M2qxtItSvEH7Z+IBr0y4CPgFUjzU+urzOB6SefAcHHuLmzeQQX0CjuHX/ytynDhbgVbbwIDAQAB";
+    const ARGON2026H1_KEY: &str = "MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEB/we6GOO/xwxivy4HhkrYFAAPo6e2nc346Wo2o2U+GvoPWSPJz91s/xrEvA3Bk9kWHUUXVZS5morFEzsgdHqPg==";
+
+    fn custom_log(key: &str) -> CtLogConfig {
+        serde_json::from_value(serde_json::json!({
+            "description": "luCT private test log",
+            "url": "https://private.luct.dev/",
+            "key": key,
+            "mmd": 86400,
+            "operator": "luCT test CA"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn validate_log_list_signature() {
+        let signer = SigningKey::from_slice(&[0x42; 32]).unwrap();
+        let pem = format!(
+            "-----BEGIN PUBLIC KEY-----\n{}\n-----END PUBLIC KEY-----\n",
+            BASE64_STANDARD.encode(signer.public_key())
+        );
+        let key = LogListKey::from_pem(&pem).unwrap();
+        assert_eq!(key, LogListKey::from_der(signer.public_key()).unwrap());
+
+        let signature = signer.sign(b"log list");
+        key.validate(b"log list", &signature).unwrap();
+        assert_eq!(
+            key.validate(b"other list", &signature),
+            Err(SignatureValidationError::InvalidSignature)
+        );
+
+        assert!(
+            LogListKey::from_pem("-----BEGIN PUBLIC KEY-----\nAAAA\n-----END PUBLIC KEY-----")
+                .is_err()
+        );
+        assert!(LogListKey::from_der(vec![1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn add_custom_logs() {
+        let log_list: LogList = serde_json::from_str(ALL_LOG_LIST).unwrap();
+        let known = log_list.all_logs();
+        let custom = custom_log(RSA_KEY);
+
+        let logs = with_custom_logs(
+            log_list.all_logs(),
+            &known,
+            std::slice::from_ref(&custom),
+            false,
+        )
+        .unwrap();
+        assert_eq!(logs.len(), 248);
+        assert_eq!(
+            logs.last().unwrap().metadata().unwrap().operator(),
+            "luCT test CA"
+        );
+
+        let logs = with_custom_logs(
+            log_list.all_logs(),
+            &known,
+            std::slice::from_ref(&custom),
+            true,
+        )
+        .unwrap();
+        assert_eq!(logs.len(), 1);
+
+        // Custom logs must not reuse the key of a public log or another custom log
+        let colliding = custom_log(ARGON2026H1_KEY);
+        let err = with_custom_logs(
+            log_list.all_logs(),
+            &known,
+            std::slice::from_ref(&colliding),
+            false,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            &err,
+            LogListError::LogIdCollision { existing, .. } if existing == "Google 'Argon2026h1' log"
+        ));
+        assert!(with_custom_logs(vec![], &[], &[custom.clone(), custom.clone()], true).is_err());
+
+        // Collisions are detected against all known logs, even if they are excluded or inactive
+        assert!(matches!(
+            with_custom_logs(vec![], &known, &[colliding], true),
+            Err(LogListError::LogIdCollision { .. })
+        ));
+
+        // Custom logs must name their operator
+        let mut anonymous = serde_json::to_value(&custom).unwrap();
+        anonymous.as_object_mut().unwrap().remove("operator");
+        let anonymous: CtLogConfig = serde_json::from_value(anonymous).unwrap();
+        assert_eq!(
+            with_custom_logs(vec![], &known, &[anonymous], true),
+            Err(LogListError::MissingOperator(
+                "luCT private test log".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,229 @@
+//! Parser for the log list of Apple's certificate transparency program
+//!
+//! The schema resembles [`v3`](crate::log_list::v3), but the list carries an asset version
+//! instead of a timestamp, logs have no type and read-only logs carry no final tree head.
+//!
+//! See https://support.apple.com/en-us/103214
+
+use crate::{
+    CtLog, LogId,
+    log_list::{ListedLog, LogMetadata, LogState, LogUrl, PreviousOwner, TemporalInterval},
+    utils::base64::Base64,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppleLogList {
+    version: String,
+    asset_version: u64,
+    operators: Vec<Operators>,
+}
+
+impl AppleLogList {
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Returns the asset version, which increases with every published list
+    pub fn asset_version(&self) -> u64 {
+        self.asset_version
+    }
+
+    pub fn currently_active_logs(&self) -> Vec<CtLog> {
+        self.active_logs(Utc::now())
+    }
+
+    /// Returns the logs, that are qualified, usable or read-only and still accept certificates
+    /// expiring after `time`
+    pub fn active_logs(&self, time: DateTime<Utc>) -> Vec<CtLog> {
+        self.logs(|log| {
+            log.temporal_interval
+                .as_ref()
+                .is_some_and(|interval| interval.end_exclusive > time)
+                && matches!(
+                    log.state,
+                    Some(
+                        LogState::Qualified { .. }
+                            | LogState::Usable { .. }
+                            | LogState::Readonly { .. }
+                    )
+                )
+        })
+    }
+
+    pub fn all_logs(&self) -> Vec<CtLog> {
+        self.logs(|_| true)
+    }
+
+    /// Returns the [`LogMetadata`] of the log with `log_id`
+    pub fn metadata(&self, log_id: &LogId) -> Option<LogMetadata> {
+        self.entries()
+            .find(|(_, log)| log.listed().log_id().as_ref() == Some(log_id))
+            .map(|(operator, log)| log.metadata(operator))
+    }
+
+    fn entries(&self) -> impl Iterator<Item = (&Operators, &Logs)> {
+        self.operators.iter().flat_map(|operator| {
+            operator
+                .logs
+                .iter()
+                .chain(operator.tiled_logs.iter())
+                .map(move |log| (operator, log))
+        })
+    }
+
+    fn logs(&self, filter: impl Fn(&Logs) -> bool) -> Vec<CtLog> {
+        self.entries()
+            .filter(|(_, log)| filter(log))
+            .filter_map(|(operator, log)| log.listed().ct_log(log.metadata(operator)))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Operators {
+    name: String,
+    #[serde(default)]
+    email: Vec<String>,
+    #[serde(default)]
+    logs: Vec<Logs>,
+    #[serde(default)]
+    tiled_logs: Vec<Logs>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Logs {
+    description: String,
+    log_id: Base64<Vec<u8>>,
+    key: Base64<Vec<u8>>,
+    mmd: u64,
+    state: Option<LogState>,
+    temporal_interval: Option<TemporalInterval>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    previous_owners: Vec<PreviousOwner>,
+    #[serde(flatten)]
+    url: LogUrl,
+}
+
+impl Logs {
+    fn listed(&self) -> ListedLog<'_> {
+        ListedLog {
+            description: &self.description,
+            log_id: &self.log_id.0,
+            key: &self.key,
+            mmd: self.mmd,
+            url: &self.url,
+        }
+    }
+
+    fn metadata(&self, operator: &Operators) -> LogMetadata {
+        LogMetadata {
+            operator: operator.name.clone(),
+            state: self.state.clone(),
+            temporal_interval: self.temporal_interval.clone(),
+            previous_owners: self.previous_owners.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_list::{merge_active_logs, merge_logs, v3::LogList};
+
+    const APPLE_LOG_LIST: &str = include_str!("../../../testdata/apple_log_list.json");
+    const ALL_LOG_LIST: &str = include_str!("../../../testdata/all_logs_list.json");
+
+    #[test]
+    fn parse_apple_log_list() {
+        let log_list: AppleLogList = serde_json::from_str(APPLE_LOG_LIST).unwrap();
+        assert_eq!(log_list.asset_version(), 42);
+        assert_eq!(log_list.all_logs().len(), 5);
+
+        let time = DateTime::parse_from_rfc3339("2026-02-01T00:00:00Z")
+            .unwrap()
+            .to_utc();
+        let active_logs = log_list.active_logs(time);
+        assert_eq!(active_logs.len(), 4);
+        assert!(active_logs.iter().any(|log| log.config().is_tiling()));
+
+        // Read-only logs have no final tree head in Apple's list
+        let readonly = log_list
+            .all_logs()
+            .into_iter()
+            .find(|log| {
+                matches!(
+                    log.metadata().unwrap().state(),
+                    Some(LogState::Readonly { .. })
+                )
+            })
+            .unwrap();
+        assert_eq!(readonly.metadata().unwrap().final_tree_head(), None);
+    }
+
+    #[test]
+    fn merge_log_lists() {
+        let google: LogList = serde_json::from_str(ALL_LOG_LIST).unwrap();
+        let apple: AppleLogList = serde_json::from_str(APPLE_LOG_LIST).unwrap();
+        let sabre = LogId::try_from("GgT/SdBUHUCv9qDDv/HYxGcvTuzuI0BomGsXQC7ciX0=").unwrap();
+
+        // For logs in both lists, the metadata of the first list is kept
+        let merged = merge_logs([google.all_logs(), apple.all_logs()]);
+        assert_eq!(merged.len(), 247);
+        let log = merged.iter().find(|log| log.log_id() == &sabre).unwrap();
+        assert_eq!(log.metadata().cloned(), google.metadata(&sabre));
+        assert!(log.metadata().unwrap().final_tree_head().is_some());
+
+        let merged = merge_logs([apple.all_logs(), google.all_logs()]);
+        assert_eq!(merged.len(), 247);
+        let log = merged.iter().find(|log| log.log_id() == &sabre).unwrap();
+        assert_eq!(log.metadata().cloned(), apple.metadata(&sabre));
+        assert_eq!(log.metadata().unwrap().final_tree_head(), None);
+    }
+
+    #[test]
+    fn merge_active_log_lists() {
+        let google: LogList = serde_json::from_str(ALL_LOG_LIST).unwrap();
+        let time = DateTime::parse_from_rfc3339("2026-02-01T00:00:00Z")
+            .unwrap()
+            .to_utc();
+        let nimbus = LogId::try_from("yzj3FYl8hKFEX1vB3fvJbvKaWc1HCmkFhbDLFMMUWOc=").unwrap();
+
+        // Apple retired a log, that is still usable in Google's list
+        let mut apple: serde_json::Value = serde_json::from_str(APPLE_LOG_LIST).unwrap();
+        for operator in apple["operators"].as_array_mut().unwrap() {
+            for log in operator["logs"].as_array_mut().unwrap() {
+                if log["log_id"] == nimbus.to_string() {
+                    log["state"] = serde_json::json!({
+                        "retired": { "timestamp": "2026-01-15T00:00:00Z" }
+                    });
+                }
+            }
+        }
+        let apple: AppleLogList = serde_json::from_value(apple).unwrap();
+        assert!(
+            google
+                .active_logs(time)
+                .iter()
+                .any(|log| log.log_id() == &nimbus)
+        );
+
+        // The list of the policy decides, whether one of its logs is active
+        let merged = merge_active_logs(
+            apple.active_logs(time),
+            &apple.all_logs(),
+            [google.active_logs(time)],
+        );
+        assert!(merged.iter().all(|log| log.log_id() != &nimbus));
+
+        let merged = merge_active_logs(
+            google.active_logs(time),
+            &google.all_logs(),
+            [apple.active_logs(time)],
+        );
+        let log = merged.iter().find(|log| log.log_id() == &nimbus).unwrap();
+        assert_eq!(log.metadata().cloned(), google.metadata(&nimbus));
+    }
+}
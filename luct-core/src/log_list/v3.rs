@@ -1,7 +1,10 @@
-use crate::{CtLog, CtLogConfig, Version, utils::base64::Base64};
+use crate::{
+    CtLog, LogId,
+    log_list::{ListedLog, LogMetadata, LogState, LogUrl, PreviousOwner, TemporalInterval},
+    utils::base64::Base64,
+};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use url::Url;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LogList {
@@ -11,6 +14,15 @@ pub struct LogList {
 }
 
 impl LogList {
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Returns the time, at which the log list was published
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        self.log_list_timestamp
+    }
+
     pub fn currently_active_logs(&self) -> Vec<CtLog> {
         self.active_logs(Utc::now())
     }
@@ -31,7 +43,9 @@ impl LogList {
                 state.as_ref().is_some_and(|state| {
                     matches!(
                         state,
-                        State::Qualified { .. } | State::Usable { .. } | State::Readonly { .. }
+                        LogState::Qualified { .. }
+                            | LogState::Usable { .. }
+                            | LogState::Readonly { .. }
                     )
                 })
             },
@@ -48,42 +62,34 @@ impl LogList {
         self.logs(|_| true, |_| true, |_| true)
     }
 
+    /// Returns the [`LogMetadata`] of the log with `log_id`
+    pub fn metadata(&self, log_id: &LogId) -> Option<LogMetadata> {
+        self.entries()
+            .find(|(_, log)| log.listed().log_id().as_ref() == Some(log_id))
+            .map(|(operator, log)| log.metadata(operator))
+    }
+
+    fn entries(&self) -> impl Iterator<Item = (&Operators, &Logs)> {
+        self.operators.iter().flat_map(|operator| {
+            operator
+                .logs
+                .iter()
+                .chain(operator.tiled_logs.iter())
+                .map(move |log| (operator, log))
+        })
+    }
+
     fn logs<TF, SF, TYF>(&self, time_filter: TF, state_filter: SF, type_filter: TYF) -> Vec<CtLog>
     where
-        TF: Fn(&Option<Interval>) -> bool,
-        SF: Fn(&Option<State>) -> bool,
+        TF: Fn(&Option<TemporalInterval>) -> bool,
+        SF: Fn(&Option<LogState>) -> bool,
         TYF: Fn(&Option<LogType>) -> bool,
     {
-        self.operators
-            .iter()
-            .flat_map(|op| op.logs.iter().chain(op.tiled_logs.iter()))
-            .filter(|&log| time_filter(&log.temporal_interval))
-            .filter(|&log| state_filter(&log.state))
-            .filter(|&log| type_filter(&log.log_type))
-            .filter_map(|log| {
-                let config = CtLogConfig {
-                    description: log.description.clone(),
-                    version: Version::V1,
-                    url: match &log.url {
-                        LogUrl::Log { url } => url.clone(),
-
-                        LogUrl::TiledLog { submission_url, .. } => submission_url.clone(),
-                    },
-                    tile_url: match &log.url {
-                        LogUrl::Log { .. } => None,
-                        LogUrl::TiledLog { monitoring_url, .. } => Some(monitoring_url.clone()),
-                    },
-                    key: log.key.clone(),
-                    mmd: log.mmd,
-                };
-                let log = CtLog::new(config);
-
-                if log.log_id() == &log.log_id {
-                    Some(log)
-                } else {
-                    None
-                }
-            })
+        self.entries()
+            .filter(|(_, log)| time_filter(&log.temporal_interval))
+            .filter(|(_, log)| state_filter(&log.state))
+            .filter(|(_, log)| type_filter(&log.log_type))
+            .filter_map(|(operator, log)| log.listed().ct_log(log.metadata(operator)))
             .collect()
     }
 }
@@ -103,8 +109,8 @@ struct Logs {
     key: Base64<Vec<u8>>,
     mmd: u64,
     dns: Option<String>,
-    state: Option<State>,
-    temporal_interval: Option<Interval>,
+    state: Option<LogState>,
+    temporal_interval: Option<TemporalInterval>,
     log_type: Option<LogType>,
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     previous_owners: Vec<PreviousOwner>,
@@ -112,46 +118,25 @@ struct Logs {
     url: LogUrl,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(untagged)]
-enum LogUrl {
-    Log {
-        url: Url,
-    },
-    TiledLog {
-        submission_url: Url,
-        monitoring_url: Url,
-    },
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-enum State {
-    Pending {
-        timestamp: DateTime<Utc>,
-    },
-    Qualified {
-        timestamp: DateTime<Utc>,
-    },
-    Usable {
-        timestamp: DateTime<Utc>,
-    },
-    Readonly {
-        timestamp: DateTime<Utc>,
-        final_tree_head: FinalTreeHead,
-    },
-    Retired {
-        timestamp: DateTime<Utc>,
-    },
-    Rejected {
-        timestamp: DateTime<Utc>,
-    },
-}
+impl Logs {
+    fn listed(&self) -> ListedLog<'_> {
+        ListedLog {
+            description: &self.description,
+            log_id: &self.log_id.0,
+            key: &self.key,
+            mmd: self.mmd,
+            url: &self.url,
+        }
+    }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-struct Interval {
-    start_inclusive: DateTime<Utc>,
-    end_exclusive: DateTime<Utc>,
+    fn metadata(&self, operator: &Operators) -> LogMetadata {
+        LogMetadata {
+            operator: operator.name.clone(),
+            state: self.state.clone(),
+            temporal_interval: self.temporal_interval.clone(),
+            previous_owners: self.previous_owners.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -162,18 +147,6 @@ enum LogType {
     MonitoringOnly,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-struct PreviousOwner {
-    name: String,
-    end_time: DateTime<Utc>,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-struct FinalTreeHead {
-    sha256_root_hash: Base64<Vec<u8>>,
-    tree_size: u64,
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -199,4 +172,47 @@ mod tests {
         let active_logs = log_list.active_logs(time);
         assert_eq!(active_logs.len(), 71);
     }
+
+    #[test]
+    fn log_metadata() {
+        let log_list: LogList = serde_json::from_str(ALL_LOG_LIST).unwrap();
+        let all_logs = log_list.all_logs();
+        assert!(all_logs.iter().all(|log| log.metadata().is_some()));
+
+        let log_id = LogId::try_from("pcl4kl1XRheChw3YiWYLXFVki30AQPLsB2hR0YhpGfc=").unwrap();
+        let metadata = log_list.metadata(&log_id).unwrap();
+        assert_eq!(metadata.operator(), "Let's Encrypt");
+        assert!(matches!(metadata.state(), Some(LogState::Usable { .. })));
+        assert_eq!(
+            metadata.state_timestamp().unwrap().to_rfc3339(),
+            "2025-11-27T03:00:00+00:00"
+        );
+        assert_eq!(
+            metadata
+                .temporal_interval()
+                .unwrap()
+                .end_exclusive()
+                .to_rfc3339(),
+            "2026-06-18T00:00:00+00:00"
+        );
+
+        // The metadata is attached to the logs
+        let log = all_logs.iter().find(|log| log.log_id() == &log_id).unwrap();
+        assert_eq!(log.metadata(), Some(&metadata));
+
+        // Read-only logs carry their final tree head
+        let readonly = all_logs
+            .iter()
+            .filter_map(|log| log.metadata()?.final_tree_head())
+            .collect::<Vec<_>>();
+        assert!(!readonly.is_empty());
+        assert!(
+            readonly
+                .iter()
+                .all(|sth| sth.sha256_root_hash().len() == 32)
+        );
+
+        let unknown = LogId::try_from("1.3.6.1.4.1.11129.2.4.2").unwrap();
+        assert_eq!(log_list.metadata(&unknown), None);
+    }
 }
@@ -0,0 +1,100 @@
+use crate::{
+    CertificateChain, CodecError, CtLog, LogId, SignatureValidationError, Version, store::Hashable,
+    tree::HashOutput, utils::codec::Decode, v1, v2,
+};
+use serde::{Deserialize, Serialize};
+use std::io::Cursor;
+
+impl CtLog {
+    /// Validate a [`SignedCertificateTimestamp`] of any version against the [`CertificateChain`]
+    ///
+    /// `as_precert` is only considered for v1 SCTs, see [`CtLog::validate_sct_v1`].
+    pub fn validate_sct(
+        &self,
+        cert: &CertificateChain,
+        sct: &SignedCertificateTimestamp,
+        as_precert: bool,
+    ) -> Result<(), SignatureValidationError> {
+        match (self.config.version(), sct) {
+            (Version::V1, SignedCertificateTimestamp::V1(sct)) => {
+                self.validate_sct_v1(cert, sct, as_precert)
+            }
+            (Version::V2, SignedCertificateTimestamp::V2(sct)) => self.validate_sct_v2(cert, sct),
+            (version, sct) => Err(SignatureValidationError::VersionMismatch {
+                expected: version.clone(),
+                received: sct.version(),
+            }),
+        }
+    }
+}
+
+/// A signed certificate timestamp of either version
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SignedCertificateTimestamp {
+    V1(v1::SignedCertificateTimestamp),
+    V2(v2::SignedCertificateTimestamp),
+}
+
+impl SignedCertificateTimestamp {
+    /// Parse the SCTs delivered in the `signed_certificate_timestamp` TLS extension
+    ///
+    /// These SCTs are issued over the final certificate rather than a precertificate.
+    /// See RFC 6962 3.3.1
+    pub fn from_tls_extension(data: &[u8]) -> Result<Vec<Self>, CodecError> {
+        let list = v1::SctList::decode(Cursor::new(data))?;
+        Ok(list.into_inner().into_iter().map(Self::from).collect())
+    }
+
+    pub fn version(&self) -> Version {
+        match self {
+            SignedCertificateTimestamp::V1(_) => Version::V1,
+            SignedCertificateTimestamp::V2(_) => Version::V2,
+        }
+    }
+
+    pub fn log_id(&self) -> LogId {
+        match self {
+            SignedCertificateTimestamp::V1(sct) => sct.log_id(),
+            SignedCertificateTimestamp::V2(sct) => sct.log_id(),
+        }
+    }
+
+    pub fn timestamp(&self) -> u64 {
+        match self {
+            SignedCertificateTimestamp::V1(sct) => sct.timestamp(),
+            SignedCertificateTimestamp::V2(sct) => sct.timestamp(),
+        }
+    }
+
+    /// Return the leaf index, if the SCT contains one
+    ///
+    /// Only v1 SCTs issued by tiling logs contain a leaf index.
+    pub fn leaf_index(&self) -> Option<u64> {
+        match self {
+            SignedCertificateTimestamp::V1(sct) => sct.leaf_index().map(|index| *index),
+            SignedCertificateTimestamp::V2(_) => None,
+        }
+    }
+}
+
+impl From<v1::SignedCertificateTimestamp> for SignedCertificateTimestamp {
+    fn from(value: v1::SignedCertificateTimestamp) -> Self {
+        Self::V1(value)
+    }
+}
+
+impl From<v2::SignedCertificateTimestamp> for SignedCertificateTimestamp {
+    fn from(value: v2::SignedCertificateTimestamp) -> Self {
+        Self::V2(value)
+    }
+}
+
+impl Hashable for SignedCertificateTimestamp {
+    fn hash(&self) -> HashOutput {
+        match self {
+            SignedCertificateTimestamp::V1(sct) => sct.hash(),
+            SignedCertificateTimestamp::V2(sct) => sct.hash(),
+        }
+    }
+}
@@ -1,10 +1,34 @@
-use crate::utils::{
-    codec::{CodecError, Decode, Encode},
-    codec_vec::CodecVec,
+use crate::{
+    Version,
+    utils::{
+        codec::{CodecError, Decode, Encode},
+        codec_vec::CodecVec,
+    },
+};
+use const_oid::db::{
+    rfc5912::{ID_EC_PUBLIC_KEY, RSA_ENCRYPTION},
+    rfc8410::ID_ED_25519,
+};
+use ed25519_dalek::{
+    Signature as Ed25519Signature, SigningKey as Ed25519SigningKey,
+    VerifyingKey as Ed25519VerifyingKey,
 };
 use p256::{
-    ecdsa::{Signature as EcdsaSignature, VerifyingKey, signature::Verifier},
-    pkcs8::DecodePublicKey,
+    ecdsa::{
+        Signature as EcdsaSignature, SigningKey as EcdsaSigningKey,
+        VerifyingKey as EcdsaVerifyingKey,
+        signature::{Signer as _, Verifier},
+    },
+    pkcs8::{
+        DecodePublicKey, EncodePublicKey,
+        spki::{SubjectPublicKeyInfoRef, der::Decode as DerDecode},
+    },
+};
+use rsa::{
+    RsaPublicKey,
+    pkcs1v15::{Signature as RsaSignature, VerifyingKey as RsaVerifyingKey},
+    sha2::Sha256 as RsaSha256,
+    traits::PublicKeyParts,
 };
 use serde::{Deserialize, Serialize};
 use std::{
@@ -14,6 +38,9 @@ use std::{
 };
 use thiserror::Error;
 
+/// Minimum size of RSA keys in bits
+const MIN_RSA_KEY_SIZE: usize = 2048;
+
 /// See RFC 5246 4.7
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub(crate) struct Signature<T> {
@@ -49,6 +76,103 @@ impl<T> Decode for Signature<T> {
     }
 }
 
+/// A signature that does not carry its algorithm
+///
+/// The signature algorithm is determined by the key of the log instead.
+///
+/// See RFC 9162 4.8 and 4.10
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub(crate) struct UntaggedSignature<T> {
+    signature: CodecVec<u16>,
+    inner: PhantomData<T>,
+}
+
+impl<T> std::fmt::Debug for UntaggedSignature<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("UntaggedSignature")
+            .field(&hex::encode(&self.signature))
+            .finish()
+    }
+}
+
+impl<T> From<Vec<u8>> for UntaggedSignature<T> {
+    fn from(value: Vec<u8>) -> Self {
+        Self {
+            signature: value.into(),
+            inner: PhantomData,
+        }
+    }
+}
+
+impl<T> Encode for UntaggedSignature<T> {
+    fn encode(&self, writer: impl Write) -> Result<(), CodecError> {
+        self.signature.encode(writer)
+    }
+}
+
+impl<T> Decode for UntaggedSignature<T> {
+    fn decode(reader: impl Read) -> Result<Self, CodecError> {
+        Ok(Self {
+            signature: CodecVec::decode(reader)?,
+            inner: PhantomData,
+        })
+    }
+}
+
+/// A private key, that can produce signatures which validate against the corresponding public key
+///
+/// This is implemented for ECDSA keys over P-256 and Ed25519 keys.
+pub trait Signer {
+    /// Return the DER encoded subject public key info of the key
+    fn public_key(&self) -> Vec<u8>;
+
+    /// The [`HashAlgorithm`] used by the signatures
+    fn hash_algorithm(&self) -> HashAlgorithm;
+
+    /// The [`SignatureAlgorithm`] used by the signatures
+    fn signature_algorithm(&self) -> SignatureAlgorithm;
+
+    /// Sign the data
+    fn sign(&self, data: &[u8]) -> Vec<u8>;
+}
+
+impl Signer for EcdsaSigningKey {
+    fn public_key(&self) -> Vec<u8> {
+        self.verifying_key().to_public_key_der().unwrap().into_vec()
+    }
+
+    fn hash_algorithm(&self) -> HashAlgorithm {
+        HashAlgorithm::Sha256
+    }
+
+    fn signature_algorithm(&self) -> SignatureAlgorithm {
+        SignatureAlgorithm::Ecdsa
+    }
+
+    fn sign(&self, data: &[u8]) -> Vec<u8> {
+        let signature: EcdsaSignature = self.try_sign(data).unwrap();
+        signature.to_der().as_bytes().to_vec()
+    }
+}
+
+impl Signer for Ed25519SigningKey {
+    fn public_key(&self) -> Vec<u8> {
+        self.verifying_key().to_public_key_der().unwrap().into_vec()
+    }
+
+    fn hash_algorithm(&self) -> HashAlgorithm {
+        HashAlgorithm::Intrinsic
+    }
+
+    fn signature_algorithm(&self) -> SignatureAlgorithm {
+        SignatureAlgorithm::Ed25519
+    }
+
+    fn sign(&self, data: &[u8]) -> Vec<u8> {
+        self.try_sign(data).unwrap().to_bytes().to_vec()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Error)]
 pub enum SignatureValidationError {
     #[error("Hash algorithm {0} is not supported by the implementation")]
@@ -60,61 +184,180 @@ pub enum SignatureValidationError {
     #[error("Key could not be parsed for the specified signature algorithm")]
     MalformedKey,
 
+    #[error("Key has {received} bits, but at least {expected} bits are required")]
+    InsufficientKeySize { received: usize, expected: usize },
+
     #[error("Signature could not be parsed for the specified signature algorithm")]
     MalformedSignature,
 
     #[error("Signature verification failed")]
     InvalidSignature,
 
+    #[error("The checkpoint is cosigned by {received} witnesses, but {expected} are required")]
+    InsufficientCosignatures { received: usize, expected: usize },
+
+    #[error("The log id of the signed structure does not match the log")]
+    LogIdMismatch,
+
+    #[error(
+        "A structure of version {received} can not be validated by a log of version {expected}"
+    )]
+    VersionMismatch {
+        expected: Version,
+        received: Version,
+    },
+
     #[error("Error decoding a value: {0}")]
     CodecError(#[from] CodecError),
 }
 
 impl<T: Encode> Signature<T> {
+    pub(crate) fn sign(val: &T, signer: &impl Signer) -> Result<Self, CodecError> {
+        let mut data = Cursor::new(vec![]);
+        val.encode(&mut data)?;
+
+        Ok(Self {
+            algorithm: SignatureAndHashAlgorithm {
+                hash: signer.hash_algorithm(),
+                signature: signer.signature_algorithm(),
+            },
+            signature: signer.sign(&data.into_inner()).into(),
+            inner: PhantomData,
+        })
+    }
+
+    pub fn validate(&self, val: &T, key: &[u8]) -> Result<(), SignatureValidationError> {
+        let mut data = Cursor::new(vec![]);
+        val.encode(&mut data)?;
+        validate_with_algorithm(
+            &self.algorithm,
+            &data.into_inner(),
+            self.signature.as_ref(),
+            key,
+        )
+    }
+}
+
+impl<T: Encode> UntaggedSignature<T> {
     pub fn validate(&self, val: &T, key: &[u8]) -> Result<(), SignatureValidationError> {
         let mut data = Cursor::new(vec![]);
         val.encode(&mut data)?;
 
-        // let _digest: Box<dyn DynDigest> = match &self.algorithm.hash {
-        //     HashAlgorithm::Sha224 => Box::new(Sha224::new()),
-        //     HashAlgorithm::Sha256 => Box::new(Sha256::new()),
-        //     HashAlgorithm::Sha384 => Box::new(Sha384::new()),
-        //     HashAlgorithm::Sha512 => Box::new(Sha512::new()),
-        //     alg => {
-        //         return Err(SignatureValidationError::UnsupportedHashAlgorithm(
-        //             alg.clone(),
-        //         ));
-        //     }
-        // };
-
-        match &self.algorithm.signature {
-            SignatureAlgorithm::Ecdsa => {
-                match &self.algorithm.hash {
-                    HashAlgorithm::Sha256 => (),
-                    alg => {
-                        return Err(SignatureValidationError::UnsupportedHashAlgorithm(
-                            alg.clone(),
-                        ));
-                    }
-                }
-
-                let verifying_key = VerifyingKey::from_public_key_der(key)
-                    .map_err(|_| SignatureValidationError::MalformedKey)?;
-
-                let signature = EcdsaSignature::from_der(self.signature.as_ref())
-                    .map_err(|_| SignatureValidationError::MalformedSignature)?;
-
-                verifying_key
-                    .verify(&data.into_inner(), &signature)
-                    .map_err(|_| SignatureValidationError::InvalidSignature)?;
-
-                Ok(())
+        // RFC 9162 only allows ECDSA with P-256 and Ed25519
+        let algorithm = match SignatureAndHashAlgorithm::from_key(key)? {
+            SignatureAndHashAlgorithm {
+                signature: SignatureAlgorithm::Rsa,
+                ..
+            } => {
+                return Err(SignatureValidationError::UnsupportedSignatureAlgorithm(
+                    SignatureAlgorithm::Rsa,
+                ));
             }
-            alg => Err(SignatureValidationError::UnsupportedSignatureAlgorithm(
-                alg.clone(),
-            )),
+            algorithm => algorithm,
+        };
+
+        validate_with_algorithm(&algorithm, &data.into_inner(), self.signature.as_ref(), key)
+    }
+}
+
+pub(crate) fn validate_with_algorithm(
+    algorithm: &SignatureAndHashAlgorithm,
+    data: &[u8],
+    signature: &[u8],
+    key: &[u8],
+) -> Result<(), SignatureValidationError> {
+    // let _digest: Box<dyn DynDigest> = match &algorithm.hash {
+    //     HashAlgorithm::Sha224 => Box::new(Sha224::new()),
+    //     HashAlgorithm::Sha256 => Box::new(Sha256::new()),
+    //     HashAlgorithm::Sha384 => Box::new(Sha384::new()),
+    //     HashAlgorithm::Sha512 => Box::new(Sha512::new()),
+    //     alg => {
+    //         return Err(SignatureValidationError::UnsupportedHashAlgorithm(
+    //             alg.clone(),
+    //         ));
+    //     }
+    // };
+
+    match (&algorithm.signature, &algorithm.hash) {
+        (SignatureAlgorithm::Ecdsa, HashAlgorithm::Sha256) => {
+            validate_ecdsa_sha256(data, signature, key)
+        }
+        (SignatureAlgorithm::Rsa, HashAlgorithm::Sha256) => {
+            validate_rsa_sha256(data, signature, key)
+        }
+        (SignatureAlgorithm::Ed25519, HashAlgorithm::Intrinsic) => {
+            validate_ed25519(data, signature, key)
         }
+        (
+            SignatureAlgorithm::Ecdsa | SignatureAlgorithm::Rsa | SignatureAlgorithm::Ed25519,
+            alg,
+        ) => Err(SignatureValidationError::UnsupportedHashAlgorithm(
+            alg.clone(),
+        )),
+        (alg, _) => Err(SignatureValidationError::UnsupportedSignatureAlgorithm(
+            alg.clone(),
+        )),
+    }
+}
+
+fn validate_ecdsa_sha256(
+    data: &[u8],
+    signature: &[u8],
+    key: &[u8],
+) -> Result<(), SignatureValidationError> {
+    let verifying_key = EcdsaVerifyingKey::from_public_key_der(key)
+        .map_err(|_| SignatureValidationError::MalformedKey)?;
+
+    let signature = EcdsaSignature::from_der(signature)
+        .map_err(|_| SignatureValidationError::MalformedSignature)?;
+
+    verifying_key
+        .verify(data, &signature)
+        .map_err(|_| SignatureValidationError::InvalidSignature)
+}
+
+/// RSA signatures use RSASSA-PKCS1-v1_5 with keys of at least 2048 bits
+///
+/// See RFC 6962 2.1.4
+fn validate_rsa_sha256(
+    data: &[u8],
+    signature: &[u8],
+    key: &[u8],
+) -> Result<(), SignatureValidationError> {
+    let public_key = RsaPublicKey::from_public_key_der(key)
+        .map_err(|_| SignatureValidationError::MalformedKey)?;
+
+    let key_size = public_key.size() * 8;
+    if key_size < MIN_RSA_KEY_SIZE {
+        return Err(SignatureValidationError::InsufficientKeySize {
+            received: key_size,
+            expected: MIN_RSA_KEY_SIZE,
+        });
     }
+
+    let verifying_key = RsaVerifyingKey::<RsaSha256>::new(public_key);
+    let signature = RsaSignature::try_from(signature)
+        .map_err(|_| SignatureValidationError::MalformedSignature)?;
+
+    verifying_key
+        .verify(data, &signature)
+        .map_err(|_| SignatureValidationError::InvalidSignature)
+}
+
+fn validate_ed25519(
+    data: &[u8],
+    signature: &[u8],
+    key: &[u8],
+) -> Result<(), SignatureValidationError> {
+    let verifying_key = Ed25519VerifyingKey::from_public_key_der(key)
+        .map_err(|_| SignatureValidationError::MalformedKey)?;
+
+    let signature = Ed25519Signature::from_slice(signature)
+        .map_err(|_| SignatureValidationError::MalformedSignature)?;
+
+    verifying_key
+        .verify_strict(data, &signature)
+        .map_err(|_| SignatureValidationError::InvalidSignature)
 }
 
 /// See RFC 5246 7.4.1.4.1
@@ -124,6 +367,30 @@ pub(crate) struct SignatureAndHashAlgorithm {
     pub signature: SignatureAlgorithm,
 }
 
+impl SignatureAndHashAlgorithm {
+    /// Determine the algorithm from the subject public key info of a key
+    pub(crate) fn from_key(key: &[u8]) -> Result<Self, SignatureValidationError> {
+        let key_info = SubjectPublicKeyInfoRef::from_der(key)
+            .map_err(|_| SignatureValidationError::MalformedKey)?;
+
+        match key_info.algorithm.oid {
+            ID_EC_PUBLIC_KEY => Ok(Self {
+                hash: HashAlgorithm::Sha256,
+                signature: SignatureAlgorithm::Ecdsa,
+            }),
+            RSA_ENCRYPTION => Ok(Self {
+                hash: HashAlgorithm::Sha256,
+                signature: SignatureAlgorithm::Rsa,
+            }),
+            ID_ED_25519 => Ok(Self {
+                hash: HashAlgorithm::Intrinsic,
+                signature: SignatureAlgorithm::Ed25519,
+            }),
+            _ => Err(SignatureValidationError::MalformedKey),
+        }
+    }
+}
+
 impl Encode for SignatureAndHashAlgorithm {
     fn encode(&self, mut writer: impl Write) -> Result<(), CodecError> {
         self.hash.encode(&mut writer)?;
@@ -141,7 +408,7 @@ impl Decode for SignatureAndHashAlgorithm {
     }
 }
 
-/// See RFC 5246 7.4.1.4.1
+/// See RFC 5246 7.4.1.4.1 and RFC 8422 5.1.3
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum HashAlgorithm {
     None,
@@ -151,6 +418,7 @@ pub enum HashAlgorithm {
     Sha256,
     Sha384,
     Sha512,
+    Intrinsic,
 }
 
 impl Encode for HashAlgorithm {
@@ -163,6 +431,7 @@ impl Encode for HashAlgorithm {
             HashAlgorithm::Sha256 => 4,
             HashAlgorithm::Sha384 => 5,
             HashAlgorithm::Sha512 => 6,
+            HashAlgorithm::Intrinsic => 8,
         };
         Ok(writer.write_all(&[discriminant])?)
     }
@@ -181,6 +450,7 @@ impl Decode for HashAlgorithm {
             4 => Ok(HashAlgorithm::Sha256),
             5 => Ok(HashAlgorithm::Sha384),
             6 => Ok(HashAlgorithm::Sha512),
+            8 => Ok(HashAlgorithm::Intrinsic),
             x => Err(CodecError::UnknownVariant("HashAlgorithm", x as u64)),
         }
     }
@@ -196,17 +466,19 @@ impl Display for HashAlgorithm {
             HashAlgorithm::Sha256 => write!(f, "Sha256"),
             HashAlgorithm::Sha384 => write!(f, "Sha384"),
             HashAlgorithm::Sha512 => write!(f, "Sha512"),
+            HashAlgorithm::Intrinsic => write!(f, "Intrinsic"),
         }
     }
 }
 
-/// See RFC 5246 7.4.1.4.1
+/// See RFC 5246 7.4.1.4.1 and RFC 8422 5.1.3
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum SignatureAlgorithm {
     Anonymous,
     Rsa,
     Dsa,
     Ecdsa,
+    Ed25519,
 }
 
 impl Encode for SignatureAlgorithm {
@@ -216,6 +488,7 @@ impl Encode for SignatureAlgorithm {
             SignatureAlgorithm::Rsa => 1,
             SignatureAlgorithm::Dsa => 2,
             SignatureAlgorithm::Ecdsa => 3,
+            SignatureAlgorithm::Ed25519 => 7,
         };
         Ok(writer.write_all(&[discriminant])?)
     }
@@ -231,6 +504,7 @@ impl Decode for SignatureAlgorithm {
             1 => Ok(SignatureAlgorithm::Rsa),
             2 => Ok(SignatureAlgorithm::Dsa),
             3 => Ok(SignatureAlgorithm::Ecdsa),
+            7 => Ok(SignatureAlgorithm::Ed25519),
             x => Err(CodecError::UnknownVariant("SignatureAlgorithm", x as u64)),
         }
     }
@@ -243,6 +517,7 @@ impl Display for SignatureAlgorithm {
             SignatureAlgorithm::Rsa => write!(f, "Rsa"),
             SignatureAlgorithm::Dsa => write!(f, "Dsa"),
             SignatureAlgorithm::Ecdsa => write!(f, "Ecdsa"),
+            SignatureAlgorithm::Ed25519 => write!(f, "Ed25519"),
         }
     }
 }
@@ -0,0 +1,116 @@
+use crate::{
+    CtLog, LogId, SignatureValidationError, Version,
+    tree::{HashOutput, TreeHead},
+    v1, v2,
+};
+use serde::{Deserialize, Serialize};
+
+impl CtLog {
+    /// Validate a [`SignedTreeHead`] of any version against the key of this log
+    pub fn validate_sth(&self, sth: &SignedTreeHead) -> Result<(), SignatureValidationError> {
+        match (self.config.version(), sth) {
+            (Version::V1, SignedTreeHead::V1(sth)) => self.validate_sth_v1(sth),
+            (Version::V2, SignedTreeHead::V2(sth)) => self.validate_sth_v2(sth),
+            (version, sth) => Err(SignatureValidationError::VersionMismatch {
+                expected: version.clone(),
+                received: sth.version(),
+            }),
+        }
+    }
+}
+
+/// A signed tree head of either version
+///
+/// # Note:
+/// The serialization is untagged, such that [`v1::SignedTreeHead`] serialized by previous versions
+/// can still be read.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SignedTreeHead {
+    V1(v1::SignedTreeHead),
+    V2(v2::SignedTreeHead),
+}
+
+impl SignedTreeHead {
+    pub fn version(&self) -> Version {
+        match self {
+            SignedTreeHead::V1(_) => Version::V1,
+            SignedTreeHead::V2(_) => Version::V2,
+        }
+    }
+
+    /// Return the [`LogId`] of the log, if the STH contains it
+    ///
+    /// Only v2 STHs contain the [`LogId`].
+    pub fn log_id(&self) -> Option<LogId> {
+        match self {
+            SignedTreeHead::V1(_) => None,
+            SignedTreeHead::V2(sth) => Some(sth.log_id()),
+        }
+    }
+
+    pub fn tree_size(&self) -> u64 {
+        match self {
+            SignedTreeHead::V1(sth) => sth.tree_size(),
+            SignedTreeHead::V2(sth) => sth.tree_size(),
+        }
+    }
+
+    pub fn timestamp(&self) -> u64 {
+        match self {
+            SignedTreeHead::V1(sth) => sth.timestamp(),
+            SignedTreeHead::V2(sth) => sth.timestamp(),
+        }
+    }
+
+    pub fn root_hash(&self) -> &HashOutput {
+        match self {
+            SignedTreeHead::V1(sth) => sth.sha256_root_hash(),
+            SignedTreeHead::V2(sth) => sth.root_hash(),
+        }
+    }
+}
+
+impl From<v1::SignedTreeHead> for SignedTreeHead {
+    fn from(value: v1::SignedTreeHead) -> Self {
+        Self::V1(value)
+    }
+}
+
+impl From<v2::SignedTreeHead> for SignedTreeHead {
+    fn from(value: v2::SignedTreeHead) -> Self {
+        Self::V2(value)
+    }
+}
+
+impl From<&SignedTreeHead> for TreeHead {
+    fn from(value: &SignedTreeHead) -> Self {
+        match value {
+            SignedTreeHead::V1(sth) => TreeHead::from(sth),
+            SignedTreeHead::V2(sth) => TreeHead::from(sth),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{ARGON2025H1_STH2806, V2_ECDSA_STH};
+
+    #[test]
+    fn serialization_compatible_with_v1() {
+        let sth: v1::responses::GetSthResponse = serde_json::from_str(ARGON2025H1_STH2806).unwrap();
+        let sth = v1::SignedTreeHead::try_from(sth).unwrap();
+
+        // STHs that were stored as v1 STHs must be parsed as v1 STHs
+        let json = serde_json::to_string(&sth).unwrap();
+        let parsed: SignedTreeHead = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, SignedTreeHead::V1(sth));
+
+        let sth: v2::responses::GetSthResponse = serde_json::from_str(V2_ECDSA_STH).unwrap();
+        let sth = SignedTreeHead::V2(v2::SignedTreeHead::try_from(sth).unwrap());
+        let json = serde_json::to_string(&sth).unwrap();
+        let parsed: SignedTreeHead = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, sth);
+    }
+}
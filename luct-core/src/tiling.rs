@@ -1,13 +1,18 @@
 mod checkpoint;
 mod data_tile;
 mod tile;
+mod witness;
 
-use crate::tree::{ProofGenerationError, ProofValidationError};
+use crate::{
+    Fingerprint,
+    tree::{ProofGenerationError, ProofValidationError},
+};
 pub use checkpoint::{Checkpoint, ParseCheckpointError};
-pub use data_tile::{DataTile, DataTileId};
+pub use data_tile::{DataTile, DataTileId, TileLeaf};
 use itertools::Itertools;
 use thiserror::Error;
 pub use tile::{Tile, TileId};
+pub use witness::{Witness, WitnessPolicy, WitnessPolicyError};
 
 #[derive(Debug, Clone, PartialEq, Eq, Error)]
 pub enum TilingError {
@@ -17,6 +22,18 @@ pub enum TilingError {
     #[error("The tile that was returned by the log is malformed")]
     MalformedTile,
 
+    #[error("The data tile does not correspond to the level 0 tile")]
+    DataTileMismatch,
+
+    #[error("The hash of leaf {0} does not match the hash in the tile")]
+    LeafHashMismatch(u64),
+
+    #[error("The issuer certificate returned by the log is malformed")]
+    MalformedIssuer,
+
+    #[error("The issuer certificate returned by the log does not match the fingerprint {0}")]
+    IssuerMismatch(Fingerprint),
+
     #[error("The SCT has no leaf index")]
     LeafIndexMissing,
 
@@ -33,6 +50,13 @@ pub enum TilingError {
     AuditProofError(ProofValidationError),
 }
 
+/// Returns the [`Url`](url::Url) path, at which the issuer certificate with the [`Fingerprint`] should be found
+///
+/// Append this path to the `tile_url`, to get the full path.
+pub fn issuer_url(fingerprint: &Fingerprint) -> String {
+    format!("issuer/{}", hex::encode(fingerprint.0))
+}
+
 /// Turn an index into a url as specified in the tiling spec, i.e. "1234067" to "x001/x234/067"
 fn index_to_url(idx: u64) -> String {
     let idx = idx.to_string();
@@ -75,4 +99,16 @@ mod tests {
         assert_eq!(index_to_url(987654321), "x987/x654/321");
         assert_eq!(index_to_url(1987654321), "x001/x987/x654/321");
     }
+
+    #[test]
+    fn test_issuer_url() {
+        let mut fingerprint = Fingerprint([0; 32]);
+        fingerprint.0[0] = 0xe6;
+        fingerprint.0[31] = 0xd4;
+
+        assert_eq!(
+            issuer_url(&fingerprint),
+            format!("issuer/e6{}d4", "0".repeat(60))
+        );
+    }
 }
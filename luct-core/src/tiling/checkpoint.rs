@@ -1,7 +1,7 @@
 use std::io::{Cursor, Read, Write};
 
 use crate::{
-    CtLog, LogId, SignatureValidationError, Version,
+    CtLog, SignatureValidationError, Signer, Version,
     signature::Signature as Signed,
     tree::{HashOutput, TreeHead},
     utils::codec::{CodecError, Decode, Encode},
@@ -20,9 +20,6 @@ pub enum ParseCheckpointError {
     #[error("{field_name} could not be parsed")]
     MalformedField { field_name: &'static str },
 
-    #[error("Unexpected extensions appended to the note. We only expect notes with 3 fields")]
-    UnexpectedExtensions,
-
     #[error("The note contains no signatures.")]
     NoSignatures,
 
@@ -31,6 +28,11 @@ pub enum ParseCheckpointError {
 }
 
 impl CtLog {
+    /// Validate the note signature of the log on the [`Checkpoint`]
+    ///
+    /// The RFC 6962 note signature only covers the tree head, not the extension lines.
+    /// The extension lines are therefore dropped from the returned [`SignedTreeHead`].
+    /// Use a [`WitnessPolicy`](crate::tiling::WitnessPolicy) to authenticate them.
     pub fn validate_checkpoint(
         &self,
         checkpoint: &Checkpoint,
@@ -44,7 +46,7 @@ impl CtLog {
 
         // Find exactly one matching key in the list of keys
         // TODO: Precompute id once during initialization, rather than recomputer it here all the time
-        let id = Self::compute_checkpoint_key_id(&origin, self.log_id());
+        let id = Self::compute_checkpoint_key_id(&origin, &self.config().key);
         let sigs = checkpoint
             .signatures
             .iter()
@@ -78,14 +80,12 @@ impl CtLog {
         })
     }
 
-    fn compute_checkpoint_key_id(origin: &str, log_id: &LogId) -> [u8; 4] {
+    /// The key id is computed over the RFC 6962 log id, which is the hash of the key
+    fn compute_checkpoint_key_id(origin: &str, key: &[u8]) -> [u8; 4] {
         let mut hash = Sha256::new();
         hash.update(origin);
         hash.update([0x0A, 0x05]);
-
-        match log_id {
-            LogId::V1(log_id) => hash.update(log_id.0),
-        }
+        hash.update(Sha256::digest(key));
 
         let hash: [u8; 32] = hash.finalize().into();
         let id: [u8; 4] = hash[0..4].try_into().unwrap();
@@ -101,10 +101,11 @@ impl CtLog {
 
 #[derive(Debug, Clone)]
 pub struct Checkpoint {
-    origin: String,
+    pub(crate) origin: String,
     tree_size: u64,
     root_hash: HashOutput,
-    signatures: Vec<Signature>,
+    pub(crate) extensions: Vec<String>,
+    pub(crate) signatures: Vec<Signature>,
 }
 
 impl From<Checkpoint> for TreeHead {
@@ -117,6 +118,57 @@ impl From<Checkpoint> for TreeHead {
 }
 
 impl Checkpoint {
+    /// Create a new [`Checkpoint`] of the [`TreeHead`] without any signatures
+    ///
+    /// Use [`Checkpoint::sign`] to add signatures.
+    pub fn new(origin: String, tree_head: &TreeHead) -> Self {
+        Self {
+            origin,
+            tree_size: tree_head.tree_size,
+            root_hash: tree_head.head,
+            extensions: vec![],
+            signatures: vec![],
+        }
+    }
+
+    /// Return the extension lines of the checkpoint, without authenticating them
+    ///
+    /// # Note:
+    /// The RFC 6962 note signature of the log only covers the tree head.
+    /// Use [`WitnessPolicy::authenticated_extensions`](crate::tiling::WitnessPolicy::authenticated_extensions)
+    /// to only get extension lines, that enough witnesses cosigned.
+    pub fn unauthenticated_extensions(&self) -> &[String] {
+        &self.extensions
+    }
+
+    /// Sign the checkpoint with a RFC 6962 note signature at `timestamp`, and append the signature
+    ///
+    /// The key of the [`Signer`] is the key of the log, such that [`CtLog::validate_checkpoint`]
+    /// accepts the resulting checkpoint.
+    pub fn sign(&mut self, signer: &impl Signer, timestamp: u64) -> Result<(), CodecError> {
+        let tree_head = TreeHeadSignature {
+            version: Version::V1,
+            timestamp,
+            tree_size: self.tree_size,
+            sha256_root_hash: self.root_hash,
+        };
+        let note_sig = NoteSignature {
+            timestamp,
+            signature: Signed::sign(&tree_head, signer)?,
+        };
+
+        let mut body = Cursor::new(vec![]);
+        note_sig.encode(&mut body)?;
+
+        self.signatures.push(Signature {
+            name: self.origin.clone(),
+            id: CtLog::compute_checkpoint_key_id(&self.origin, &signer.public_key()),
+            body: body.into_inner(),
+        });
+
+        Ok(())
+    }
+
     pub fn parse_checkpoint(data: &str) -> Result<Self, ParseCheckpointError> {
         let mut data = data.lines();
 
@@ -155,10 +207,14 @@ impl Checkpoint {
                     field_name: "root_hash",
                 })?;
 
-        // Check that there is an empty line
-        let separator = data.next().ok_or(ParseCheckpointError::NoSignatures)?;
-        if !separator.is_empty() {
-            return Err(ParseCheckpointError::UnexpectedExtensions);
+        // Parse the extension lines, which are terminated by an empty line
+        let mut extensions = vec![];
+        loop {
+            let line = data.next().ok_or(ParseCheckpointError::NoSignatures)?;
+            if line.is_empty() {
+                break;
+            }
+            extensions.push(line.to_string());
         }
 
         // Parse the signatures
@@ -177,18 +233,49 @@ impl Checkpoint {
             origin,
             tree_size,
             root_hash,
+            extensions,
             signatures,
         })
     }
 
-    // TODO: `as_string` function and roundtrip test
+    /// Render the checkpoint as a signed note
+    ///
+    /// This is the inverse of [`Checkpoint::parse_checkpoint`].
+    pub fn as_string(&self) -> String {
+        let mut note = self.body();
+        note.push('\n');
+
+        for signature in &self.signatures {
+            note.push_str(&signature.as_string());
+            note.push('\n');
+        }
+
+        note
+    }
+
+    /// The body of the note, which is the message that signatures are computed over
+    pub(crate) fn body(&self) -> String {
+        let mut body = format!(
+            "{}\n{}\n{}\n",
+            self.origin,
+            self.tree_size,
+            BASE64_STANDARD.encode(self.root_hash)
+        );
+
+        for extension in &self.extensions {
+            body.push_str(extension);
+            body.push('\n');
+        }
+
+        body
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct Signature {
-    name: String,
-    id: [u8; 4],
-    body: Vec<u8>,
+pub(crate) struct Signature {
+    pub(crate) name: String,
+    pub(crate) id: [u8; 4],
+    pub(crate) body: Vec<u8>,
 }
 
 impl Signature {
@@ -207,7 +294,10 @@ impl Signature {
         Some(Self { name, id, body })
     }
 
-    // TODO: `as_string` function
+    fn as_string(&self) -> String {
+        let data = [self.id.as_slice(), &self.body].concat();
+        format!("— {} {}", self.name, BASE64_STANDARD.encode(data))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -237,6 +327,7 @@ impl Decode for NoteSignature {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use p256::ecdsa::SigningKey;
 
     const ARCHE2026H1_CHECKPOINT: &str =
         include_str!("../../../testdata/arche2026h1-signed-note.txt");
@@ -271,7 +362,7 @@ mod tests {
         assert_eq!(checkpoint.tree_size, 1822167730);
 
         let config = serde_json::from_str(ARCHE2026H1).unwrap();
-        let log = CtLog::new(config);
+        let log = CtLog::new(config).unwrap();
 
         log.validate_checkpoint(&checkpoint).unwrap();
     }
@@ -284,8 +375,84 @@ mod tests {
         assert_eq!(checkpoint.tree_size, 804475391);
 
         let config = serde_json::from_str(SYCAMORE2026H1).unwrap();
-        let log = CtLog::new(config);
+        let log = CtLog::new(config).unwrap();
 
         log.validate_checkpoint(&checkpoint).unwrap();
     }
+
+    #[test]
+    fn checkpoint_roundtrip() {
+        for note in [ARCHE2026H1_CHECKPOINT, SYCAMORE2026H1_CHECKPOINT] {
+            let checkpoint = Checkpoint::parse_checkpoint(note).unwrap();
+            assert_eq!(checkpoint.as_string(), note);
+        }
+    }
+
+    #[test]
+    fn sign_and_validate_checkpoint() {
+        let signer = SigningKey::from_slice(&[0x42; 32]).unwrap();
+        let config = format!(
+            "{{
+                \"description\": \"luCT test log\",
+                \"key\": \"{}\",
+                \"url\": \"https://test.luct.dev/2026h1/\",
+                \"tile_url\": \"https://tiles.test.luct.dev/2026h1/\",
+                \"mmd\": 60
+            }}",
+            BASE64_STANDARD.encode(signer.public_key())
+        );
+        let log = CtLog::new(serde_json::from_str(&config).unwrap()).unwrap();
+
+        let tree_head = TreeHead {
+            tree_size: 1234,
+            head: [7; 32],
+        };
+        let mut checkpoint = Checkpoint::new("test.luct.dev/2026h1".to_string(), &tree_head);
+        checkpoint.sign(&signer, 1767225600000).unwrap();
+
+        let note = checkpoint.as_string();
+        let checkpoint = Checkpoint::parse_checkpoint(&note).unwrap();
+        assert_eq!(checkpoint.as_string(), note);
+
+        let sth = log.validate_checkpoint(&checkpoint).unwrap();
+        assert_eq!(sth.tree_size(), 1234);
+        assert_eq!(sth.timestamp(), 1767225600000);
+        assert_eq!(TreeHead::from(&sth), tree_head);
+
+        // A checkpoint signed by another key is rejected
+        let mut checkpoint = Checkpoint::new("test.luct.dev/2026h1".to_string(), &tree_head);
+        checkpoint
+            .sign(&SigningKey::from_slice(&[0x43; 32]).unwrap(), 1767225600000)
+            .unwrap();
+        assert!(log.validate_checkpoint(&checkpoint).is_err());
+    }
+
+    #[test]
+    fn checkpoint_with_extensions() {
+        let signer = SigningKey::from_slice(&[0x42; 32]).unwrap();
+        let tree_head = TreeHead {
+            tree_size: 1234,
+            head: [7; 32],
+        };
+        let mut checkpoint = Checkpoint::new("test.luct.dev/2026h1".to_string(), &tree_head);
+        checkpoint.extensions = vec!["ext-a 1".to_string(), "ext-b 2".to_string()];
+        checkpoint.sign(&signer, 1767225600000).unwrap();
+
+        let note = checkpoint.as_string();
+        assert!(note.contains("\next-a 1\next-b 2\n\n— test.luct.dev/2026h1 "));
+
+        let parsed = Checkpoint::parse_checkpoint(&note).unwrap();
+        assert_eq!(parsed.unauthenticated_extensions(), ["ext-a 1", "ext-b 2"]);
+        assert_eq!(parsed.tree_size, 1234);
+        assert_eq!(parsed.signatures, checkpoint.signatures);
+        assert_eq!(parsed.as_string(), note);
+
+        // A note without the empty line terminating the extension lines has no signatures
+        let note =
+            "test.luct.dev/2026h1\n1234\nBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwc=\next-a 1\n";
+        assert_eq!(
+            Checkpoint::parse_checkpoint(note).unwrap_err(),
+            ParseCheckpointError::NoSignatures
+        );
+    }
 }
@@ -1,5 +1,23 @@
-use crate::tiling::index_to_url;
-use std::num::NonZeroU8;
+use crate::{
+    Certificate, Fingerprint, Version,
+    store::Hashable,
+    tiling::{Tile, TilingError, index_to_url},
+    utils::{
+        codec::{CodecError, Decode},
+        codec_vec::CodecVec,
+        u24::U24,
+    },
+    v1::{
+        LogEntry, MerkleTreeLeaf,
+        tree::{Leaf, TimestampedEntry},
+    },
+};
+use std::{
+    io::{Cursor, Read},
+    num::NonZeroU8,
+    sync::Arc,
+};
+use x509_cert::{Certificate as Cert, der::Decode as CertDecode};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DataTileId {
@@ -37,17 +55,189 @@ impl DataTileId {
             None => format!("tile/data/{}", index_url),
         }
     }
+
+    /// Create a [`DataTile`], by adding the data to this [`DataTileId`]
+    ///
+    /// Unlike a [`Tile`], the size of the entries is not fixed, so the data is only checked when
+    /// decoding the leaves.
+    pub fn with_data(self, data: Arc<Vec<u8>>) -> DataTile {
+        DataTile { id: self, data }
+    }
+
+    /// Returns `true`, if this [`DataTileId`] is partial, `false` otherwise
+    pub fn is_partial(&self) -> bool {
+        self.partial.is_some()
+    }
+
+    /// Turn a partial [`DataTileId`] into one that is not partial
+    ///
+    /// Does nothing if [`DataTileId`] is already partial.
+    pub fn into_unpartial(mut self) -> Self {
+        self.partial = None;
+        self
+    }
+
+    fn width(&self) -> usize {
+        match self.partial {
+            Some(val) => usize::from(u8::from(val)),
+            None => 256,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DataTile {
     id: DataTileId,
-    data: Vec<u8>,
+    data: Arc<Vec<u8>>,
+}
+
+impl DataTile {
+    /// Return the [`DataTileId`] of this [`DataTile`]
+    pub fn id(&self) -> &DataTileId {
+        &self.id
+    }
+
+    /// Decode the [`TileLeaves`](TileLeaf) contained in this [`DataTile`]
+    ///
+    /// Fails, if the data can not be decoded or the number of entries does not match the width
+    /// of the tile.
+    pub fn leaves(&self) -> Result<Vec<TileLeaf>, TilingError> {
+        let mut reader = Cursor::new(self.data.as_slice());
+
+        let mut leaves = vec![];
+        while (reader.position() as usize) < self.data.len() {
+            let leaf = TileLeaf::decode(&mut reader).map_err(|_| TilingError::MalformedTile)?;
+            leaves.push(leaf);
+        }
+
+        if leaves.len() != self.id.width() {
+            return Err(TilingError::MalformedTile);
+        }
+
+        Ok(leaves)
+    }
+
+    /// Decode the leaves and check them against the hashes of the corresponding level 0 [`Tile`]
+    ///
+    /// Both tiles need to have the same index and width, i.e. if one of them was fetched as
+    /// a full tile instead of a partial one, the other one must be too.
+    pub fn validate(&self, tile: &Tile) -> Result<Vec<TileLeaf>, TilingError> {
+        if tile.id().level != 0 || tile.id().index != self.id.index {
+            return Err(TilingError::DataTileMismatch);
+        }
+
+        let leaves = self.leaves()?;
+        let hashes = tile.hashes();
+        if leaves.len() != hashes.len() {
+            return Err(TilingError::DataTileMismatch);
+        }
+
+        for (idx, (leaf, hash)) in leaves.iter().zip(hashes).enumerate() {
+            if leaf.as_merkle_tree_leaf().hash() != hash {
+                return Err(TilingError::LeafHashMismatch(
+                    self.id.index * 256 + idx as u64,
+                ));
+            }
+        }
+
+        Ok(leaves)
+    }
+}
+
+/// A single entry of a [`DataTile`]
+///
+/// Besides the [`MerkleTreeLeaf`], the entry contains the precertificate, if it is a precert
+/// entry, and the fingerprints of the chain, that was submitted alongside the certificate.
+///
+/// See C2SP static-ct-api
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TileLeaf {
+    pub(crate) entry: TimestampedEntry,
+    pub(crate) pre_certificate: Option<Certificate>,
+    pub(crate) certificate_chain: Vec<Fingerprint>,
+}
+
+impl TileLeaf {
+    /// Returns the [`MerkleTreeLeaf`] of this entry, i.e. the value that is hashed into the tree
+    pub fn as_merkle_tree_leaf(&self) -> MerkleTreeLeaf {
+        MerkleTreeLeaf {
+            version: Version::V1,
+            leaf: Leaf::TimestampedEntry(self.entry.clone()),
+        }
+    }
+
+    pub fn timestamp(&self) -> u64 {
+        self.entry.timestamp
+    }
+
+    /// Returns the index of this entry in the log, as recorded in the leaf extensions
+    pub fn leaf_index(&self) -> Option<u64> {
+        self.entry
+            .extensions
+            .leaf_index()
+            .map(|leaf_index| *leaf_index)
+    }
+
+    /// Returns `true`, if this entry was logged from a precertificate
+    pub fn is_precert(&self) -> bool {
+        matches!(self.entry.log_entry, LogEntry::PreCert(_))
+    }
+
+    /// Returns the precertificate as submitted to the log, if this is a precert entry
+    pub fn pre_certificate(&self) -> Option<&Certificate> {
+        self.pre_certificate.as_ref()
+    }
+
+    /// Returns the SHA-256 fingerprints of the chain, starting with the issuer
+    ///
+    /// The certificates can be fetched from the `issuer` endpoint of the log.
+    pub fn certificate_chain(&self) -> &[Fingerprint] {
+        &self.certificate_chain
+    }
+}
+
+impl Decode for TileLeaf {
+    fn decode(mut reader: impl Read) -> Result<Self, CodecError> {
+        let entry = TimestampedEntry::decode(&mut reader)?;
+
+        let pre_certificate = match entry.log_entry {
+            LogEntry::X509(_) => None,
+            LogEntry::PreCert(_) => {
+                let cert_bytes = CodecVec::<U24>::decode(&mut reader)?;
+                Some(Certificate(Cert::from_der(cert_bytes.as_ref())?))
+            }
+        };
+
+        let chain_bytes = CodecVec::<u16>::decode(&mut reader)?;
+        let chain_bytes = chain_bytes.as_ref();
+        if !chain_bytes.len().is_multiple_of(32) {
+            return Err(CodecError::UnexpectedSize {
+                read: chain_bytes.len(),
+                expected: chain_bytes.len().next_multiple_of(32),
+            });
+        }
+        let certificate_chain = chain_bytes
+            .chunks_exact(32)
+            .map(|fingerprint| Fingerprint(fingerprint.try_into().unwrap()))
+            .collect();
+
+        Ok(Self {
+            entry,
+            pre_certificate,
+            certificate_chain,
+        })
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::{tests::CERT_CHAIN_GOOGLE_COM, tiling::TileId, tree::NodeKey};
+    use base64::{Engine, prelude::BASE64_STANDARD};
+    use sha2::{Digest, Sha256};
+    use x509_cert::der::Encode as CertEncode;
+
+    const GOOGLE_GET_ENTRY: &str = include_str!("../../../testdata/google-entry.json");
 
     #[test]
     fn as_url() {
@@ -78,6 +268,128 @@ mod test {
         );
     }
 
+    #[test]
+    fn decode_data_tile() {
+        let (data, hashes, chain) = get_tiles();
+        let tile = TileId::from_node_key(&NodeKey::leaf(0), 2)
+            .unwrap()
+            .with_data(Arc::new(hashes))
+            .unwrap();
+        let data_tile = DataTileId::from_index(0, 2)
+            .unwrap()
+            .with_data(Arc::new(data));
+
+        let leaves = data_tile.validate(&tile).unwrap();
+        assert_eq!(leaves.len(), 2);
+
+        // The first entry is the precert entry of the google.com certificate
+        assert!(leaves[0].is_precert());
+        assert_eq!(leaves[0].timestamp(), 1748856933195);
+        assert_eq!(leaves[0].leaf_index(), None);
+        assert_eq!(
+            leaves[0].pre_certificate().unwrap().get_subject_name(),
+            "*.google.com"
+        );
+        assert_eq!(leaves[0].certificate_chain(), &chain[..]);
+
+        // The second entry is a final certificate with a leaf index extension
+        assert!(!leaves[1].is_precert());
+        assert_eq!(leaves[1].timestamp(), 1767225600000);
+        assert_eq!(leaves[1].leaf_index(), Some(1));
+        assert_eq!(leaves[1].pre_certificate(), None);
+        assert_eq!(leaves[1].certificate_chain(), &chain[..1]);
+    }
+
+    #[test]
+    fn reject_malformed_data_tile() {
+        let (data, mut hashes, _) = get_tiles();
+
+        // A data tile with fewer entries than its width
+        let data_tile = data_tile_id(0, Some(3)).with_data(Arc::new(data.clone()));
+        assert_eq!(data_tile.leaves(), Err(TilingError::MalformedTile));
+
+        // A data tile with trailing data
+        let mut trailing = data.clone();
+        trailing.push(0);
+        let data_tile = data_tile_id(0, Some(2)).with_data(Arc::new(trailing));
+        assert_eq!(data_tile.leaves(), Err(TilingError::MalformedTile));
+
+        // A level 0 tile of a different index
+        let data_tile = data_tile_id(0, Some(2)).with_data(Arc::new(data));
+        let tile = TileId::from_node_key(&NodeKey::leaf(256), 258)
+            .unwrap()
+            .with_data(Arc::new(hashes.clone()))
+            .unwrap();
+        assert_eq!(
+            data_tile.validate(&tile),
+            Err(TilingError::DataTileMismatch)
+        );
+
+        // A level 0 tile with a different leaf hash
+        hashes[40] ^= 1;
+        let tile = TileId::from_node_key(&NodeKey::leaf(0), 2)
+            .unwrap()
+            .with_data(Arc::new(hashes))
+            .unwrap();
+        assert_eq!(
+            data_tile.validate(&tile),
+            Err(TilingError::LeafHashMismatch(1))
+        );
+    }
+
+    /// Assemble a data tile and the corresponding level 0 tile from raw bytes
+    ///
+    /// The first entry is the precert entry from `google-entry.json`, the second one is an x509
+    /// entry of the issuer certificate of `google-chain.pem`.
+    fn get_tiles() -> (Vec<u8>, Vec<u8>, Vec<Fingerprint>) {
+        let response: serde_json::Value = serde_json::from_str(GOOGLE_GET_ENTRY).unwrap();
+        let entry = &response["entries"][0];
+        let leaf_input = BASE64_STANDARD
+            .decode(entry["leaf_input"].as_str().unwrap())
+            .unwrap();
+        let extra_data = BASE64_STANDARD
+            .decode(entry["extra_data"].as_str().unwrap())
+            .unwrap();
+
+        let chain = Cert::load_pem_chain(CERT_CHAIN_GOOGLE_COM.as_bytes())
+            .unwrap()
+            .into_iter()
+            .skip(1)
+            .map(|cert| Certificate(cert).fingerprint_sha256())
+            .collect::<Vec<_>>();
+
+        let mut data = vec![];
+        let mut hashes = vec![];
+
+        // The timestamped entry of the precert leaf, followed by the precertificate and chain
+        let pre_cert_len = u32::from_be_bytes([0, extra_data[0], extra_data[1], extra_data[2]]);
+        data.extend_from_slice(&leaf_input[2..]);
+        data.extend_from_slice(&extra_data[..3 + pre_cert_len as usize]);
+        data.extend_from_slice(&(32 * chain.len() as u16).to_be_bytes());
+        chain
+            .iter()
+            .for_each(|fingerprint| data.extend_from_slice(&fingerprint.0));
+        hashes.extend(Sha256::digest([&[0], leaf_input.as_slice()].concat()));
+
+        // An x509 entry with a leaf index extension
+        let mut cert = vec![];
+        let _ = Cert::load_pem_chain(CERT_CHAIN_GOOGLE_COM.as_bytes()).unwrap()[1]
+            .encode_to_vec(&mut cert)
+            .unwrap();
+        let mut entry = vec![];
+        entry.extend_from_slice(&1767225600000u64.to_be_bytes());
+        entry.extend_from_slice(&[0, 0]);
+        entry.extend_from_slice(&(cert.len() as u32).to_be_bytes()[1..]);
+        entry.extend_from_slice(&cert);
+        entry.extend_from_slice(&[0, 8, 0, 0, 5, 0, 0, 0, 0, 1]);
+        data.extend_from_slice(&entry);
+        data.extend_from_slice(&[0, 32]);
+        data.extend_from_slice(&chain[0].0);
+        hashes.extend(Sha256::digest([&[0, 0, 0], entry.as_slice()].concat()));
+
+        (data, hashes, chain)
+    }
+
     fn data_tile_id(index: u64, partial: Option<u8>) -> DataTileId {
         DataTileId {
             index,
@@ -7,8 +7,8 @@ use std::{num::NonZeroU8, sync::Arc};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TileId {
-    level: u8,
-    index: u64,
+    pub(crate) level: u8,
+    pub(crate) index: u64,
     partial: Option<NonZeroU8>,
     tree_size: u64,
 }
@@ -117,6 +117,14 @@ impl Tile {
         &self.id
     }
 
+    /// Returns the hashes of the base nodes contained within this tile
+    pub(crate) fn hashes(&self) -> Vec<HashOutput> {
+        self.data
+            .chunks_exact(32)
+            .map(|hash| hash.try_into().unwrap())
+            .collect()
+    }
+
     /// Recomputes the [`NodeKeys`](NodeKey) contained within this tile
     pub fn recompute_node_keys(&self) -> Vec<(NodeKey, HashOutput)> {
         // Get the initial Node keys
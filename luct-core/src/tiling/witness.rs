@@ -0,0 +1,430 @@
+use crate::{
+    SignatureValidationError,
+    tiling::checkpoint::{Checkpoint, Signature},
+};
+use base64::{Engine, prelude::BASE64_STANDARD};
+use ed25519_dalek::{Signature as Ed25519Signature, VerifyingKey};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Display},
+};
+use thiserror::Error;
+
+/// The signature type of timestamped Ed25519 cosignatures
+///
+/// See C2SP signed-note
+const COSIGNATURE_V1: u8 = 0x04;
+
+/// A witness, that cosigns checkpoints of logs
+///
+/// A witness is specified by its verifier key, i.e. `<name>+<key id>+<key>`, where the key id is
+/// the hex encoded key id and the key is the base64 encoding of the signature type and public key.
+///
+/// See C2SP tlog-cosignature
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Witness {
+    name: String,
+    id: [u8; 4],
+    key: VerifyingKey,
+}
+
+impl Witness {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn compute_key_id(name: &str, key: &VerifyingKey) -> [u8; 4] {
+        let mut hash = Sha256::new();
+        hash.update(name);
+        hash.update([0x0A, COSIGNATURE_V1]);
+        hash.update(key.as_bytes());
+
+        let hash: [u8; 32] = hash.finalize().into();
+        hash[0..4].try_into().unwrap()
+    }
+
+    /// Validate a cosignature of this witness over the checkpoint
+    fn validate_cosignature(&self, checkpoint: &Checkpoint, signature: &Signature) -> bool {
+        if signature.name != self.name || signature.id != self.id || signature.body.len() != 72 {
+            return false;
+        }
+
+        let (timestamp, cosignature) = signature.body.split_at(8);
+        let timestamp = u64::from_be_bytes(timestamp.try_into().unwrap());
+        let Ok(cosignature) = Ed25519Signature::from_slice(cosignature) else {
+            return false;
+        };
+
+        let message = format!("cosignature/v1\ntime {timestamp}\n{}", checkpoint.body());
+        self.key
+            .verify_strict(message.as_bytes(), &cosignature)
+            .is_ok()
+    }
+}
+
+impl Display for Witness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let key = [&[COSIGNATURE_V1], self.key.as_bytes().as_slice()].concat();
+        write!(
+            f,
+            "{}+{}+{}",
+            self.name,
+            hex::encode(self.id),
+            BASE64_STANDARD.encode(key)
+        )
+    }
+}
+
+impl TryFrom<&str> for Witness {
+    type Error = ();
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut parts = value.split('+');
+        let (Some(name), Some(id), Some(key), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(());
+        };
+
+        let key = BASE64_STANDARD.decode(key).map_err(|_| ())?;
+        let Some((&COSIGNATURE_V1, key)) = key.split_first() else {
+            return Err(());
+        };
+        let key = VerifyingKey::try_from(key).map_err(|_| ())?;
+
+        // The key id must match the one computed from the name and key
+        let computed_id = Self::compute_key_id(name, &key);
+        if hex::decode(id).map_err(|_| ())? != computed_id {
+            return Err(());
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            id: computed_id,
+            key,
+        })
+    }
+}
+
+impl Serialize for Witness {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Witness {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let key = String::deserialize(deserializer)?;
+        Witness::try_from(key.as_str())
+            .map_err(|_| serde::de::Error::custom(format!("Invalid witness key {key}")))
+    }
+}
+
+/// A policy that requires checkpoints to be cosigned by at least `threshold` of the `witnesses`
+///
+/// A log can only present a split view to a client, that enforces this policy, if the log
+/// also convinces `threshold` witnesses of the same split view.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedWitnessPolicy")]
+pub struct WitnessPolicy {
+    threshold: usize,
+    witnesses: Vec<Witness>,
+}
+
+#[derive(Deserialize)]
+struct UncheckedWitnessPolicy {
+    threshold: usize,
+    witnesses: Vec<Witness>,
+}
+
+impl TryFrom<UncheckedWitnessPolicy> for WitnessPolicy {
+    type Error = WitnessPolicyError;
+
+    fn try_from(value: UncheckedWitnessPolicy) -> Result<Self, Self::Error> {
+        Self::new(value.threshold, value.witnesses)
+    }
+}
+
+impl WitnessPolicy {
+    /// Create a policy, that requires `threshold` of the `witnesses` to cosign a checkpoint
+    ///
+    /// The threshold must be between 1 and the number of witnesses, and every witness
+    /// may only be listed once.
+    pub fn new(threshold: usize, witnesses: Vec<Witness>) -> Result<Self, WitnessPolicyError> {
+        if threshold == 0 {
+            return Err(WitnessPolicyError::ZeroThreshold);
+        }
+
+        if threshold > witnesses.len() {
+            return Err(WitnessPolicyError::UnreachableThreshold {
+                threshold,
+                witnesses: witnesses.len(),
+            });
+        }
+
+        let mut keys = BTreeSet::new();
+        if let Some(duplicate) = witnesses
+            .iter()
+            .find(|witness| !keys.insert(witness.key.as_bytes()))
+        {
+            return Err(WitnessPolicyError::DuplicateWitness(duplicate.to_string()));
+        }
+
+        Ok(Self {
+            threshold,
+            witnesses,
+        })
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub fn witnesses(&self) -> &[Witness] {
+        &self.witnesses
+    }
+
+    /// Check that the [`Checkpoint`] carries valid cosignatures of enough witnesses
+    ///
+    /// Signatures of unknown witnesses and invalid cosignatures are ignored.
+    pub fn validate(&self, checkpoint: &Checkpoint) -> Result<(), SignatureValidationError> {
+        let cosigned = self
+            .witnesses
+            .iter()
+            .filter(|witness| {
+                checkpoint
+                    .signatures
+                    .iter()
+                    .any(|signature| witness.validate_cosignature(checkpoint, signature))
+            })
+            .count();
+
+        if cosigned < self.threshold {
+            return Err(SignatureValidationError::InsufficientCosignatures {
+                received: cosigned,
+                expected: self.threshold,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Return the extension lines of the [`Checkpoint`], if enough witnesses cosigned them
+    ///
+    /// The note signature of the log does not cover the extension lines, but cosignatures do.
+    pub fn authenticated_extensions<'a>(
+        &self,
+        checkpoint: &'a Checkpoint,
+    ) -> Result<&'a [String], SignatureValidationError> {
+        self.validate(checkpoint)?;
+        Ok(&checkpoint.extensions)
+    }
+}
+
+/// Error returned when a [`WitnessPolicy`] could never be met or would accept any checkpoint
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum WitnessPolicyError {
+    #[error("The threshold of a witness policy must be at least 1")]
+    ZeroThreshold,
+
+    #[error("The threshold {threshold} exceeds the number of witnesses {witnesses}")]
+    UnreachableThreshold { threshold: usize, witnesses: usize },
+
+    #[error("The witness {0} is listed more than once")]
+    DuplicateWitness(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tree::TreeHead;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    const ORIGIN: &str = "test.luct.dev/2026h1";
+
+    fn get_witness(name: &str, seed: u8) -> (Witness, SigningKey) {
+        let key = SigningKey::from_bytes(&[seed; 32]);
+        let witness = Witness {
+            name: name.to_string(),
+            id: Witness::compute_key_id(name, &key.verifying_key()),
+            key: key.verifying_key(),
+        };
+
+        (witness, key)
+    }
+
+    fn cosign(checkpoint: &mut Checkpoint, witness: &Witness, key: &SigningKey, timestamp: u64) {
+        let message = format!("cosignature/v1\ntime {timestamp}\n{}", checkpoint.body());
+        let signature = key.sign(message.as_bytes());
+
+        checkpoint.signatures.push(Signature {
+            name: witness.name.clone(),
+            id: witness.id,
+            body: [timestamp.to_be_bytes().as_slice(), &signature.to_bytes()].concat(),
+        });
+    }
+
+    fn get_checkpoint() -> Checkpoint {
+        let tree_head = TreeHead {
+            tree_size: 1234,
+            head: [7; 32],
+        };
+        Checkpoint::new(ORIGIN.to_string(), &tree_head)
+    }
+
+    #[test]
+    fn witness_key_roundtrip() {
+        let (witness, _) = get_witness("witness.luct.dev", 1);
+
+        let vkey = witness.to_string();
+        assert!(vkey.starts_with("witness.luct.dev+"));
+        assert_eq!(Witness::try_from(vkey.as_str()).unwrap(), witness);
+
+        let json = serde_json::to_string(&witness).unwrap();
+        assert_eq!(serde_json::from_str::<Witness>(&json).unwrap(), witness);
+
+        // A key id that does not match the key is rejected
+        let (other, _) = get_witness("other.luct.dev", 1);
+        let vkey = vkey.replace(&hex::encode(witness.id), &hex::encode(other.id));
+        assert!(Witness::try_from(vkey.as_str()).is_err());
+    }
+
+    #[test]
+    fn validate_k_of_n() {
+        let witnesses = (1..=5)
+            .map(|idx| get_witness(&format!("witness{idx}.luct.dev"), idx))
+            .collect::<Vec<_>>();
+        let policy = WitnessPolicy::new(
+            2,
+            witnesses
+                .iter()
+                .map(|(witness, _)| witness.clone())
+                .collect(),
+        )
+        .unwrap();
+
+        let mut checkpoint = get_checkpoint();
+        assert_eq!(
+            policy.validate(&checkpoint),
+            Err(SignatureValidationError::InsufficientCosignatures {
+                received: 0,
+                expected: 2
+            })
+        );
+
+        // A cosignature from an unknown witness does not count
+        let (unknown, unknown_key) = get_witness("unknown.luct.dev", 42);
+        cosign(&mut checkpoint, &unknown, &unknown_key, 1767225600);
+
+        // Two cosignatures from the same witness only count once
+        let (witness, key) = &witnesses[0];
+        cosign(&mut checkpoint, witness, key, 1767225600);
+        cosign(&mut checkpoint, witness, key, 1767225601);
+        assert_eq!(
+            policy.validate(&checkpoint),
+            Err(SignatureValidationError::InsufficientCosignatures {
+                received: 1,
+                expected: 2
+            })
+        );
+
+        // A cosignature made by the wrong key does not count
+        let (witness, _) = &witnesses[1];
+        cosign(&mut checkpoint, witness, &unknown_key, 1767225600);
+        assert!(policy.validate(&checkpoint).is_err());
+
+        let (witness, key) = &witnesses[2];
+        cosign(&mut checkpoint, witness, key, 1767225600);
+        policy.validate(&checkpoint).unwrap();
+
+        // The cosignatures survive a roundtrip through the note format
+        let checkpoint = Checkpoint::parse_checkpoint(&checkpoint.as_string()).unwrap();
+        policy.validate(&checkpoint).unwrap();
+    }
+
+    #[test]
+    fn cosignature_covers_checkpoint() {
+        let (witness, key) = get_witness("witness.luct.dev", 1);
+        let policy = WitnessPolicy::new(1, vec![witness.clone()]).unwrap();
+
+        let mut checkpoint = get_checkpoint();
+        cosign(&mut checkpoint, &witness, &key, 1767225600);
+        policy.validate(&checkpoint).unwrap();
+
+        // Moving the cosignature to a different checkpoint invalidates it
+        let tree_head = TreeHead {
+            tree_size: 1235,
+            head: [7; 32],
+        };
+        let mut other_checkpoint = Checkpoint::new(ORIGIN.to_string(), &tree_head);
+        other_checkpoint.signatures = checkpoint.signatures.clone();
+        assert!(policy.validate(&other_checkpoint).is_err());
+    }
+
+    #[test]
+    fn cosignature_covers_extensions() {
+        let (witness, key) = get_witness("witness.luct.dev", 1);
+        let policy = WitnessPolicy::new(1, vec![witness.clone()]).unwrap();
+
+        let mut checkpoint = get_checkpoint();
+        checkpoint.extensions = vec!["ext 1".to_string()];
+        cosign(&mut checkpoint, &witness, &key, 1767225600);
+
+        let note = checkpoint.as_string();
+        let parsed = Checkpoint::parse_checkpoint(&note).unwrap();
+        assert_eq!(
+            policy.authenticated_extensions(&parsed),
+            Ok(["ext 1".to_string()].as_slice())
+        );
+
+        // Changing the extension line invalidates the cosignature
+        let tampered = note.replace("ext 1", "ext 2");
+        let tampered = Checkpoint::parse_checkpoint(&tampered).unwrap();
+        assert_eq!(tampered.unauthenticated_extensions(), ["ext 2"]);
+        assert!(policy.authenticated_extensions(&tampered).is_err());
+    }
+
+    #[test]
+    fn reject_invalid_policy() {
+        let (first, _) = get_witness("first.luct.dev", 1);
+        let (second, _) = get_witness("second.luct.dev", 2);
+
+        assert_eq!(
+            WitnessPolicy::new(0, vec![first.clone()]),
+            Err(WitnessPolicyError::ZeroThreshold)
+        );
+        assert_eq!(
+            WitnessPolicy::new(3, vec![first.clone(), second.clone()]),
+            Err(WitnessPolicyError::UnreachableThreshold {
+                threshold: 3,
+                witnesses: 2
+            })
+        );
+        assert_eq!(
+            WitnessPolicy::new(2, vec![first.clone(), first.clone()]),
+            Err(WitnessPolicyError::DuplicateWitness(first.to_string()))
+        );
+
+        // Deserialization applies the same checks
+        let policy = WitnessPolicy::new(2, vec![first.clone(), second.clone()]).unwrap();
+        let json = serde_json::to_string(&policy).unwrap();
+        assert_eq!(
+            serde_json::from_str::<WitnessPolicy>(&json).unwrap(),
+            policy
+        );
+
+        let json = serde_json::json!({ "threshold": 0, "witnesses": [first.clone()] });
+        assert!(serde_json::from_value::<WitnessPolicy>(json).is_err());
+
+        let json = serde_json::json!({ "threshold": 2, "witnesses": [first.clone(), first] });
+        assert!(serde_json::from_value::<WitnessPolicy>(json).is_err());
+    }
+}
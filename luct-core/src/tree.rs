@@ -3,6 +3,7 @@ pub use crate::tree::{
     consistency::ConsistencyProof,
     inclusion::AuditProof,
     node::{Node, NodeKey},
+    range::RangeProof,
 };
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -10,6 +11,7 @@ use thiserror::Error;
 mod consistency;
 mod inclusion;
 mod node;
+mod range;
 
 pub type HashOutput = [u8; 32];
 
@@ -44,6 +46,15 @@ pub enum ProofValidationError {
 
     #[error("Merkle path was too long")]
     PathTooLong,
+
+    #[error("The log returned a structure that is not the expected proof")]
+    UnexpectedProofType,
+
+    #[error("The proof was issued by another log")]
+    LogIdMismatch,
+
+    #[error("The proof is for tree size {received}, expected {expected}")]
+    TreeSizeMismatch { expected: u64, received: u64 },
 }
 
 #[derive(Debug, Clone)]
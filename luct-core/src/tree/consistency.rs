@@ -1,8 +1,10 @@
 use crate::{
     store::{StoreRead, Hashable},
     tree::{HashOutput, Node, NodeKey, ProofGenerationError, ProofValidationError, Tree, TreeHead},
+    utils::base64::Base64,
 };
 use futures::{FutureExt, future::join_all};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
 
 impl<N, L> Tree<N, L>
 where
@@ -74,6 +76,38 @@ pub struct ConsistencyProof {
     pub(crate) path: Vec<HashOutput>,
 }
 
+/// Serialized as a list of base64 encoded hashes, like the `consistency` field of RFC 6962
+impl Serialize for ConsistencyProof {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.path
+            .iter()
+            .map(|hash| Base64(hash.to_vec()))
+            .collect::<Vec<_>>()
+            .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ConsistencyProof {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let path = Vec::<Base64<Vec<u8>>>::deserialize(deserializer)?
+            .into_iter()
+            .map(|hash| {
+                hash.0.try_into().map_err(|hash: Vec<u8>| {
+                    de::Error::invalid_length(hash.len(), &"a hash of 32 bytes")
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { path })
+    }
+}
+
 impl ConsistencyProof {
     /// This follows RFC 9162 2.1.4.2
     pub fn validate(
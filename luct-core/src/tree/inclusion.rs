@@ -1,8 +1,10 @@
 use crate::{
     store::{StoreRead, Hashable},
     tree::{HashOutput, Node, NodeKey, ProofGenerationError, ProofValidationError, Tree, TreeHead},
+    utils::base64::Base64,
 };
 use futures::{FutureExt, future::join_all};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
 
 impl<N, L> Tree<N, L>
 where
@@ -36,7 +38,7 @@ where
     }
 }
 
-fn get_audit_proof<F, O>(head: &TreeHead, index: u64, get: F) -> Vec<O>
+pub(super) fn get_audit_proof<F, O>(head: &TreeHead, index: u64, get: F) -> Vec<O>
 where
     F: Fn(NodeKey) -> O,
 {
@@ -69,6 +71,49 @@ pub struct AuditProof {
     pub(crate) path: Vec<HashOutput>,
 }
 
+/// An [`AuditProof`] in the format of the `get-proof-by-hash` response of RFC 6962
+#[derive(Serialize, Deserialize)]
+struct SerializedAuditProof {
+    leaf_index: u64,
+    audit_path: Vec<Base64<Vec<u8>>>,
+}
+
+impl Serialize for AuditProof {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        SerializedAuditProof {
+            leaf_index: self.index,
+            audit_path: self.path.iter().map(|hash| Base64(hash.to_vec())).collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for AuditProof {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let proof = SerializedAuditProof::deserialize(deserializer)?;
+        let path = proof
+            .audit_path
+            .into_iter()
+            .map(|hash| {
+                hash.0.try_into().map_err(|hash: Vec<u8>| {
+                    de::Error::invalid_length(hash.len(), &"a hash of 32 bytes")
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            index: proof.leaf_index,
+            path,
+        })
+    }
+}
+
 impl AuditProof {
     pub fn index(&self) -> u64 {
         self.index
@@ -0,0 +1,176 @@
+use crate::{
+    store::Hashable,
+    tree::{
+        AuditProof, HashOutput, Node, NodeKey, ProofValidationError, TreeHead,
+        inclusion::get_audit_proof,
+    },
+};
+use std::{cmp::Ordering, collections::BTreeMap};
+
+/// A proof, that a consecutive range of leaves is contained in a tree
+///
+/// The proof consists of the [`AuditProofs`](AuditProof) of the first and the last leaf of the range.
+/// Together with the leaves of the range, they contain all nodes needed to recompute the tree head.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RangeProof {
+    pub(crate) first: AuditProof,
+    pub(crate) last: AuditProof,
+}
+
+impl RangeProof {
+    pub fn new(first: AuditProof, last: AuditProof) -> Self {
+        Self { first, last }
+    }
+
+    /// Returns the index of the first leaf of the range
+    pub fn start(&self) -> u64 {
+        self.first.index
+    }
+
+    /// Returns the index after the last leaf of the range
+    pub fn end(&self) -> u64 {
+        self.last.index + 1
+    }
+
+    /// Validate, that the `leaves` are the leaves `start..end` of the tree with the given [`TreeHead`]
+    pub fn validate(
+        &self,
+        head: &TreeHead,
+        leaves: &[impl Hashable],
+    ) -> Result<(), ProofValidationError> {
+        let (start, end) = (self.start(), self.end());
+        if start >= end || end > head.tree_size {
+            return Err(ProofValidationError::InvalidIndex {
+                tree_size: head.tree_size,
+                index: self.last.index,
+            });
+        }
+        if leaves.len() as u64 != end - start {
+            return Err(ProofValidationError::InvalidIndex {
+                tree_size: head.tree_size,
+                index: start + leaves.len() as u64,
+            });
+        }
+
+        // Collect the nodes of the audit paths, which lie outside of the range
+        let mut nodes = BTreeMap::new();
+        for proof in [&self.first, &self.last] {
+            let keys = get_audit_proof(head, proof.index, |key| key);
+            match proof.path.len().cmp(&keys.len()) {
+                Ordering::Less => return Err(ProofValidationError::PathTooShort),
+                Ordering::Greater => return Err(ProofValidationError::PathTooLong),
+                Ordering::Equal => (),
+            }
+
+            // The audit path starts at the leaf, while the keys start at the root
+            nodes.extend(keys.into_iter().zip(proof.path.iter().rev().copied()));
+        }
+
+        let leaves = leaves.iter().map(Hashable::hash).collect::<Vec<_>>();
+        let root = range_hash(NodeKey::full_range(head.tree_size), start, &leaves, &nodes)?;
+        if root != head.head {
+            return Err(ProofValidationError::HashMismatch);
+        }
+
+        Ok(())
+    }
+}
+
+/// Recompute the hash of the node `key` from the `leaves` starting at `start` and the `nodes` outside of them
+fn range_hash(
+    key: NodeKey,
+    start: u64,
+    leaves: &[HashOutput],
+    nodes: &BTreeMap<NodeKey, HashOutput>,
+) -> Result<HashOutput, ProofValidationError> {
+    let end = start + leaves.len() as u64;
+
+    if key.end <= start || key.start >= end {
+        return nodes
+            .get(&key)
+            .copied()
+            .ok_or(ProofValidationError::PathTooShort);
+    }
+
+    if key.is_leaf() {
+        return Ok(leaves[(key.start - start) as usize]);
+    }
+
+    let (left, right) = key.split();
+    Ok(Node {
+        left: range_hash(left, start, leaves, nodes)?,
+        right: range_hash(right, start, leaves, nodes)?,
+    }
+    .hash())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{store::MemoryStore, tree::Tree};
+
+    #[tokio::test]
+    async fn validate_range_proofs() {
+        let tree = Tree::<MemoryStore<NodeKey, HashOutput>, MemoryStore<u64, String>>::new(
+            MemoryStore::default(),
+            MemoryStore::default(),
+        );
+
+        let leaves = (0..13).map(|idx| format!("leaf {idx}")).collect::<Vec<_>>();
+        for leaf in &leaves {
+            tree.insert_entry(leaf.clone()).await;
+        }
+        let head = tree.recompute_tree_head().await;
+
+        for start in 0..13 {
+            for end in start + 1..=13 {
+                let proof = RangeProof::new(
+                    tree.get_audit_proof(&head, start).await.unwrap(),
+                    tree.get_audit_proof(&head, end - 1).await.unwrap(),
+                );
+                proof
+                    .validate(&head, &leaves[start as usize..end as usize])
+                    .unwrap();
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn reject_invalid_range() {
+        let tree = Tree::<MemoryStore<NodeKey, HashOutput>, MemoryStore<u64, String>>::new(
+            MemoryStore::default(),
+            MemoryStore::default(),
+        );
+
+        let leaves = (0..7).map(|idx| format!("leaf {idx}")).collect::<Vec<_>>();
+        for leaf in &leaves {
+            tree.insert_entry(leaf.clone()).await;
+        }
+        let head = tree.recompute_tree_head().await;
+
+        let proof = RangeProof::new(
+            tree.get_audit_proof(&head, 1).await.unwrap(),
+            tree.get_audit_proof(&head, 5).await.unwrap(),
+        );
+        proof.validate(&head, &leaves[1..6]).unwrap();
+
+        // A modified leaf in the middle of the range
+        let mut tampered = leaves[1..6].to_vec();
+        tampered[2] = "other leaf".to_string();
+        assert_eq!(
+            proof.validate(&head, &tampered),
+            Err(ProofValidationError::HashMismatch)
+        );
+
+        // A missing leaf
+        assert!(proof.validate(&head, &leaves[1..5]).is_err());
+
+        // A truncated audit path
+        let mut truncated = proof.clone();
+        truncated.last.path.pop();
+        assert_eq!(
+            truncated.validate(&head, &leaves[1..6]),
+            Err(ProofValidationError::PathTooShort)
+        );
+    }
+}
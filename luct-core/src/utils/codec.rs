@@ -21,6 +21,9 @@ pub enum CodecError {
 
     #[error("A field contained {read} bytes (expected {expected} bytes)")]
     UnexpectedSize { read: usize, expected: usize },
+
+    #[error("The chain does not contain the issuer of the precertificate signing certificate")]
+    MissingPrecertSigningIssuer,
 }
 
 impl From<std::io::Error> for CodecError {
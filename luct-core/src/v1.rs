@@ -15,22 +15,21 @@ use x509_cert::{
     der::{Decode as DerDecode, Encode as DerEncode},
 };
 
+pub(crate) mod entries;
 pub(crate) mod extension;
 pub(crate) mod proof;
 pub mod responses;
 pub(crate) mod roots;
 pub(crate) mod sct;
 pub(crate) mod sth;
+pub(crate) mod submission;
 pub(crate) mod tree;
 
+pub use entries::LogEntryChain;
 pub use sct::SignedCertificateTimestamp;
 pub use sth::SignedTreeHead;
 pub use tree::MerkleTreeLeaf;
 
-// TODO(Submission support): Requests and responses for submission
-
-// TODO: LogEntryChain type
-
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct LogId(pub(crate) [u8; 32]);
 
@@ -0,0 +1,129 @@
+use crate::{
+    Certificate,
+    utils::{
+        codec::{CodecError, Decode},
+        codec_vec::CodecVec,
+        u24::U24,
+    },
+    v1::{
+        LogEntry, MerkleTreeLeaf,
+        responses::GetEntriesResponse,
+        tree::{Leaf, TimestampedEntry},
+    },
+};
+use std::io::{Cursor, Read};
+use x509_cert::{Certificate as Cert, der::Decode as CertDecode};
+
+/// The certificates, that were submitted alongside an entry of the log
+///
+/// This is the decoded `extra_data` of a `get-entries` response, i.e. either a
+/// `X509ChainEntry` or a `PrecertChainEntry` without the leaf certificate.
+///
+/// See RFC 6962 4.6
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEntryChain {
+    pub(crate) pre_certificate: Option<Certificate>,
+    pub(crate) certificate_chain: Vec<Certificate>,
+}
+
+impl LogEntryChain {
+    /// Returns the precertificate as submitted to the log, if this is a precert entry
+    pub fn pre_certificate(&self) -> Option<&Certificate> {
+        self.pre_certificate.as_ref()
+    }
+
+    /// Returns the chain, starting with the issuer and ending with the root certificate
+    pub fn certificate_chain(&self) -> &[Certificate] {
+        &self.certificate_chain
+    }
+
+    /// Decode the `extra_data` belonging to the `leaf`
+    fn decode(leaf: &MerkleTreeLeaf, mut reader: impl Read) -> Result<Self, CodecError> {
+        let Leaf::TimestampedEntry(TimestampedEntry { log_entry, .. }) = &leaf.leaf;
+
+        let pre_certificate = match log_entry {
+            LogEntry::X509(_) => None,
+            LogEntry::PreCert(_) => Some(decode_cert(&mut reader)?),
+        };
+
+        let chain_bytes = CodecVec::<U24>::decode(&mut reader)?;
+        let chain_len = chain_bytes.as_ref().len() as u64;
+        let mut chain_reader = Cursor::new(chain_bytes.as_ref());
+
+        let mut certificate_chain = vec![];
+        while chain_reader.position() < chain_len {
+            certificate_chain.push(decode_cert(&mut chain_reader)?);
+        }
+
+        Ok(Self {
+            pre_certificate,
+            certificate_chain,
+        })
+    }
+}
+
+fn decode_cert(mut reader: impl Read) -> Result<Certificate, CodecError> {
+    let cert_bytes = CodecVec::<U24>::decode(&mut reader)?;
+    Ok(Certificate(Cert::from_der(cert_bytes.as_ref())?))
+}
+
+impl TryFrom<&GetEntriesResponse> for Vec<(MerkleTreeLeaf, LogEntryChain)> {
+    type Error = CodecError;
+
+    fn try_from(response: &GetEntriesResponse) -> Result<Self, Self::Error> {
+        response
+            .entries
+            .iter()
+            .map(|entry| {
+                let leaf = entry.leaf_input.0.0.clone();
+                let mut reader = Cursor::new(entry.extra_data.0.as_slice());
+                let chain = LogEntryChain::decode(&leaf, &mut reader)?;
+
+                if reader.position() != entry.extra_data.0.len() as u64 {
+                    return Err(CodecError::UnexpectedSize {
+                        read: reader.position() as usize,
+                        expected: entry.extra_data.0.len(),
+                    });
+                }
+
+                Ok((leaf, chain))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const GOOGLE_GET_ENTRY: &str = include_str!("../../../testdata/google-entry.json");
+
+    #[test]
+    fn decode_precert_chain_entry() {
+        let response: GetEntriesResponse = serde_json::from_str(GOOGLE_GET_ENTRY).unwrap();
+        let entries = Vec::<(MerkleTreeLeaf, LogEntryChain)>::try_from(&response).unwrap();
+        assert_eq!(entries.len(), 1);
+
+        let (leaf, chain) = &entries[0];
+        assert_eq!(leaf, &response.entries[0].leaf_input.0.0);
+
+        let pre_certificate = chain.pre_certificate().unwrap();
+        assert!(pre_certificate.is_precert().unwrap());
+        assert_eq!(pre_certificate.get_subject_name(), "*.google.com");
+
+        // The chain starts with the issuer of the precertificate
+        let issuer = chain.certificate_chain().first().unwrap();
+        assert_eq!(issuer.get_subject_name(), "WR2");
+        assert_eq!(
+            pre_certificate.get_authority_key_info(),
+            issuer.get_subject_key_info()
+        );
+    }
+
+    #[test]
+    fn reject_trailing_extra_data() {
+        let mut response: GetEntriesResponse = serde_json::from_str(GOOGLE_GET_ENTRY).unwrap();
+        response.entries[0].extra_data.0.push(0);
+
+        assert!(Vec::<(MerkleTreeLeaf, LogEntryChain)>::try_from(&response).is_err());
+    }
+}
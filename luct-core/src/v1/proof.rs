@@ -92,7 +92,7 @@ mod tests {
     #[test]
     fn validate_sth_consistency() {
         let old_sth: GetSthResponse = serde_json::from_str(ARGON2025H1_STH2806).unwrap();
-        let old_tree_head = TreeHead::from(&old_sth.try_into().unwrap());
+        let old_tree_head = TreeHead::from(&SignedTreeHead::try_from(old_sth).unwrap());
 
         let new_sth: GetSthResponse = serde_json::from_str(ARGON2025H1_STH2906).unwrap();
         let proof: GetSthConsistencyResponse =
@@ -102,7 +102,7 @@ mod tests {
         proof
             .validate(
                 &old_tree_head,
-                &TreeHead::from(&new_sth.try_into().unwrap()),
+                &TreeHead::from(&SignedTreeHead::try_from(new_sth).unwrap()),
             )
             .unwrap();
     }
@@ -119,7 +119,7 @@ mod tests {
         let leaf = cert.as_leaf_v1(&scts[0], true).unwrap();
 
         let sth: GetSthResponse = serde_json::from_str(ARGON2025H2_STH_0506).unwrap();
-        let tree_head = TreeHead::from(&sth.try_into().unwrap());
+        let tree_head = TreeHead::from(&SignedTreeHead::try_from(sth).unwrap());
 
         let audit_proof: GetProofByHashResponse = serde_json::from_str(GOOGLE_AUDIT_PROOF).unwrap();
         let proof = AuditProof::try_from(audit_proof).unwrap();
@@ -10,6 +10,26 @@ use crate::{
 };
 use serde::{Deserialize, Serialize};
 
+/// Request sent to `/ct/v1/add-chain` and `/ct/v1/add-pre-chain`
+///
+/// See RFC 6962 4.1 and 4.2
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddChainRequest {
+    pub(crate) chain: Vec<Base64<Vec<u8>>>,
+}
+
+/// Response returned by call to `/ct/v1/add-chain` and `/ct/v1/add-pre-chain`
+///
+/// See RFC 6962 4.1 and 4.2
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddChainResponse {
+    pub(crate) sct_version: u8,
+    pub(crate) id: Base64<Vec<u8>>,
+    pub(crate) timestamp: u64,
+    pub(crate) extensions: Base64<Vec<u8>>,
+    pub(crate) signature: Base64<Vec<u8>>,
+}
+
 /// Response returned by call to `/ct/v1/get-sth`
 ///
 /// See RFC 6962 4.3
@@ -124,6 +124,34 @@ mod test {
     use super::*;
     use crate::tests::{ARGON2025H1_STH2806, get_log_argon2025h1};
 
+    const RSA2048_LOG: &str = "{
+        \"description\": \"RSA test log\",
+        \"url\": \"https://rsa.example.com/\",
+        \"key\": \"MIIBIjANBgkqhkiG9w0BAQEFAAOCAQ8AMIIBCgKCAQEAx6oWwChRUlt+HMwGItja0QXerqgosKP+V9KPt8ZDq110rjtvcJcytr64xtxMebc/fSjvUlUazTQx24H1jhN3ThZShRTru9ymB1ygyJVIrIvgrFvN/omZuWx8O7as5G0T+scoTr3p3i62ZlNiIH34xvwMXOl4Mk4LfAP75O3R6GL8aKau53byGQUHPAStc/PDO0WI9zyNwysnznO9BsFg9szJaa05DvqZ5FHsvT/CAi3srV4mJ8KwAEcoOKEuaA3nZsKnK9BJfO/YvXRx2M2qxtItSvEH7Z+IBr0y4CPgFUjzU+urzOB6SefAcHHuLmzeQQX0CjuHX/ytynDhbgVbbwIDAQAB\",
+        \"mmd\": 86400
+    }";
+
+    const RSA2048_STH: &str = "{
+        \"tree_size\":1425614114,
+        \"timestamp\":1751114416696,
+        \"sha256_root_hash\":\"W+h4lj3wVocztX9Vm+M4MgEF8FXqdf2BY7oZ3jUJtQ4=\",
+        \"tree_head_signature\":\"BAEBAIo/df2p5SaOAIXeWMLoN9OBAT7kVyYXlmhbUWFV3EpGP+WlJBk8LuAwOgeUMP/hfQNnNLkHNHXCOVkNxpPku/lQlx1RgWjTXIH0uKNrkizH15TH8c5+I+hIt+4v9PWMxKVUHijU25KnIIdBg+drmRD1tpwnthSVb6AXIQFweIFw0qJu3mPOv7RzX/FDH96DH//iodDzmkRlRjHMpUgQt165IYTeNT+x2LCShKgEIJmnrs+TjKtXYUecjilqACPher1FLOD+EGn5iq5jQwjjSUAqLFvfrq9aqmbJBj4J6biUgLbMWpsGwH+rZpnIvV+O5cHJsr1X55P35JSI2VvR8oY=\"
+    }";
+
+    const RSA1024_LOG: &str = "{
+        \"description\": \"RSA test log with short key\",
+        \"url\": \"https://rsa.example.com/\",
+        \"key\": \"MIGfMA0GCSqGSIb3DQEBAQUAA4GNADCBiQKBgQC3k0epe6RLPmRx1/urDypure557WbvHF/axcyUO+z4n9fPLYZzZph7Rg00Jllx7cIzna5QZ2XjsKLUIGH8qYKTbaTW1xdoEyC4yGIW/1wl2dr5gIw5PvV23gIxgvrr8Xy/KfpgTvTuf+M8RqzDhHE0RWEGmMc97m9vhTUTC8sRNwIDAQAB\",
+        \"mmd\": 86400
+    }";
+
+    const RSA1024_STH: &str = "{
+        \"tree_size\":1425614114,
+        \"timestamp\":1751114416696,
+        \"sha256_root_hash\":\"W+h4lj3wVocztX9Vm+M4MgEF8FXqdf2BY7oZ3jUJtQ4=\",
+        \"tree_head_signature\":\"BAEAgG4GbDdnIBlGUuaMe5as5CG76/BSWbfllm2cg9zIgOxY6Mq58tZW+pymSe5I6xh7TJ1ItaeMQGHQ5fcd/3ClnfhM7X935bHmnzQCmxQXGk/Ic+2S9EA87icvw5tdM+z7T9JgKSw2miNkM8BRi6rCVIQ9cjHDa2mCBdndzTw3WbRx\"
+    }";
+
     #[test]
     fn sth_codec_roundtrip() {
         let sth: GetSthResponse = serde_json::from_str(ARGON2025H1_STH2806).unwrap();
@@ -138,4 +166,39 @@ mod test {
         let sth: GetSthResponse = serde_json::from_str(ARGON2025H1_STH2806).unwrap();
         log.validate_sth_v1(&sth.try_into().unwrap()).unwrap();
     }
+
+    #[test]
+    fn validate_rsa_sth() {
+        let log = CtLog::new(serde_json::from_str(RSA2048_LOG).unwrap()).unwrap();
+        let sth: GetSthResponse = serde_json::from_str(RSA2048_STH).unwrap();
+        let sth: SignedTreeHead = sth.try_into().unwrap();
+        log.validate_sth_v1(&sth).unwrap();
+
+        // Changing the signed data must invalidate the signature
+        let mut tampered_sth = sth.clone();
+        tampered_sth.tree_size += 1;
+        assert_eq!(
+            log.validate_sth_v1(&tampered_sth),
+            Err(SignatureValidationError::InvalidSignature)
+        );
+
+        // ECDSA keys can't validate RSA signatures
+        assert_eq!(
+            get_log_argon2025h1().validate_sth_v1(&sth),
+            Err(SignatureValidationError::MalformedKey)
+        );
+    }
+
+    #[test]
+    fn reject_short_rsa_key() {
+        let log = CtLog::new(serde_json::from_str(RSA1024_LOG).unwrap()).unwrap();
+        let sth: GetSthResponse = serde_json::from_str(RSA1024_STH).unwrap();
+        assert_eq!(
+            log.validate_sth_v1(&sth.try_into().unwrap()),
+            Err(SignatureValidationError::InsufficientKeySize {
+                received: 1024,
+                expected: 2048
+            })
+        );
+    }
 }
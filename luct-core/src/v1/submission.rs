@@ -0,0 +1,135 @@
+use crate::{
+    CertificateChain,
+    utils::{
+        base64::Base64,
+        codec::{CodecError, Decode, Encode},
+        codec_vec::CodecVec,
+    },
+    v1::{
+        SignedCertificateTimestamp,
+        responses::{AddChainRequest, AddChainResponse},
+    },
+};
+use std::io::{Cursor, Write};
+
+impl From<&CertificateChain> for AddChainRequest {
+    fn from(chain: &CertificateChain) -> Self {
+        Self {
+            chain: chain.as_der_chain().into_iter().map(Base64).collect(),
+        }
+    }
+}
+
+impl TryFrom<&AddChainResponse> for SignedCertificateTimestamp {
+    type Error = CodecError;
+
+    fn try_from(response: &AddChainResponse) -> Result<Self, Self::Error> {
+        if response.sct_version != 0 {
+            return Err(CodecError::UnknownVariant(
+                "Version",
+                response.sct_version as u64,
+            ));
+        }
+        if response.id.0.len() != 32 {
+            return Err(CodecError::UnexpectedSize {
+                read: response.id.0.len(),
+                expected: 32,
+            });
+        }
+
+        // The response contains the fields of the SCT, so we reassemble and decode it
+        let mut bytes = Cursor::new(vec![]);
+        bytes.write_all(&[response.sct_version])?;
+        bytes.write_all(&response.id.0)?;
+        response.timestamp.encode(&mut bytes)?;
+        CodecVec::<u16>::from(response.extensions.0.clone()).encode(&mut bytes)?;
+        bytes.write_all(&response.signature.0)?;
+
+        let bytes = bytes.into_inner();
+        let mut reader = Cursor::new(bytes.as_slice());
+        let sct = SignedCertificateTimestamp::decode(&mut reader)?;
+
+        if reader.position() != bytes.len() as u64 {
+            return Err(CodecError::UnexpectedSize {
+                read: reader.position() as usize,
+                expected: bytes.len(),
+            });
+        }
+
+        Ok(sct)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        SignatureValidationError,
+        tests::{CERT_CHAIN_GOOGLE_COM, get_log_argon2025h1, get_log_argon2025h2},
+    };
+
+    const PRECERT_CHAIN_GOOGLE_COM: &str =
+        include_str!("../../../testdata/google-precert-chain.pem");
+    const ADD_PRE_CHAIN_RESPONSE: &str =
+        include_str!("../../../testdata/argon2025h2-add-pre-chain.json");
+
+    #[test]
+    fn add_chain_request() {
+        let chain = CertificateChain::from_pem_chain(PRECERT_CHAIN_GOOGLE_COM).unwrap();
+        let request = AddChainRequest::from(&chain);
+        assert_eq!(request.chain.len(), 3);
+
+        let json = serde_json::to_string(&request).unwrap();
+        let request: AddChainRequest = serde_json::from_str(&json).unwrap();
+        let chain2 = CertificateChain::from_der_chain(
+            &request
+                .chain
+                .into_iter()
+                .map(|cert| cert.0)
+                .collect::<Vec<_>>(),
+        )
+        .unwrap();
+        assert_eq!(chain2, chain);
+    }
+
+    #[test]
+    fn validate_add_pre_chain_response() {
+        let response: AddChainResponse = serde_json::from_str(ADD_PRE_CHAIN_RESPONSE).unwrap();
+        let sct = SignedCertificateTimestamp::try_from(&response).unwrap();
+
+        // The log returns the same SCT, that is embedded in the final certificate
+        let final_chain = CertificateChain::from_pem_chain(CERT_CHAIN_GOOGLE_COM).unwrap();
+        assert_eq!(sct, final_chain.cert().extract_scts_v1().unwrap()[0]);
+
+        let log = get_log_argon2025h2();
+        assert_eq!(log.log_id(), &sct.log_id());
+
+        let chain = CertificateChain::from_pem_chain(PRECERT_CHAIN_GOOGLE_COM).unwrap();
+        log.validate_sct_v1(&chain, &sct, true).unwrap();
+        assert_eq!(
+            log.validate_sct_v1(&chain, &sct, false),
+            Err(SignatureValidationError::InvalidSignature)
+        );
+        assert_eq!(
+            get_log_argon2025h1().validate_sct_v1(&chain, &sct, true),
+            Err(SignatureValidationError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn reject_malformed_response() {
+        let response: AddChainResponse = serde_json::from_str(ADD_PRE_CHAIN_RESPONSE).unwrap();
+
+        let mut unknown_version = response.clone();
+        unknown_version.sct_version = 1;
+        assert!(SignedCertificateTimestamp::try_from(&unknown_version).is_err());
+
+        let mut short_id = response.clone();
+        short_id.id.0.pop();
+        assert!(SignedCertificateTimestamp::try_from(&short_id).is_err());
+
+        let mut trailing = response;
+        trailing.signature.0.push(0);
+        assert!(SignedCertificateTimestamp::try_from(&trailing).is_err());
+    }
+}
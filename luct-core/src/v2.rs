@@ -0,0 +1,193 @@
+use crate::{
+    tree::HashOutput,
+    utils::{
+        append_vec::SizedAppendVec,
+        codec::{CodecError, Decode, Encode},
+        codec_vec::CodecVec,
+    },
+};
+use const_oid::ObjectIdentifier;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    fmt::{self, Display},
+    io::{Read, Write},
+    str::FromStr,
+};
+
+pub(crate) mod anchors;
+pub(crate) mod proof;
+pub mod responses;
+pub(crate) mod sct;
+pub(crate) mod sth;
+pub(crate) mod trans_item;
+pub(crate) mod tree;
+
+pub use proof::{ConsistencyProofDataV2, InclusionProofDataV2};
+pub use sct::{SignedCertificateTimestamp, SignedCertificateTimestampDataV2};
+pub use sth::SignedTreeHead;
+pub(crate) use trans_item::TransItemList;
+pub use trans_item::{TimestampedCertificateEntryDataV2, TransItem};
+pub use tree::MerkleTreeLeaf;
+
+/// The id of a v2 log is an OID assigned to the log
+///
+/// It is encoded as the DER encoding of the OID without the ASN.1 tag and length.
+///
+/// See RFC 9162 4.4
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LogId(pub(crate) ObjectIdentifier);
+
+impl fmt::Debug for LogId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("LogId").field(&self.0.to_string()).finish()
+    }
+}
+
+impl Display for LogId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl TryFrom<&str> for LogId {
+    type Error = ();
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Ok(Self(ObjectIdentifier::from_str(value).map_err(|_| ())?))
+    }
+}
+
+impl Serialize for LogId {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for LogId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let oid = String::deserialize(deserializer)?;
+        LogId::try_from(oid.as_str())
+            .map_err(|_| serde::de::Error::custom(format!("Invalid log id {oid}")))
+    }
+}
+
+impl Encode for LogId {
+    fn encode(&self, writer: impl Write) -> Result<(), CodecError> {
+        CodecVec::<u8>::from(self.0.as_bytes().to_vec()).encode(writer)
+    }
+}
+
+impl Decode for LogId {
+    fn decode(reader: impl Read) -> Result<Self, CodecError> {
+        let bytes = CodecVec::<u8>::decode(reader)?;
+        let oid = ObjectIdentifier::from_bytes(bytes.as_ref())
+            .map_err(|_| CodecError::UnexpectedVariant)?;
+        Ok(Self(oid))
+    }
+}
+
+/// A hash of a node of the merkle tree
+///
+/// Only SHA-256 is supported, therefore the hash must be 32 bytes long.
+///
+/// See RFC 9162 4.9
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub(crate) struct NodeHash(pub(crate) HashOutput);
+
+impl fmt::Debug for NodeHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("NodeHash")
+            .field(&hex::encode(self.0))
+            .finish()
+    }
+}
+
+impl Encode for NodeHash {
+    fn encode(&self, mut writer: impl Write) -> Result<(), CodecError> {
+        (self.0.len() as u8).encode(&mut writer)?;
+        self.0.encode(&mut writer)
+    }
+}
+
+impl Decode for NodeHash {
+    fn decode(mut reader: impl Read) -> Result<Self, CodecError> {
+        let len = u8::decode(&mut reader)? as usize;
+        if len != 32 {
+            return Err(CodecError::UnexpectedSize {
+                read: len,
+                expected: 32,
+            });
+        }
+
+        Ok(Self(<[u8; 32]>::decode(&mut reader)?))
+    }
+}
+
+/// Extensions of SCTs and STHs
+///
+/// See RFC 9162 4.6
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub(crate) struct Extensions(Vec<Extension>);
+
+impl Encode for Extensions {
+    fn encode(&self, writer: impl Write) -> Result<(), CodecError> {
+        SizedAppendVec::from(self.0.clone()).encode(writer)
+    }
+}
+
+impl Decode for Extensions {
+    fn decode(reader: impl Read) -> Result<Self, CodecError> {
+        Ok(Self(SizedAppendVec::decode(reader)?.into()))
+    }
+}
+
+/// See RFC 9162 4.6
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub(crate) struct Extension {
+    pub(crate) extension_type: u16,
+    pub(crate) extension_data: CodecVec<u16>,
+}
+
+impl Encode for Extension {
+    fn encode(&self, mut writer: impl Write) -> Result<(), CodecError> {
+        self.extension_type.encode(&mut writer)?;
+        self.extension_data.encode(&mut writer)?;
+        Ok(())
+    }
+}
+
+impl Decode for Extension {
+    fn decode(mut reader: impl Read) -> Result<Self, CodecError> {
+        Ok(Self {
+            extension_type: u16::decode(&mut reader)?,
+            extension_data: CodecVec::decode(&mut reader)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn log_id_codec_roundtrip() {
+        let log_id = LogId::try_from("1.3.101.8192").unwrap();
+        assert_eq!(log_id.to_string(), "1.3.101.8192");
+
+        let mut bytes = vec![];
+        log_id.encode(&mut bytes).unwrap();
+        assert_eq!(bytes, vec![4, 0x2b, 0x65, 0xc0, 0x00]);
+
+        let decoded = LogId::decode(Cursor::new(bytes)).unwrap();
+        assert_eq!(log_id, decoded);
+
+        assert!(LogId::try_from("TnWjJ1yaEMM4W2zU3z9S6x3w4I4bjWnAsfpksWKaOd8=").is_err());
+    }
+}
@@ -0,0 +1,11 @@
+use crate::{Certificate, v2::responses::GetAnchorsResponse};
+
+impl From<&GetAnchorsResponse> for Vec<Certificate> {
+    fn from(response: &GetAnchorsResponse) -> Self {
+        response
+            .certificates
+            .iter()
+            .filter_map(|cert| Certificate::from_der(&cert.0).ok())
+            .collect()
+    }
+}
@@ -0,0 +1,227 @@
+use crate::{
+    tree::{AuditProof, ConsistencyProof, ProofValidationError, TreeHead},
+    utils::{
+        append_vec::SizedAppendVec,
+        codec::{CodecError, Decode, Encode},
+    },
+    v2::{
+        LogId, NodeHash, TransItem,
+        responses::{GetProofByHashResponse, GetSthConsistencyResponse},
+        sth::SignedTreeHead,
+    },
+};
+use std::io::{Read, Write};
+
+/// See RFC 9162 4.11
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsistencyProofDataV2 {
+    pub(crate) log_id: LogId,
+    pub(crate) tree_size_1: u64,
+    pub(crate) tree_size_2: u64,
+    pub(crate) consistency_path: SizedAppendVec<NodeHash>,
+}
+
+impl Encode for ConsistencyProofDataV2 {
+    fn encode(&self, mut writer: impl Write) -> Result<(), CodecError> {
+        self.log_id.encode(&mut writer)?;
+        self.tree_size_1.encode(&mut writer)?;
+        self.tree_size_2.encode(&mut writer)?;
+        self.consistency_path.encode(&mut writer)?;
+        Ok(())
+    }
+}
+
+impl Decode for ConsistencyProofDataV2 {
+    fn decode(mut reader: impl Read) -> Result<Self, CodecError> {
+        Ok(Self {
+            log_id: LogId::decode(&mut reader)?,
+            tree_size_1: u64::decode(&mut reader)?,
+            tree_size_2: u64::decode(&mut reader)?,
+            consistency_path: SizedAppendVec::decode(&mut reader)?,
+        })
+    }
+}
+
+/// See RFC 9162 4.12
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InclusionProofDataV2 {
+    pub(crate) log_id: LogId,
+    pub(crate) tree_size: u64,
+    pub(crate) leaf_index: u64,
+    pub(crate) inclusion_path: SizedAppendVec<NodeHash>,
+}
+
+impl Encode for InclusionProofDataV2 {
+    fn encode(&self, mut writer: impl Write) -> Result<(), CodecError> {
+        self.log_id.encode(&mut writer)?;
+        self.tree_size.encode(&mut writer)?;
+        self.leaf_index.encode(&mut writer)?;
+        self.inclusion_path.encode(&mut writer)?;
+        Ok(())
+    }
+}
+
+impl Decode for InclusionProofDataV2 {
+    fn decode(mut reader: impl Read) -> Result<Self, CodecError> {
+        Ok(Self {
+            log_id: LogId::decode(&mut reader)?,
+            tree_size: u64::decode(&mut reader)?,
+            leaf_index: u64::decode(&mut reader)?,
+            inclusion_path: SizedAppendVec::decode(&mut reader)?,
+        })
+    }
+}
+
+impl GetSthConsistencyResponse {
+    /// Extract the [`ConsistencyProof`] between the tree heads `first` and `second`
+    ///
+    /// The proof must be issued by the same log as the tree heads and cover their exact tree sizes.
+    pub fn into_proof(
+        self,
+        first: &SignedTreeHead,
+        second: &SignedTreeHead,
+    ) -> Result<ConsistencyProof, ProofValidationError> {
+        let TransItem::ConsistencyProofV2(proof) = self.consistency.0.0 else {
+            return Err(ProofValidationError::UnexpectedProofType);
+        };
+
+        if proof.log_id != first.log_id || proof.log_id != second.log_id {
+            return Err(ProofValidationError::LogIdMismatch);
+        }
+        check_tree_size(first.tree_size(), proof.tree_size_1)?;
+        check_tree_size(second.tree_size(), proof.tree_size_2)?;
+
+        Ok(ConsistencyProof {
+            path: Vec::from(proof.consistency_path)
+                .into_iter()
+                .map(|hash| hash.0)
+                .collect(),
+        })
+    }
+}
+
+impl GetProofByHashResponse {
+    /// Extract the [`AuditProof`] against the tree head `sth`
+    ///
+    /// The proof must be issued by the same log as the tree head and cover its exact tree size.
+    pub fn into_proof(self, sth: &SignedTreeHead) -> Result<AuditProof, ProofValidationError> {
+        let TransItem::InclusionProofV2(proof) = self.inclusion.0.0 else {
+            return Err(ProofValidationError::UnexpectedProofType);
+        };
+
+        if proof.log_id != sth.log_id {
+            return Err(ProofValidationError::LogIdMismatch);
+        }
+        check_tree_size(sth.tree_size(), proof.tree_size)?;
+
+        Ok(AuditProof {
+            index: proof.leaf_index,
+            path: Vec::from(proof.inclusion_path)
+                .into_iter()
+                .map(|hash| hash.0)
+                .collect(),
+        })
+    }
+}
+
+fn check_tree_size(expected: u64, received: u64) -> Result<(), ProofValidationError> {
+    if expected != received {
+        return Err(ProofValidationError::TreeSizeMismatch { expected, received });
+    }
+
+    Ok(())
+}
+
+impl From<&SignedTreeHead> for TreeHead {
+    fn from(value: &SignedTreeHead) -> Self {
+        Self {
+            tree_size: value.tree_size(),
+            head: *value.root_hash(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        CertificateChain,
+        store::Hashable,
+        tests::{CERT_CHAIN_V2, V2_ECDSA_STH, get_log_v2_ecdsa},
+        utils::base64::Base64,
+        v2::responses::GetSthResponse,
+    };
+
+    const V2_AUDIT_PROOF: &str = include_str!("../../../testdata/v2-get-proof-by-hash.json");
+    const V2_LEAF_HASH: &str = "HL2mcuJ/WNpddO+tcuIaNrqhrNAKBFx/TlxISDxs/VI=";
+
+    #[test]
+    fn audit_sct() {
+        let cert = CertificateChain::from_pem_chain(CERT_CHAIN_V2).unwrap();
+        cert.verify_chain().unwrap();
+        let scts = cert.cert().extract_scts_v2().unwrap();
+
+        let log = get_log_v2_ecdsa();
+        assert_eq!(log.log_id(), &scts[0].log_id());
+
+        let leaf = cert.as_leaf_v2(&scts[0]).unwrap();
+        let expected_hash: Base64<Vec<u8>> =
+            serde_json::from_str(&format!("\"{V2_LEAF_HASH}\"")).unwrap();
+        assert_eq!(leaf.hash().as_slice(), expected_hash.0.as_slice());
+
+        let sth: GetSthResponse = serde_json::from_str(V2_ECDSA_STH).unwrap();
+        let sth = SignedTreeHead::try_from(sth).unwrap();
+        log.validate_sth_v2(&sth).unwrap();
+
+        let audit_proof: GetProofByHashResponse = serde_json::from_str(V2_AUDIT_PROOF).unwrap();
+        let proof = audit_proof.clone().into_proof(&sth).unwrap();
+        proof.validate(&TreeHead::from(&sth), &leaf).unwrap();
+
+        // The STH returned alongside the proof is the same one
+        let proof_sth = SignedTreeHead::try_from(audit_proof.sth.0.0).unwrap();
+        assert_eq!(proof_sth, sth);
+    }
+
+    #[test]
+    fn reject_wrong_proof_type() {
+        let sth: GetSthResponse = serde_json::from_str(V2_ECDSA_STH).unwrap();
+        let response = GetProofByHashResponse {
+            inclusion: sth.sth.clone(),
+            sth: sth.sth.clone(),
+        };
+        let sth = SignedTreeHead::try_from(sth).unwrap();
+
+        assert_eq!(
+            response.into_proof(&sth),
+            Err(ProofValidationError::UnexpectedProofType)
+        );
+    }
+
+    #[test]
+    fn reject_proof_of_other_tree() {
+        let sth: GetSthResponse = serde_json::from_str(V2_ECDSA_STH).unwrap();
+        let sth = SignedTreeHead::try_from(sth).unwrap();
+        let audit_proof: GetProofByHashResponse = serde_json::from_str(V2_AUDIT_PROOF).unwrap();
+
+        let tamper = |f: &dyn Fn(&mut InclusionProofDataV2)| {
+            let mut response = audit_proof.clone();
+            let TransItem::InclusionProofV2(proof) = &mut response.inclusion.0.0 else {
+                unreachable!()
+            };
+            f(proof);
+            response.into_proof(&sth)
+        };
+
+        assert_eq!(
+            tamper(&|proof| proof.log_id = LogId::try_from("1.3.101.8193").unwrap()),
+            Err(ProofValidationError::LogIdMismatch)
+        );
+        assert_eq!(
+            tamper(&|proof| proof.tree_size += 1),
+            Err(ProofValidationError::TreeSizeMismatch {
+                expected: sth.tree_size(),
+                received: sth.tree_size() + 1
+            })
+        );
+    }
+}
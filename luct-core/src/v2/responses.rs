@@ -0,0 +1,45 @@
+//! The response structures of a v2 log.
+//!
+//! These structures correspond to the ones described in RFC 9162 Section 5.
+//! They can be parsed using `serde_json`, and then be transformed into other structures to be validated.
+
+use crate::{
+    utils::{base64::Base64, codec::Codec},
+    v2::TransItem,
+};
+use serde::{Deserialize, Serialize};
+
+/// Response returned by call to `/ct/v2/get-sth`
+///
+/// See RFC 9162 5.2
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GetSthResponse {
+    pub(crate) sth: Base64<Codec<TransItem>>,
+}
+
+/// Response returned by call to `/ct/v2/get-sth-consistency`
+///
+/// See RFC 9162 5.3
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GetSthConsistencyResponse {
+    pub(crate) consistency: Base64<Codec<TransItem>>,
+    pub(crate) sth: Base64<Codec<TransItem>>,
+}
+
+/// Response returned by call to `/ct/v2/get-proof-by-hash`
+///
+/// See RFC 9162 5.4
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GetProofByHashResponse {
+    pub(crate) inclusion: Base64<Codec<TransItem>>,
+    pub(crate) sth: Base64<Codec<TransItem>>,
+}
+
+/// Response returned by call to `/ct/v2/get-anchors`
+///
+/// See RFC 9162 5.7
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GetAnchorsResponse {
+    pub(crate) certificates: Vec<Base64<Vec<u8>>>,
+    pub(crate) max_chain_length: u64,
+}
@@ -0,0 +1,133 @@
+use crate::{
+    CertificateChain, CtLog,
+    signature::{SignatureValidationError, UntaggedSignature},
+    store::Hashable,
+    tree::HashOutput,
+    utils::codec::{CodecError, Decode, Encode},
+    v2::{Extensions, LogId, TransItem},
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::{Cursor, Read, Write};
+
+impl CtLog {
+    /// Validate a v2 [`SignedCertificateTimestamp`] against the [`CertificateChain`]
+    ///
+    /// Unlike v1 SCTs, the SCT itself specifies whether it was issued for a precertificate.
+    pub fn validate_sct_v2(
+        &self,
+        cert: &CertificateChain,
+        sct: &SignedCertificateTimestamp,
+    ) -> Result<(), SignatureValidationError> {
+        if self.log_id() != &sct.log_id() {
+            return Err(SignatureValidationError::LogIdMismatch);
+        }
+
+        let leaf = cert
+            .as_leaf_v2(sct)
+            .map_err(SignatureValidationError::CodecError)?;
+
+        sct.data.signature.validate(&leaf.0, &self.config.key)
+    }
+}
+
+/// A signed certificate timestamp of version 2.
+///
+/// See RFC 9162 4.8
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedCertificateTimestamp {
+    pub(crate) precert: bool,
+    pub(crate) data: SignedCertificateTimestampDataV2,
+}
+
+impl SignedCertificateTimestamp {
+    pub fn log_id(&self) -> crate::LogId {
+        crate::LogId::V2(self.data.log_id.clone())
+    }
+
+    pub fn timestamp(&self) -> u64 {
+        self.data.timestamp
+    }
+
+    /// Returns `true`, if this SCT was issued for a precertificate
+    pub fn is_precert(&self) -> bool {
+        self.precert
+    }
+}
+
+impl TryFrom<TransItem> for SignedCertificateTimestamp {
+    type Error = CodecError;
+
+    fn try_from(value: TransItem) -> Result<Self, Self::Error> {
+        match value {
+            TransItem::X509SctV2(data) => Ok(Self {
+                precert: false,
+                data,
+            }),
+            TransItem::PrecertSctV2(data) => Ok(Self {
+                precert: true,
+                data,
+            }),
+            _ => Err(CodecError::UnexpectedVariant),
+        }
+    }
+}
+
+impl From<SignedCertificateTimestamp> for TransItem {
+    fn from(value: SignedCertificateTimestamp) -> Self {
+        match value.precert {
+            false => TransItem::X509SctV2(value.data),
+            true => TransItem::PrecertSctV2(value.data),
+        }
+    }
+}
+
+impl Encode for SignedCertificateTimestamp {
+    fn encode(&self, writer: impl Write) -> Result<(), CodecError> {
+        TransItem::from(self.clone()).encode(writer)
+    }
+}
+
+impl Decode for SignedCertificateTimestamp {
+    fn decode(reader: impl Read) -> Result<Self, CodecError> {
+        TransItem::decode(reader)?.try_into()
+    }
+}
+
+impl Hashable for SignedCertificateTimestamp {
+    fn hash(&self) -> HashOutput {
+        let mut bytes = Cursor::new(vec![]);
+        self.encode(&mut bytes).unwrap();
+        Sha256::digest(bytes.into_inner()).into()
+    }
+}
+
+/// See RFC 9162 4.8
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedCertificateTimestampDataV2 {
+    pub(crate) log_id: LogId,
+    pub(crate) timestamp: u64,
+    pub(crate) sct_extensions: Extensions,
+    pub(crate) signature: UntaggedSignature<TransItem>,
+}
+
+impl Encode for SignedCertificateTimestampDataV2 {
+    fn encode(&self, mut writer: impl Write) -> Result<(), CodecError> {
+        self.log_id.encode(&mut writer)?;
+        self.timestamp.encode(&mut writer)?;
+        self.sct_extensions.encode(&mut writer)?;
+        self.signature.encode(&mut writer)?;
+        Ok(())
+    }
+}
+
+impl Decode for SignedCertificateTimestampDataV2 {
+    fn decode(mut reader: impl Read) -> Result<Self, CodecError> {
+        Ok(Self {
+            log_id: LogId::decode(&mut reader)?,
+            timestamp: u64::decode(&mut reader)?,
+            sct_extensions: Extensions::decode(&mut reader)?,
+            signature: UntaggedSignature::decode(&mut reader)?,
+        })
+    }
+}
@@ -0,0 +1,182 @@
+use crate::{
+    CtLog,
+    signature::{SignatureValidationError, UntaggedSignature},
+    tree::HashOutput,
+    utils::codec::{CodecError, Decode, Encode},
+    v2::{Extensions, LogId, NodeHash, TransItem, responses::GetSthResponse},
+};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+
+impl CtLog {
+    pub fn validate_sth_v2(&self, sth: &SignedTreeHead) -> Result<(), SignatureValidationError> {
+        if self.log_id() != &sth.log_id() {
+            return Err(SignatureValidationError::LogIdMismatch);
+        }
+
+        sth.signature.validate(&sth.tree_head, &self.config.key)
+    }
+}
+
+/// A signed tree head of version 2
+///
+/// See RFC 9162 4.10
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct SignedTreeHead {
+    pub(crate) log_id: LogId,
+    pub(crate) tree_head: TreeHeadDataV2,
+    pub(crate) signature: UntaggedSignature<TreeHeadDataV2>,
+}
+
+impl SignedTreeHead {
+    pub fn log_id(&self) -> crate::LogId {
+        crate::LogId::V2(self.log_id.clone())
+    }
+
+    pub fn tree_size(&self) -> u64 {
+        self.tree_head.tree_size
+    }
+
+    pub fn timestamp(&self) -> u64 {
+        self.tree_head.timestamp
+    }
+
+    pub fn root_hash(&self) -> &HashOutput {
+        &self.tree_head.root_hash.0
+    }
+}
+
+impl TryFrom<TransItem> for SignedTreeHead {
+    type Error = CodecError;
+
+    fn try_from(value: TransItem) -> Result<Self, Self::Error> {
+        match value {
+            TransItem::SignedTreeHeadV2(sth) => Ok(sth),
+            _ => Err(CodecError::UnexpectedVariant),
+        }
+    }
+}
+
+impl TryFrom<GetSthResponse> for SignedTreeHead {
+    type Error = ();
+
+    fn try_from(value: GetSthResponse) -> Result<Self, Self::Error> {
+        value.sth.0.0.try_into().map_err(|_| ())
+    }
+}
+
+impl Encode for SignedTreeHead {
+    fn encode(&self, mut writer: impl Write) -> Result<(), CodecError> {
+        self.log_id.encode(&mut writer)?;
+        self.tree_head.encode(&mut writer)?;
+        self.signature.encode(&mut writer)?;
+        Ok(())
+    }
+}
+
+impl Decode for SignedTreeHead {
+    fn decode(mut reader: impl Read) -> Result<Self, CodecError> {
+        Ok(Self {
+            log_id: LogId::decode(&mut reader)?,
+            tree_head: TreeHeadDataV2::decode(&mut reader)?,
+            signature: UntaggedSignature::decode(&mut reader)?,
+        })
+    }
+}
+
+/// The data of the tree head, that gets signed by the log
+///
+/// See RFC 9162 4.9
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub(crate) struct TreeHeadDataV2 {
+    pub(crate) timestamp: u64,
+    pub(crate) tree_size: u64,
+    pub(crate) root_hash: NodeHash,
+    pub(crate) sth_extensions: Extensions,
+}
+
+impl Encode for TreeHeadDataV2 {
+    fn encode(&self, mut writer: impl Write) -> Result<(), CodecError> {
+        self.timestamp.encode(&mut writer)?;
+        self.tree_size.encode(&mut writer)?;
+        self.root_hash.encode(&mut writer)?;
+        self.sth_extensions.encode(&mut writer)?;
+        Ok(())
+    }
+}
+
+impl Decode for TreeHeadDataV2 {
+    fn decode(mut reader: impl Read) -> Result<Self, CodecError> {
+        Ok(Self {
+            timestamp: u64::decode(&mut reader)?,
+            tree_size: u64::decode(&mut reader)?,
+            root_hash: NodeHash::decode(&mut reader)?,
+            sth_extensions: Extensions::decode(&mut reader)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{
+        V2_ECDSA_STH, V2_ED25519_STH, get_log_argon2025h1, get_log_v2_ecdsa, get_log_v2_ed25519,
+    };
+    use std::io::Cursor;
+
+    #[test]
+    fn validate_sth() {
+        for (log, sth) in [
+            (get_log_v2_ecdsa(), V2_ECDSA_STH),
+            (get_log_v2_ed25519(), V2_ED25519_STH),
+        ] {
+            let sth: GetSthResponse = serde_json::from_str(sth).unwrap();
+            let sth = SignedTreeHead::try_from(sth).unwrap();
+            assert_eq!(&sth.log_id(), log.log_id());
+            assert_eq!(sth.tree_size(), 2);
+            log.validate_sth_v2(&sth).unwrap();
+
+            let mut tampered_sth = sth.clone();
+            tampered_sth.tree_head.timestamp += 1;
+            assert_eq!(
+                log.validate_sth_v2(&tampered_sth),
+                Err(SignatureValidationError::InvalidSignature)
+            );
+        }
+    }
+
+    #[test]
+    fn reject_sth_of_other_log() {
+        let sth: GetSthResponse = serde_json::from_str(V2_ECDSA_STH).unwrap();
+        let sth = SignedTreeHead::try_from(sth).unwrap();
+
+        assert_eq!(
+            get_log_v2_ed25519().validate_sth_v2(&sth),
+            Err(SignatureValidationError::LogIdMismatch)
+        );
+        assert_eq!(
+            get_log_argon2025h1().validate_sth(&sth.into()),
+            Err(SignatureValidationError::VersionMismatch {
+                expected: crate::Version::V1,
+                received: crate::Version::V2
+            })
+        );
+    }
+
+    #[test]
+    fn sth_codec_roundtrip() {
+        let response: GetSthResponse = serde_json::from_str(V2_ECDSA_STH).unwrap();
+        let sth = SignedTreeHead::try_from(response.clone()).unwrap();
+
+        let mut bytes = Cursor::new(vec![]);
+        TransItem::SignedTreeHeadV2(sth.clone())
+            .encode(&mut bytes)
+            .unwrap();
+        let mut expected = Cursor::new(vec![]);
+        response.sth.0.encode(&mut expected).unwrap();
+        assert_eq!(bytes.into_inner(), expected.into_inner());
+
+        let json = serde_json::to_string(&sth).unwrap();
+        assert_eq!(serde_json::from_str::<SignedTreeHead>(&json).unwrap(), sth);
+    }
+}
@@ -0,0 +1,208 @@
+use crate::{
+    utils::{
+        append_vec::SizedAppendVec,
+        codec::{CodecError, Decode, Encode},
+        codec_vec::CodecVec,
+        u24::U24,
+    },
+    v2::{
+        Extensions, NodeHash,
+        proof::{ConsistencyProofDataV2, InclusionProofDataV2},
+        sct::SignedCertificateTimestampDataV2,
+        sth::SignedTreeHead,
+    },
+};
+use std::io::{Cursor, Read, Write};
+use x509_cert::{
+    certificate::{Rfc5280, TbsCertificateInner},
+    der::{Decode as DerDecode, Encode as DerEncode},
+};
+
+/// The container of all structures that are produced by a v2 log
+///
+/// See RFC 9162 4.5
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransItem {
+    X509EntryV2(TimestampedCertificateEntryDataV2),
+    PrecertEntryV2(TimestampedCertificateEntryDataV2),
+    X509SctV2(SignedCertificateTimestampDataV2),
+    PrecertSctV2(SignedCertificateTimestampDataV2),
+    SignedTreeHeadV2(SignedTreeHead),
+    ConsistencyProofV2(ConsistencyProofDataV2),
+    InclusionProofV2(InclusionProofDataV2),
+}
+
+impl TransItem {
+    fn versioned_type(&self) -> u16 {
+        match self {
+            TransItem::X509EntryV2(_) => 1,
+            TransItem::PrecertEntryV2(_) => 2,
+            TransItem::X509SctV2(_) => 3,
+            TransItem::PrecertSctV2(_) => 4,
+            TransItem::SignedTreeHeadV2(_) => 5,
+            TransItem::ConsistencyProofV2(_) => 6,
+            TransItem::InclusionProofV2(_) => 7,
+        }
+    }
+}
+
+impl Encode for TransItem {
+    fn encode(&self, mut writer: impl Write) -> Result<(), CodecError> {
+        Encode::encode(&self.versioned_type(), &mut writer)?;
+
+        match self {
+            TransItem::X509EntryV2(entry) | TransItem::PrecertEntryV2(entry) => {
+                entry.encode(&mut writer)
+            }
+            TransItem::X509SctV2(sct) | TransItem::PrecertSctV2(sct) => sct.encode(&mut writer),
+            TransItem::SignedTreeHeadV2(sth) => sth.encode(&mut writer),
+            TransItem::ConsistencyProofV2(proof) => proof.encode(&mut writer),
+            TransItem::InclusionProofV2(proof) => proof.encode(&mut writer),
+        }
+    }
+}
+
+impl Decode for TransItem {
+    fn decode(mut reader: impl Read) -> Result<Self, CodecError> {
+        match <u16 as Decode>::decode(&mut reader)? {
+            1 => Ok(TransItem::X509EntryV2(Decode::decode(&mut reader)?)),
+            2 => Ok(TransItem::PrecertEntryV2(Decode::decode(&mut reader)?)),
+            3 => Ok(TransItem::X509SctV2(Decode::decode(&mut reader)?)),
+            4 => Ok(TransItem::PrecertSctV2(Decode::decode(&mut reader)?)),
+            5 => Ok(TransItem::SignedTreeHeadV2(Decode::decode(&mut reader)?)),
+            6 => Ok(TransItem::ConsistencyProofV2(Decode::decode(&mut reader)?)),
+            7 => Ok(TransItem::InclusionProofV2(Decode::decode(&mut reader)?)),
+            x => Err(CodecError::UnknownVariant("VersionedTransType", x as u64)),
+        }
+    }
+}
+
+/// A list of [`TransItems`](TransItem), as embedded into certificates
+///
+/// See RFC 9162 6.2
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TransItemList(SizedAppendVec<SerializedTransItem>);
+
+impl TransItemList {
+    pub fn into_inner(self) -> Vec<TransItem> {
+        Vec::from(self.0).into_iter().map(|item| item.0).collect()
+    }
+}
+
+impl Encode for TransItemList {
+    fn encode(&self, writer: impl Write) -> Result<(), CodecError> {
+        self.0.encode(writer)
+    }
+}
+
+impl Decode for TransItemList {
+    fn decode(reader: impl Read) -> Result<Self, CodecError> {
+        Ok(Self(SizedAppendVec::decode(reader)?))
+    }
+}
+
+/// A [`TransItem`] prefixed by its length
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SerializedTransItem(TransItem);
+
+impl Encode for SerializedTransItem {
+    fn encode(&self, writer: impl Write) -> Result<(), CodecError> {
+        let mut bytes = Cursor::new(vec![]);
+        self.0.encode(&mut bytes)?;
+        CodecVec::<u16>::from(bytes.into_inner()).encode(writer)
+    }
+}
+
+impl Decode for SerializedTransItem {
+    fn decode(reader: impl Read) -> Result<Self, CodecError> {
+        let bytes = CodecVec::<u16>::decode(reader)?;
+        Ok(Self(TransItem::decode(Cursor::new(bytes.as_ref()))?))
+    }
+}
+
+/// The entry of a certificate or precertificate as it is stored in the log
+///
+/// See RFC 9162 4.7
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimestampedCertificateEntryDataV2 {
+    pub(crate) timestamp: u64,
+    pub(crate) issuer_key_hash: NodeHash,
+    pub(crate) tbs_certificate: TbsCertificateInner<Rfc5280>,
+    pub(crate) sct_extensions: Extensions,
+}
+
+impl Encode for TimestampedCertificateEntryDataV2 {
+    fn encode(&self, mut writer: impl Write) -> Result<(), CodecError> {
+        Encode::encode(&self.timestamp, &mut writer)?;
+        self.issuer_key_hash.encode(&mut writer)?;
+
+        let mut cert_bytes = vec![];
+        let _len = self.tbs_certificate.encode_to_vec(&mut cert_bytes)?;
+        CodecVec::<U24>::from(cert_bytes).encode(&mut writer)?;
+
+        self.sct_extensions.encode(&mut writer)?;
+        Ok(())
+    }
+}
+
+impl Decode for TimestampedCertificateEntryDataV2 {
+    fn decode(mut reader: impl Read) -> Result<Self, CodecError> {
+        let timestamp = <u64 as Decode>::decode(&mut reader)?;
+        let issuer_key_hash = NodeHash::decode(&mut reader)?;
+        let cert_bytes = CodecVec::<U24>::decode(&mut reader)?;
+        let tbs_certificate = TbsCertificateInner::<Rfc5280>::from_der(cert_bytes.as_ref())?;
+        let sct_extensions = Extensions::decode(&mut reader)?;
+
+        Ok(Self {
+            timestamp,
+            issuer_key_hash,
+            tbs_certificate,
+            sct_extensions,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CertificateChain, tests::CERT_CHAIN_V2};
+
+    #[test]
+    fn trans_item_list_codec_roundtrip() {
+        let cert = CertificateChain::from_pem_chain(CERT_CHAIN_V2).unwrap();
+        let scts = cert.cert().extract_scts_v2().unwrap();
+        let leaf = cert.as_leaf_v2(&scts[0]).unwrap();
+
+        let items = scts
+            .into_iter()
+            .map(TransItem::from)
+            .chain(std::iter::once(leaf.0))
+            .collect::<Vec<_>>();
+        let list = TransItemList(
+            items
+                .iter()
+                .cloned()
+                .map(SerializedTransItem)
+                .collect::<Vec<_>>()
+                .into(),
+        );
+
+        let mut writer = Cursor::new(vec![]);
+        list.encode(&mut writer).unwrap();
+        let items2 = TransItemList::decode(Cursor::new(writer.into_inner()))
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(items, items2);
+    }
+
+    #[test]
+    fn reject_unknown_item() {
+        // versioned_type 0 is reserved
+        let bytes = [0u8, 0, 0, 0];
+        assert!(matches!(
+            TransItem::decode(Cursor::new(bytes)),
+            Err(CodecError::UnknownVariant(_, 0))
+        ));
+    }
+}
@@ -0,0 +1,48 @@
+use crate::{
+    store::Hashable,
+    utils::codec::{CodecError, Decode, Encode},
+    v2::TransItem,
+};
+use sha2::{Digest, Sha256};
+use std::io::{Cursor, Read, Write};
+
+/// A leaf of a v2 log
+///
+/// The leafs of v2 logs are [`TransItems`](TransItem) of type `x509_entry_v2` or `precert_entry_v2`.
+///
+/// See RFC 9162 4.7
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleTreeLeaf(pub(crate) TransItem);
+
+impl TryFrom<TransItem> for MerkleTreeLeaf {
+    type Error = CodecError;
+
+    fn try_from(value: TransItem) -> Result<Self, Self::Error> {
+        match value {
+            TransItem::X509EntryV2(_) | TransItem::PrecertEntryV2(_) => Ok(Self(value)),
+            _ => Err(CodecError::UnexpectedVariant),
+        }
+    }
+}
+
+impl Hashable for MerkleTreeLeaf {
+    fn hash(&self) -> [u8; 32] {
+        let mut bytes = Cursor::new(vec![]);
+        bytes.write_all(&[0]).unwrap();
+        self.encode(&mut bytes).unwrap();
+
+        Sha256::digest(bytes.into_inner()).into()
+    }
+}
+
+impl Encode for MerkleTreeLeaf {
+    fn encode(&self, writer: impl Write) -> Result<(), CodecError> {
+        self.0.encode(writer)
+    }
+}
+
+impl Decode for MerkleTreeLeaf {
+    fn decode(reader: impl Read) -> Result<Self, CodecError> {
+        TransItem::decode(reader)?.try_into()
+    }
+}
@@ -10,13 +10,12 @@ use std::{
 /// - `V1` corresponds to RFC 6962
 /// - `V2` corresponds to RFC 9162
 ///
-/// Currently, only [`Version::V1`] is supported
-///
 /// See RFC 6962 3.2
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub enum Version {
     #[default]
     V1,
+    V2,
 }
 
 impl Serialize for Version {
@@ -26,6 +25,7 @@ impl Serialize for Version {
     {
         match self {
             Version::V1 => serializer.serialize_u8(1),
+            Version::V2 => serializer.serialize_u8(2),
         }
     }
 }
@@ -38,6 +38,7 @@ impl<'de> Deserialize<'de> for Version {
         let version: u8 = <u8>::deserialize(deserializer)?;
         match version {
             1 => Ok(Version::V1),
+            2 => Ok(Version::V2),
             x => Err(serde::de::Error::custom(format!("Unsupported version {x}"))),
         }
     }
@@ -47,6 +48,7 @@ impl Display for Version {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Version::V1 => write!(f, "V1"),
+            Version::V2 => write!(f, "V2"),
         }
     }
 }
@@ -55,6 +57,7 @@ impl Encode for Version {
     fn encode(&self, mut writer: impl Write) -> Result<(), CodecError> {
         let discriminant = match self {
             Version::V1 => 0,
+            Version::V2 => 1,
         };
         Ok(writer.write_all(&[discriminant])?)
     }
@@ -67,6 +70,7 @@ impl Decode for Version {
 
         match buf[0] {
             0 => Ok(Version::V1),
+            1 => Ok(Version::V2),
             x => Err(CodecError::UnknownVariant("Version", x as u64)),
         }
     }
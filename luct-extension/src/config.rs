@@ -1,6 +1,8 @@
 use crate::USER_AGENT;
+use luct_client::{rate_limit::RateLimitConfig, retry::DEFAULT_MAX_ATTEMPTS};
+use luct_core::{CtLogConfig, tiling::WitnessPolicy};
 use luct_otlsp::OtlspClientConfig;
-use luct_scanner::ScannerConfig;
+use luct_scanner::{PolicyKind, ScannerConfig};
 use serde::{Deserialize, Serialize};
 use url::Url;
 use web_sys::{Storage, window};
@@ -52,9 +54,33 @@ pub struct ExtensionConfig {
     #[serde(default = "default_sth_update_threshold")]
     sth_update_threshold: u64,
 
+    #[serde(default)]
+    witness_policy: Option<WitnessPolicy>,
+
     #[serde(default = "default_report_lru_cache")]
     report_lru_cache: usize,
 
+    #[serde(default = "default_host_requests_per_second")]
+    host_requests_per_second: u32,
+
+    #[serde(default = "default_host_request_burst")]
+    host_request_burst: u32,
+
+    #[serde(default = "default_max_requests_in_flight")]
+    max_requests_in_flight: usize,
+
+    #[serde(default = "default_max_attempts")]
+    max_attempts: u32,
+
+    #[serde(default)]
+    ct_policy: PolicyKind,
+
+    #[serde(default)]
+    custom_logs: Vec<CtLogConfig>,
+
+    #[serde(default = "default_false")]
+    exclude_public_logs: bool,
+
     #[serde(default = "default_false")]
     debug_output: bool,
 }
@@ -63,6 +89,21 @@ impl ExtensionConfig {
     pub fn report_lru_cache(&self) -> usize {
         self.report_lru_cache
     }
+
+    /// The maximum number of attempts of a request to a log, including the first one
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Logs, that are not part of the public log list
+    pub fn custom_logs(&self) -> &[CtLogConfig] {
+        &self.custom_logs
+    }
+
+    /// Returns `true`, if only the custom logs should be used
+    pub fn exclude_public_logs(&self) -> bool {
+        self.exclude_public_logs
+    }
 }
 
 fn default_otlsp_url() -> String {
@@ -85,6 +126,22 @@ fn default_report_lru_cache() -> usize {
     1000
 }
 
+fn default_host_requests_per_second() -> u32 {
+    RateLimitConfig::DEFAULT_REQUESTS_PER_SECOND
+}
+
+fn default_host_request_burst() -> u32 {
+    RateLimitConfig::DEFAULT_BURST
+}
+
+fn default_max_requests_in_flight() -> usize {
+    RateLimitConfig::DEFAULT_MAX_IN_FLIGHT
+}
+
+fn default_max_attempts() -> u32 {
+    DEFAULT_MAX_ATTEMPTS
+}
+
 fn default_true() -> bool {
     true
 }
@@ -101,6 +158,8 @@ impl TryFrom<&ExtensionConfig> for ScannerConfig {
             .validate_cert_chain(config.validate_cert_chain)
             .sth_freshness_threshold(Duration::from_secs(config.sth_freshness_threshold))
             .sth_update_threshold(Duration::from_secs(config.sth_update_threshold))
+            .witness_policy(config.witness_policy.clone())
+            .policy(config.ct_policy)
             .build()
             .map_err(|err| err.to_string())?;
 
@@ -127,6 +186,21 @@ impl TryFrom<&ExtensionConfig> for OtlspClientConfig {
     }
 }
 
+impl TryFrom<&ExtensionConfig> for RateLimitConfig {
+    type Error = String;
+
+    fn try_from(config: &ExtensionConfig) -> Result<Self, Self::Error> {
+        let config = RateLimitConfig::builder()
+            .requests_per_second(config.host_requests_per_second)
+            .burst(config.host_request_burst)
+            .max_in_flight(config.max_requests_in_flight)
+            .build()
+            .map_err(|err| err.to_string())?;
+
+        Ok(config)
+    }
+}
+
 pub fn browser_local_store() -> Result<Storage, String> {
     let store = window()
         .map(|window| window.local_storage())
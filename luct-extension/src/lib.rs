@@ -4,12 +4,20 @@
 use crate::{browser_storage::BrowserStorage, config::load_config};
 use chrono::DateTime;
 use js_sys::{Array, Uint8Array};
-use luct_client::deduplication::RequestDeduplicationClient;
+use luct_client::{
+    cache::CachingClient,
+    deduplication::RequestDeduplicationClient,
+    rate_limit::{RateLimitClient, RateLimitConfig},
+    retry::RetryClient,
+};
 use luct_core::{
-    CertificateChain as CertChain, Fingerprint, log_list::v3::LogList, v1::SignedTreeHead,
+    CertificateChain as CertChain, Fingerprint, SignedTreeHead,
+    log_list::{v3::LogList, with_custom_logs},
 };
 use luct_otlsp::{OtlspClient, OtlspClientConfig};
-use luct_scanner::{Report, Scanner as CtScanner, ScannerConfig, ScannerImpl, Validated};
+use luct_scanner::{
+    PendingSct, Report, Scanner as CtScanner, ScannerConfig, ScannerImpl, Validated,
+};
 use luct_store::{LruCacheStore, MetadataCacheStore};
 use std::sync::Arc;
 use tracing::{Level, info};
@@ -31,9 +39,12 @@ const USER_AGENT: &str = concat!(
 struct ExtensionScannerImpl;
 
 impl ScannerImpl for ExtensionScannerImpl {
-    type Client = RequestDeduplicationClient<OtlspClient>;
+    type Client = RequestDeduplicationClient<
+        CachingClient<RetryClient<RateLimitClient<OtlspClient>>, BrowserStorage<[u8; 32], Vec<u8>>>,
+    >;
     type ReportStore = LruCacheStore<BrowserStorage<Fingerprint, Report>>;
     type SthStore = MetadataCacheStore<BrowserStorage<u64, Validated<SignedTreeHead>>>;
+    type PendingStore = BrowserStorage<[u8; 32], PendingSct>;
 }
 
 #[wasm_bindgen]
@@ -100,17 +111,33 @@ impl Scanner {
     #[wasm_bindgen(constructor)]
     pub fn new(log_list: String) -> Result<Self, String> {
         let log_list: LogList = serde_json::from_str(&log_list).map_err(|err| format!("{err}"))?;
-        let logs = log_list.currently_active_logs();
-
         let extension_config = load_config()?;
+        let logs = with_custom_logs(
+            log_list.currently_active_logs(),
+            &log_list.all_logs(),
+            extension_config.custom_logs(),
+            extension_config.exclude_public_logs(),
+        )
+        .map_err(|err| err.to_string())?;
+
         let scanner_config = ScannerConfig::try_from(&extension_config)?;
         let otlsp_config = OtlspClientConfig::try_from(&extension_config)?;
-
-        let client = RequestDeduplicationClient::new(OtlspClient::new(otlsp_config));
+        let rate_limit_config = RateLimitConfig::try_from(&extension_config)?;
+
+        let client = RetryClient::new(RateLimitClient::new(
+            OtlspClient::new(otlsp_config),
+            rate_limit_config,
+        ))
+        .with_max_attempts(extension_config.max_attempts());
+        let client = RequestDeduplicationClient::new(CachingClient::new(
+            client,
+            BrowserStorage::new_local_store("tile".to_string())?,
+        ));
 
         let report_cache =
             BrowserStorage::<Fingerprint, Report>::new_local_store("report".to_string())?;
         let report_cache = LruCacheStore::new(report_cache, extension_config.report_lru_cache());
+        let pending_store = BrowserStorage::new_local_store("pending".to_string())?;
 
         let time_source = || {
             DateTime::from_timestamp_millis(
@@ -122,7 +149,13 @@ impl Scanner {
             .unwrap()
         };
 
-        let mut scanner = CtScanner::new(scanner_config, report_cache, client, time_source);
+        let mut scanner = CtScanner::new(
+            scanner_config,
+            report_cache,
+            pending_store,
+            client,
+            time_source,
+        );
 
         for log in logs {
             let name = log.description();
@@ -161,6 +194,15 @@ impl Scanner {
         Ok(Some(report))
     }
 
+    /// Re-check the SCTs, whose inclusion was deferred until the MMD of their log passed
+    ///
+    /// Returns the updated reports.
+    #[wasm_bindgen]
+    pub async fn recheck_pending_scts(&self) -> Result<JsValue, String> {
+        let reports = self.scanner.recheck_pending_scts().await;
+        serde_wasm_bindgen::to_value(&reports).map_err(|err| format!("{err}"))
+    }
+
     #[wasm_bindgen]
     pub fn is_report_safe(report: JsValue) -> Result<bool, String> {
         let report: Report =
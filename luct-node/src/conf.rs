@@ -10,11 +10,44 @@ pub(crate) struct Config {
     /// Path to the log list to use in luct-node
     pub(crate) log_list: String,
 
+    /// Path to the PEM encoded public key of the log list
+    ///
+    /// If set, the log list file is updated to the latest signed log list on startup and every
+    /// `log_list_update_interval`.
+    pub(crate) log_list_key: Option<String>,
+
+    /// Interval in seconds, in which the log list is updated
+    #[serde(default = "default_log_list_update_interval")]
+    pub(crate) log_list_update_interval: u64,
+
     /// Path at which to serve the oblivious TLS proxy
     pub(crate) otlsp_path: Option<String>,
 
     /// Path at which to serve the metrics endpoint
     pub(crate) metrics_path: Option<String>,
+
+    /// Path at which to serve the latest collected STH of a log
+    ///
+    /// If set, the STHs of all logs in the log list are collected periodically.
+    /// The history of all collected STHs of a log is served at `<sth_path>/history`.
+    /// STHs, that were rejected as inconsistent with the history, are served at
+    /// `<sth_path>/inconsistent`.
+    pub(crate) sth_path: Option<String>,
+
+    /// Directory in which to keep the collected STHs, they are kept in memory if unset
+    pub(crate) sth_store: Option<String>,
+
+    /// Interval in seconds, in which the STHs of the logs are fetched
+    #[serde(default = "default_sth_update_interval")]
+    pub(crate) sth_update_interval: u64,
+}
+
+fn default_log_list_update_interval() -> u64 {
+    24 * 60 * 60
+}
+
+fn default_sth_update_interval() -> u64 {
+    10 * 60
 }
 
 impl Config {
@@ -0,0 +1,55 @@
+use crate::{USER_AGENT, conf::Config};
+use eyre::Context;
+use luct_client::{LogListUpdater, reqwest::ReqwestClient};
+use luct_core::{
+    CtLog,
+    log_list::{LogListKey, v3::LogList},
+};
+
+impl Config {
+    /// Returns the currently active logs of the log list
+    pub(crate) fn active_logs(&self) -> eyre::Result<Vec<CtLog>> {
+        let logs = std::fs::read_to_string(&self.log_list)
+            .with_context(|| format! {"Could not find log list file at {}", self.log_list})?;
+        let logs: LogList =
+            serde_json::from_str(&logs).with_context(|| "Failed to parse log list")?;
+        let logs = logs.currently_active_logs();
+        tracing::info!("Imported {} logs", logs.len());
+
+        Ok(logs)
+    }
+
+    /// Replace the log list file with the latest signed log list, if `log_list_key` is set
+    ///
+    /// Returns `true`, if the log list file was replaced.
+    pub(crate) async fn update_log_list(&self) -> eyre::Result<bool> {
+        let Some(key_path) = &self.log_list_key else {
+            return Ok(false);
+        };
+
+        let key = std::fs::read_to_string(key_path)
+            .with_context(|| format!("Could not read log list key at {key_path}"))?;
+        let key =
+            LogListKey::from_pem(&key).map_err(|err| eyre::eyre!("Invalid log list key: {err}"))?;
+
+        let current = std::fs::read_to_string(&self.log_list)
+            .with_context(|| format! {"Could not find log list file at {}", self.log_list})?;
+        let current: LogList =
+            serde_json::from_str(&current).with_context(|| "Failed to parse log list")?;
+
+        let updater = LogListUpdater::new(ReqwestClient::new(USER_AGENT), key);
+        let Some((updated, data, _)) = updater
+            .update(&current)
+            .await
+            .with_context(|| "Failed to update log list")?
+        else {
+            return Ok(false);
+        };
+
+        std::fs::write(&self.log_list, data)
+            .with_context(|| format!("Could not write log list file at {}", self.log_list))?;
+        tracing::info!("Updated log list to {}", updated.timestamp());
+
+        Ok(true)
+    }
+}
@@ -1,18 +1,31 @@
 #![forbid(unsafe_code)]
 
 use crate::{
-    args::Args, conf::Config, metrics::handle_metrics_request, otlsp::handle_otlsp_connection,
+    args::Args,
+    conf::Config,
+    metrics::handle_metrics_request,
+    otlsp::handle_otlsp_connection,
     state::NodeState,
+    sth::{handle_inconsistent_sth_request, handle_sth_history_request, handle_sth_request},
 };
 use axum::{Router, routing::get};
 use clap::Parser;
+use std::time::Duration;
 use tracing_subscriber::EnvFilter;
 
 mod args;
 mod conf;
+mod log_list;
 mod metrics;
 mod otlsp;
 mod state;
+mod sth;
+
+const USER_AGENT: &str = concat!(
+    "luct-node/",
+    env!("CARGO_PKG_VERSION"),
+    " (https://github.com/Sawchord/luct/)"
+);
 
 #[tokio::main(flavor = "current_thread")]
 async fn main() -> eyre::Result<()> {
@@ -30,6 +43,10 @@ async fn main() -> eyre::Result<()> {
     }
 
     let config = Config::parse()?;
+    // A failed update must not keep the node from starting, the log list on disk is still valid
+    if let Err(err) = config.update_log_list().await {
+        tracing::warn!("Keeping the current log list: {:?}", err);
+    }
 
     let listener = tokio::net::TcpListener::bind(config.endpoint_addr.clone())
         .await
@@ -53,8 +70,35 @@ async fn main() -> eyre::Result<()> {
         router
     };
 
-    let router = router.with_state(state);
+    let router = if let Some(sth_path) = &state.config().sth_path {
+        tracing::info!("Serving STH endpoint at {}", sth_path);
+        let sth_path_base = sth_path.trim_end_matches('/');
+        router
+            .route(sth_path, get(handle_sth_request))
+            .route(
+                &format!("{sth_path_base}/history"),
+                get(handle_sth_history_request),
+            )
+            .route(
+                &format!("{sth_path_base}/inconsistent"),
+                get(handle_inconsistent_sth_request),
+            )
+    } else {
+        router
+    };
+
+    let router = router.with_state(state.clone());
+    let server = async { axum::serve(listener, router).await.unwrap() };
+
+    let sth_collection = async {
+        if let Some(collector) = state.sth_collector() {
+            let interval = Duration::from_secs(state.config().sth_update_interval);
+            collector.run(interval).await
+        }
+    };
+    let log_list_updates =
+        state.run_log_list_updates(Duration::from_secs(state.config().log_list_update_interval));
+    futures::future::join3(server, sth_collection, log_list_updates).await;
 
-    axum::serve(listener, router).await.unwrap();
     Ok(())
 }
@@ -4,8 +4,6 @@ use axum::{
     response::Response,
 };
 use axum_macros::debug_handler;
-use eyre::Context;
-use luct_core::log_list::v3::LogList;
 use otlsp_server::{Destination, handle_connection};
 use std::collections::BTreeSet;
 use url::Url;
@@ -13,12 +11,7 @@ use url::Url;
 impl Config {
     /// Extract all urls the otlsp service needs to enable
     pub(crate) fn get_otlsp_urls(&self) -> eyre::Result<Vec<Url>> {
-        let logs = std::fs::read_to_string(&self.log_list)
-            .with_context(|| format! {"Could not find log list file at {}", self.log_list})?;
-        let logs: LogList =
-            serde_json::from_str(&logs).with_context(|| "Failed to parse log list")?;
-        let logs = logs.currently_active_logs();
-        tracing::info!("Imported {} logs", logs.len());
+        let logs = self.active_logs()?;
 
         let urls: BTreeSet<Url> = logs
             .iter()
@@ -1,27 +1,49 @@
-use crate::conf::Config;
+use crate::{
+    USER_AGENT,
+    conf::Config,
+    sth::{InconsistentSthStore, SthCollector, SthStore},
+};
 use axum::extract::State;
+use luct_client::reqwest::ReqwestClient;
 use otlsp_server::OtlspMetrics;
-use std::sync::Arc;
+use std::{
+    path::Path,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
 use url::Url;
 
+/// The [`SthCollector`] of the node, keeping the STHs in the configured stores
+type NodeSthCollector = SthCollector<ReqwestClient, SthStore, InconsistentSthStore>;
+
 #[derive(Debug, Clone)]
 pub(crate) struct NodeState(Arc<NodeStateInner>);
 
 #[derive(Debug)]
 struct NodeStateInner {
     config: Config,
-    otlsp_urls: Vec<Url>,
+    otlsp_urls: RwLock<Vec<Url>>,
     otlsp_metrics: OtlspMetrics,
+    sth_collector: Option<NodeSthCollector>,
 }
 
 impl NodeState {
     pub(crate) fn new(config: Config) -> eyre::Result<Self> {
         let urls = config.get_otlsp_urls()?;
+        let sth_collector = match &config.sth_path {
+            None => None,
+            Some(_) => Some(SthCollector::with_stores(
+                config.active_logs()?,
+                ReqwestClient::new(USER_AGENT),
+                config.sth_store.as_ref().map(Path::new),
+            )),
+        };
 
         Ok(Self(Arc::new(NodeStateInner {
             config,
-            otlsp_urls: urls,
+            otlsp_urls: RwLock::new(urls),
             otlsp_metrics: OtlspMetrics::default(),
+            sth_collector,
         })))
     }
 
@@ -29,11 +51,57 @@ impl NodeState {
         &self.0.config
     }
 
-    pub(crate) fn otlsp_urls(&self) -> &[Url] {
-        &self.0.otlsp_urls
+    pub(crate) fn otlsp_urls(&self) -> Vec<Url> {
+        self.0.otlsp_urls.read().unwrap().clone()
     }
 
     pub(crate) fn otlsp_metrics(&self) -> State<OtlspMetrics> {
         State(self.0.otlsp_metrics.clone())
     }
+
+    /// Returns the [`SthCollector`], if STH collection is enabled
+    pub(crate) fn sth_collector(&self) -> Option<&NodeSthCollector> {
+        self.0.sth_collector.as_ref()
+    }
+
+    /// Update the log list every `interval` and reload the logs, if it changed
+    ///
+    /// Does nothing, if no `log_list_key` is configured.
+    pub(crate) async fn run_log_list_updates(&self, interval: Duration) {
+        if self.config().log_list_key.is_none() {
+            return;
+        }
+
+        let mut interval = tokio::time::interval(interval);
+        // The log list was already updated on startup
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            match self.config().update_log_list().await {
+                Ok(true) => {
+                    if let Err(err) = self.reload_logs() {
+                        tracing::warn!("Failed to reload the logs: {:?}", err);
+                    }
+                }
+                Ok(false) => (),
+                Err(err) => tracing::warn!("Keeping the current log list: {:?}", err),
+            }
+        }
+    }
+
+    /// Reload the logs from the log list file
+    fn reload_logs(&self) -> eyre::Result<()> {
+        let urls = self.config().get_otlsp_urls()?;
+        let logs = match &self.0.sth_collector {
+            Some(_) => Some(self.config().active_logs()?),
+            None => None,
+        };
+
+        *self.0.otlsp_urls.write().unwrap() = urls;
+        if let (Some(collector), Some(logs)) = (&self.0.sth_collector, logs) {
+            collector.set_logs(logs);
+        }
+
+        Ok(())
+    }
 }
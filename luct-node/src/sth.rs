@@ -0,0 +1,532 @@
+//! Collection of the STHs of all logs, such that scanners can bootstrap and cross-check their view
+
+use crate::state::NodeState;
+use axum::{
+    extract::{Query, State},
+    response::Response,
+};
+use luct_client::{Client, CtClient, TileFetchStore};
+use luct_core::{
+    CtLog, LogId, SignedCertificateTimestamp, SignedTreeHead,
+    store::{MemoryStore, OrderedStore, SearchableStoreRead},
+    tree::{Tree, TreeHead},
+};
+use luct_store::{FilesystemStore, StoreSwitch, StringStoreValue};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::Path,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+/// The [`Store`](luct_core::store::Store) holding the history of STHs of a log, keyed by tree size
+pub(crate) type SthStore =
+    StoreSwitch<MemoryStore<u64, SignedTreeHead>, FilesystemStore<u64, SignedTreeHead>>;
+
+/// The [`Store`](luct_core::store::Store) holding the rejected STHs of a log, keyed by their timestamp
+pub(crate) type InconsistentSthStore =
+    StoreSwitch<MemoryStore<u64, InconsistentSth>, FilesystemStore<u64, InconsistentSth>>;
+
+/// An STH, that was rejected since it is inconsistent with an STH collected before
+///
+/// Both STHs are signed by the log, such that they are evidence of a split view.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct InconsistentSth {
+    sth: SignedTreeHead,
+    collected: SignedTreeHead,
+    error: String,
+}
+
+impl StringStoreValue for InconsistentSth {
+    fn serialize_value(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    fn deserialize_value(value: &str) -> Option<Self> {
+        serde_json::from_str(value).ok()
+    }
+}
+
+// NOTE: The tiles are not cached, since `LruCacheStore` is not `Sync`. Only a few tiles are
+// needed per consistency proof, and STHs are fetched rarely.
+type TileTree<C> = Tree<TileFetchStore<C>, MemoryStore<u64, SignedCertificateTimestamp>>;
+
+/// Returns the stores of the collected and of the rejected STHs of a log
+type StoreFactory<S, I> = Box<dyn Fn(&CtLog) -> (S, I) + Send + Sync>;
+
+type CollectedLogs<C, S, I> = BTreeMap<LogId, Arc<CollectedLog<C, S, I>>>;
+
+/// Periodically fetches the STHs of all logs and keeps their history
+pub(crate) struct SthCollector<C, S, I> {
+    client: C,
+    stores: StoreFactory<S, I>,
+    logs: RwLock<CollectedLogs<C, S, I>>,
+}
+
+struct CollectedLog<C, S, I> {
+    client: CtClient<C>,
+    tiles: Option<TileTree<C>>,
+    store: S,
+    inconsistent: I,
+}
+
+impl<C, S, I> fmt::Debug for SthCollector<C, S, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SthCollector")
+            .field("logs", &self.logs.read().unwrap().len())
+            .finish()
+    }
+}
+
+impl<C: Client + Clone> SthCollector<C, SthStore, InconsistentSthStore> {
+    /// Create a collector for `logs`, that keeps the STHs in `directory` or in memory, if unset
+    ///
+    /// Each log gets a subdirectory named after its log id, holding the collected STHs in `sth`
+    /// and the rejected STHs in `inconsistent`.
+    pub(crate) fn with_stores(logs: Vec<CtLog>, client: C, directory: Option<&Path>) -> Self {
+        let directory = directory.map(Path::to_path_buf);
+        Self::new(logs, client, move |log| match &directory {
+            None => (
+                StoreSwitch::A(MemoryStore::default()),
+                StoreSwitch::A(MemoryStore::default()),
+            ),
+            Some(directory) => {
+                let directory = directory.join(log.log_id().to_file_name());
+                (
+                    StoreSwitch::B(FilesystemStore::new(directory.join("sth"))),
+                    StoreSwitch::B(FilesystemStore::new(directory.join("inconsistent"))),
+                )
+            }
+        })
+    }
+}
+
+impl<
+    C: Client + Clone,
+    S: OrderedStore<Key = u64, Value = SignedTreeHead> + SearchableStoreRead,
+    I: OrderedStore<Key = u64, Value = InconsistentSth> + SearchableStoreRead,
+> SthCollector<C, S, I>
+{
+    /// Create a collector for `logs`, where `stores` returns the store of the collected and of
+    /// the rejected STHs of each log
+    pub(crate) fn new(
+        logs: Vec<CtLog>,
+        client: C,
+        stores: impl Fn(&CtLog) -> (S, I) + Send + Sync + 'static,
+    ) -> Self {
+        let collector = Self {
+            client,
+            stores: Box::new(stores),
+            logs: RwLock::new(BTreeMap::new()),
+        };
+        collector.set_logs(logs);
+        collector
+    }
+
+    /// Replace the collected logs with `logs`, e.g. after the log list was updated
+    ///
+    /// Logs, that are already collected, keep their state. Logs, that are not part of `logs`
+    /// anymore, are no longer collected or served.
+    pub(crate) fn set_logs(&self, logs: Vec<CtLog>) {
+        let mut collected = self.logs.write().unwrap();
+        let logs = logs
+            .into_iter()
+            .map(|log| match collected.get(log.log_id()) {
+                Some(existing) => (log.log_id().clone(), existing.clone()),
+                None => (log.log_id().clone(), Arc::new(self.collect_log(log))),
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        tracing::info!("Collecting the STHs of {} logs", logs.len());
+        *collected = logs;
+    }
+
+    fn collect_log(&self, log: CtLog) -> CollectedLog<C, S, I> {
+        let client = CtClient::from_log(log.clone(), self.client.clone());
+        let tiles = log.config().is_tiling().then(|| {
+            Tree::new(
+                TileFetchStore::new(log.description().to_string(), client.clone()),
+                MemoryStore::default(),
+            )
+        });
+        let (store, inconsistent) = (self.stores)(&log);
+        CollectedLog {
+            client,
+            tiles,
+            store,
+            inconsistent,
+        }
+    }
+
+    fn log(&self, log_id: &LogId) -> Option<Arc<CollectedLog<C, S, I>>> {
+        self.logs.read().unwrap().get(log_id).cloned()
+    }
+
+    /// Fetch the STHs of all logs every `interval`
+    pub(crate) async fn run(&self, interval: Duration) {
+        let mut interval = tokio::time::interval(interval);
+        loop {
+            interval.tick().await;
+            self.update_all().await;
+        }
+    }
+
+    /// Fetch the current STH of every log and add it to the history, if it is consistent
+    ///
+    /// Inconsistent STHs are kept separately as evidence of a split view.
+    pub(crate) async fn update_all(&self) {
+        let logs = self
+            .logs
+            .read()
+            .unwrap()
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+        let updates = logs.iter().map(|log| async move {
+            if let Err(err) = log.update().await {
+                tracing::warn!(
+                    "Failed to update STH of log {}: {:#}",
+                    log.client.log().description(),
+                    err
+                );
+            }
+        });
+        futures::future::join_all(updates).await;
+    }
+
+    /// Returns the latest STH of the log with `log_id`
+    pub(crate) async fn latest_sth(&self, log_id: &LogId) -> Option<SignedTreeHead> {
+        let log = self.log(log_id)?;
+        log.store.last().await.map(|(_, sth)| sth)
+    }
+
+    /// Returns all STHs of the log with `log_id`, ordered by their tree size
+    pub(crate) async fn sth_history(&self, log_id: &LogId) -> Option<Vec<SignedTreeHead>> {
+        let log = self.log(log_id)?;
+        let mut sths = log.store.filter(|_, _| true).await;
+        sths.sort_by_key(|(tree_size, _)| *tree_size);
+        Some(sths.into_iter().map(|(_, sth)| sth).collect())
+    }
+
+    /// Returns all rejected STHs of the log with `log_id`, ordered by their timestamp
+    pub(crate) async fn inconsistent_sths(&self, log_id: &LogId) -> Option<Vec<InconsistentSth>> {
+        let log = self.log(log_id)?;
+        let mut sths = log.inconsistent.filter(|_, _| true).await;
+        sths.sort_by_key(|(timestamp, _)| *timestamp);
+        Some(sths.into_iter().map(|(_, sth)| sth).collect())
+    }
+}
+
+impl<
+    C: Client,
+    S: OrderedStore<Key = u64, Value = SignedTreeHead>,
+    I: OrderedStore<Key = u64, Value = InconsistentSth>,
+> CollectedLog<C, S, I>
+{
+    async fn update(&self) -> eyre::Result<()> {
+        let sth = match &self.tiles {
+            Some(_) => self.client.get_checkpoint().await?.into(),
+            None => self.client.get_sth().await?,
+        };
+
+        if self.store.get(sth.tree_size()).await.as_ref() == Some(&sth) {
+            return Ok(());
+        }
+
+        if let Some((_, latest)) = self.store.last().await
+            && let Some(error) = self.find_inconsistency(&latest, &sth).await?
+        {
+            self.inconsistent
+                .insert(
+                    sth.timestamp(),
+                    InconsistentSth {
+                        sth: sth.clone(),
+                        collected: latest.clone(),
+                        error: error.clone(),
+                    },
+                )
+                .await;
+            eyre::bail!(
+                "STH of size {} is inconsistent with STH of size {}: {}",
+                sth.tree_size(),
+                latest.tree_size(),
+                error
+            );
+        }
+
+        tracing::debug!(
+            "Collected STH of size {} of log {}",
+            sth.tree_size(),
+            self.client.log().description()
+        );
+        self.store.insert(sth.tree_size(), sth).await;
+        Ok(())
+    }
+
+    /// Returns the reason, why the trees of both STHs are inconsistent with each other
+    ///
+    /// Failing to obtain the consistency proof is returned as an error instead, since it is no
+    /// evidence of a split view.
+    async fn find_inconsistency(
+        &self,
+        first: &SignedTreeHead,
+        second: &SignedTreeHead,
+    ) -> eyre::Result<Option<String>> {
+        let (first, second) = if first.tree_size() <= second.tree_size() {
+            (first, second)
+        } else {
+            (second, first)
+        };
+
+        if first.tree_size() == second.tree_size() {
+            if first.root_hash() != second.root_hash() {
+                return Ok(Some(
+                    "The log signed two different roots for the same tree size".to_string(),
+                ));
+            }
+            return Ok(None);
+        }
+
+        let proof = match &self.tiles {
+            None => self.client.get_consistency_proof(first, second).await?,
+            Some(tiles) => {
+                // Need to set the tree size correctly for the async proof to work
+                tiles.nodes().set_tree_size(second.tree_size());
+                tiles
+                    .get_consistency_proof(&TreeHead::from(first), &TreeHead::from(second))
+                    .await
+                    .map_err(|err| eyre::eyre!("Failed to generate consistency proof: {err}"))?
+            }
+        };
+
+        match proof.validate(&TreeHead::from(first), &TreeHead::from(second)) {
+            Ok(()) => Ok(None),
+            Err(err) => Ok(Some(format!("Failed to validate consistency proof: {err}"))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct SthQuery {
+    log_id: String,
+}
+
+pub(crate) async fn handle_sth_request(
+    state: State<NodeState>,
+    query: Query<SthQuery>,
+) -> Response {
+    tracing::debug!("Serving latest STH of log {}", query.log_id);
+
+    let Some(collector) = state.sth_collector() else {
+        return error_response(404, "STH collection is disabled");
+    };
+    let Ok(log_id) = LogId::try_from(query.log_id.as_str()) else {
+        return error_response(400, "Invalid log id");
+    };
+
+    match collector.latest_sth(&log_id).await {
+        Some(sth) => json_response(&sth),
+        None => error_response(404, "No STH of this log has been collected"),
+    }
+}
+
+pub(crate) async fn handle_sth_history_request(
+    state: State<NodeState>,
+    query: Query<SthQuery>,
+) -> Response {
+    tracing::debug!("Serving STH history of log {}", query.log_id);
+
+    let Some(collector) = state.sth_collector() else {
+        return error_response(404, "STH collection is disabled");
+    };
+    let Ok(log_id) = LogId::try_from(query.log_id.as_str()) else {
+        return error_response(400, "Invalid log id");
+    };
+
+    match collector.sth_history(&log_id).await {
+        Some(sths) => json_response(&sths),
+        None => error_response(404, "The log is not collected by this node"),
+    }
+}
+
+pub(crate) async fn handle_inconsistent_sth_request(
+    state: State<NodeState>,
+    query: Query<SthQuery>,
+) -> Response {
+    tracing::debug!("Serving inconsistent STHs of log {}", query.log_id);
+
+    let Some(collector) = state.sth_collector() else {
+        return error_response(404, "STH collection is disabled");
+    };
+    let Ok(log_id) = LogId::try_from(query.log_id.as_str()) else {
+        return error_response(400, "Invalid log id");
+    };
+
+    match collector.inconsistent_sths(&log_id).await {
+        Some(sths) => json_response(&sths),
+        None => error_response(404, "The log is not collected by this node"),
+    }
+}
+
+fn json_response(value: &impl serde::Serialize) -> Response {
+    Response::builder()
+        .status(200)
+        .header("content-type", "application/json")
+        .body(serde_json::to_string(value).unwrap().into())
+        .unwrap()
+}
+
+fn error_response(status: u16, msg: &str) -> Response {
+    Response::builder()
+        .status(status)
+        .body(msg.to_string().into())
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use luct_client::replay::{Fixture, ReplayClient};
+    use luct_core::{
+        store::StoreWrite,
+        v1::{self, responses::GetSthResponse},
+    };
+
+    const ARGON2025H1: &str = r#"{
+        "description": "Google Argon",
+        "url": "https://ct.googleapis.com/logs/us1/argon2025h1/",
+        "key": "MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEIIKh+WdoqOTblJji4WiH5AltIDUzODyvFKrXCBjw/Rab0/98J4LUh7dOJEY7+66+yCNSICuqRAX+VPnV8R1Fmg==",
+        "mmd": 86400
+    }"#;
+    const ARGON2025H1_REPLAY: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../testdata/replay/argon2025h1"
+    );
+    const ARGON2025H1_STH2806: &str = r#"{
+        "tree_size": 1425614114,
+        "timestamp": 1751114416696,
+        "sha256_root_hash": "LHtW79pwJohJF5Yn/tyozEroOnho4u3JAGn7WeHSR54=",
+        "tree_head_signature": "BAMARzBFAiEAg4w8LlTFKd3KL6lo5Zde9OupHYNN0DDk8U54PenirI4CIHL8ucpkJw5zFLh8UvLA+Zf+f8Ms+tLsVtzHuqnO0qjm"
+    }"#;
+
+    type TestCollector = SthCollector<
+        ReplayClient,
+        MemoryStore<u64, SignedTreeHead>,
+        MemoryStore<u64, InconsistentSth>,
+    >;
+
+    /// Create a collector of the Argon2025h1 log, that already collected the STH of 2025-06-28
+    async fn get_collector(client: ReplayClient) -> (CtLog, SignedTreeHead, TestCollector) {
+        let log = CtLog::new(serde_json::from_str(ARGON2025H1).unwrap()).unwrap();
+        let collector = SthCollector::new(vec![log.clone()], client, |_| {
+            (MemoryStore::default(), MemoryStore::default())
+        });
+        assert_eq!(collector.sth_history(log.log_id()).await, Some(vec![]));
+
+        let response: GetSthResponse = serde_json::from_str(ARGON2025H1_STH2806).unwrap();
+        let old_sth = SignedTreeHead::from(v1::SignedTreeHead::try_from(response).unwrap());
+        let store = &collector.log(log.log_id()).unwrap().store;
+        store.insert(old_sth.tree_size(), old_sth.clone()).await;
+
+        (log, old_sth, collector)
+    }
+
+    /// Returns the replayed Argon2025h1 log, serving the consistency proof with `status` and `body`
+    fn with_consistency_proof(status: u16, body: &str) -> ReplayClient {
+        ReplayClient::load(ARGON2025H1_REPLAY)
+            .unwrap()
+            .with_fixture(Fixture::get(
+                "https://ct.googleapis.com/logs/us1/argon2025h1/ct/v1/get-sth-consistency",
+                &[("first", "1425614114"), ("second", "1425633154")],
+                status,
+                body,
+            ))
+    }
+
+    #[tokio::test]
+    async fn collect_consistent_sths() {
+        let (log, old_sth, collector) =
+            get_collector(ReplayClient::load(ARGON2025H1_REPLAY).unwrap()).await;
+
+        // The new STH is only collected after checking its consistency to the old STH
+        collector.update_all().await;
+        let new_sth = collector.latest_sth(log.log_id()).await.unwrap();
+        assert_eq!(new_sth.tree_size(), 1425633154);
+        assert_eq!(
+            collector.sth_history(log.log_id()).await,
+            Some(vec![old_sth, new_sth])
+        );
+
+        // Unknown logs are not served
+        let unknown = LogId::try_from("1.3.101.8192").unwrap();
+        assert_eq!(collector.latest_sth(&unknown).await, None);
+        assert_eq!(collector.inconsistent_sths(&unknown).await, None);
+    }
+
+    #[tokio::test]
+    async fn reload_logs() {
+        let (log, old_sth, collector) =
+            get_collector(ReplayClient::load(ARGON2025H1_REPLAY).unwrap()).await;
+
+        // Logs that are still part of the log list keep their history
+        collector.set_logs(vec![log.clone()]);
+        assert_eq!(
+            collector.sth_history(log.log_id()).await,
+            Some(vec![old_sth])
+        );
+
+        // Logs that were removed from the log list are no longer served
+        collector.set_logs(vec![]);
+        assert_eq!(collector.sth_history(log.log_id()).await, None);
+        collector.update_all().await;
+
+        collector.set_logs(vec![log.clone()]);
+        assert_eq!(collector.sth_history(log.log_id()).await, Some(vec![]));
+    }
+
+    #[tokio::test]
+    async fn keep_inconsistent_sths() {
+        // The log returns a consistency proof, that does not connect the two STHs
+        let client = with_consistency_proof(
+            200,
+            r#"{"consistency":["AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA="]}"#,
+        );
+        let (log, old_sth, collector) = get_collector(client).await;
+
+        collector.update_all().await;
+        assert_eq!(
+            collector.sth_history(log.log_id()).await,
+            Some(vec![old_sth.clone()])
+        );
+
+        let inconsistent = collector.inconsistent_sths(log.log_id()).await.unwrap();
+        assert_eq!(inconsistent.len(), 1);
+        assert_eq!(inconsistent[0].sth.tree_size(), 1425633154);
+        assert_eq!(inconsistent[0].collected, old_sth);
+
+        // Fetching the STH again does not duplicate the evidence
+        collector.update_all().await;
+        assert_eq!(
+            collector.inconsistent_sths(log.log_id()).await,
+            Some(inconsistent)
+        );
+    }
+
+    #[tokio::test]
+    async fn ignore_unavailable_consistency_proof() {
+        let (log, old_sth, collector) = get_collector(with_consistency_proof(503, "")).await;
+
+        // Failing to fetch the proof is no evidence of a split view
+        collector.update_all().await;
+        assert_eq!(
+            collector.sth_history(log.log_id()).await,
+            Some(vec![old_sth])
+        );
+        assert_eq!(
+            collector.inconsistent_sths(log.log_id()).await,
+            Some(vec![])
+        );
+    }
+}
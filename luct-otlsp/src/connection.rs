@@ -1,7 +1,7 @@
 use crate::config::OtlspClientConfig;
 use http_body_util::BodyExt;
 use hyper::{
-    Request,
+    Request, Response,
     client::conn::http1::SendRequest,
     header::{HOST, HeaderValue, USER_AGENT},
 };
@@ -51,7 +51,7 @@ impl OtlspConnection {
         &mut self,
         url: &Url,
         params: &[(&str, &str)],
-    ) -> Result<(u16, Vec<u8>), OtlspError> {
+    ) -> Result<Response<Vec<u8>>, OtlspError> {
         assert_eq!(Some(self.host()?.as_str()), url.host_str());
 
         let mut url = url.clone();
@@ -87,18 +87,18 @@ impl OtlspConnection {
             ))?
             .send_request(request)
             .await?;
-        let status = response.status().as_u16();
-        let response: Vec<u8> = response.collect().await?.to_bytes().into();
+        let (parts, body) = response.into_parts();
+        let body: Vec<u8> = body.collect().await?.to_bytes().into();
 
         tracing::debug!(
             "Received {} bytes from request (status: {})",
-            response.len(),
-            status
+            body.len(),
+            parts.status
         );
 
         self.last_access = Instant::now();
 
-        Ok((status, response))
+        Ok(Response::from_parts(parts, body))
     }
 
     fn host(&self) -> Result<String, OtlspError> {
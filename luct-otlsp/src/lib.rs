@@ -3,6 +3,7 @@
 pub use crate::config::OtlspClientConfig;
 use crate::connection::OtlspConnection;
 use futures::lock::Mutex as FutMutex;
+use hyper::header::RETRY_AFTER;
 use luct_client::{Client, ClientError, reqwest::ReqwestClient};
 use std::{
     collections::HashMap,
@@ -51,6 +52,12 @@ impl Client for OtlspClient {
         let (status, response) = Self::request(&connection, url, params).await?;
         Ok((status, Arc::new(response)))
     }
+
+    async fn post(&self, url: &Url, body: &str) -> Result<(u16, Arc<String>), ClientError> {
+        // NOTE: The proxy connection only supports GET requests. Submissions reveal the
+        // submitted chain to the log anyway, so there is no privacy to be gained here.
+        self.fallback.post(url, body).await
+    }
 }
 
 impl OtlspClient {
@@ -142,7 +149,15 @@ impl OtlspClient {
             .get_async(url, params)
             .await
             .map_err(|err| ClientError::ConnectionErrorStd(Arc::new(err)))?;
-        Ok(response)
+
+        let status = response.status().as_u16();
+        let retry_after = response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok());
+        ClientError::check_retry_after(url, status, retry_after)?;
+
+        Ok((status, response.into_body()))
     }
 }
 
@@ -226,6 +241,6 @@ mod test {
                 .build()
                 .unwrap(),
         );
-        CtClient::new(config, client)
+        CtClient::new(config, client).unwrap()
     }
 }
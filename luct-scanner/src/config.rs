@@ -1,4 +1,6 @@
+use crate::PolicyKind;
 use derive_builder::Builder;
+use luct_core::tiling::WitnessPolicy;
 use serde::{Deserialize, Serialize};
 use web_time::Duration;
 
@@ -29,6 +31,18 @@ pub struct ScannerConfig {
     /// This value must not be larger than `sth_freshness_theshold`
     #[builder(default = "Duration::from_secs(60 * 60 * 8)")]
     pub(crate) sth_update_threshold: Duration,
+
+    /// If set, checkpoints of tiling logs are only accepted, if they satisfy this [`WitnessPolicy`]
+    ///
+    /// This prevents a log from presenting a split view, that has not been seen by the witnesses.
+    #[builder(default)]
+    pub(crate) witness_policy: Option<WitnessPolicy>,
+
+    /// The [`CtPolicy`](crate::CtPolicy), that certificates must comply with
+    ///
+    /// It can be replaced by a custom policy via [`Scanner::set_policy`](crate::Scanner::set_policy).
+    #[builder(default)]
+    pub(crate) policy: PolicyKind,
 }
 
 impl ScannerConfig {
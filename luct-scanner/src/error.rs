@@ -1,5 +1,5 @@
 use luct_client::ClientError;
-use luct_core::{CertificateError, tiling::TilingError};
+use luct_core::{CertificateError, tiling::TilingError, tree::ProofGenerationError};
 use thiserror::Error;
 
 #[derive(Debug, Clone, Error)]
@@ -13,3 +13,33 @@ pub enum ScannerError {
     #[error("Failed to construct proof from tiles {0}")]
     TilingError(#[from] TilingError),
 }
+
+impl ScannerError {
+    /// Returns `true`, if the error is likely to go away on its own, e.g. a timeout or rate limit
+    ///
+    /// Such errors say nothing about the behavior of the log.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            ScannerError::ClientError(err) => err.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns `true`, if the log answered, that a leaf is not part of its tree, or returned an
+    /// inclusion proof, that failed validation
+    pub fn is_missing_leaf(&self) -> bool {
+        match self {
+            ScannerError::ClientError(ClientError::AuditProofError(_)) => true,
+            ScannerError::ClientError(ClientError::ResponseError { code, .. }) => {
+                matches!(code, 400 | 404)
+            }
+            ScannerError::TilingError(
+                TilingError::AuditProofError(_)
+                | TilingError::AuditProofGenerationError(ProofGenerationError::InvalidIndex {
+                    ..
+                }),
+            ) => true,
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,640 @@
+//! Self-contained evidence of log misbehaviour, that can be verified offline
+
+use crate::{Scanner, ScannerError, ScannerImpl, SctSource};
+use chrono::{DateTime, Utc};
+use luct_core::{
+    CertificateChain, CertificateError, CtLog, CtLogConfig, CtLogConfigError,
+    SignatureValidationError, SignedCertificateTimestamp, SignedTreeHead,
+    tree::{AuditProof, ConsistencyProof, TreeHead},
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// A failed attempt to obtain an inclusion proof of the SCT
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FailedProof {
+    tree_size: u64,
+    attempted_at: DateTime<Utc>,
+    error: String,
+    /// The proof returned by the log, if it failed validation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    proof: Option<AuditProof>,
+}
+
+impl FailedProof {
+    /// The size of the tree, against which the proof was requested
+    pub fn tree_size(&self) -> u64 {
+        self.tree_size
+    }
+
+    pub fn attempted_at(&self) -> DateTime<Utc> {
+        self.attempted_at
+    }
+
+    pub fn error(&self) -> &str {
+        &self.error
+    }
+
+    /// Returns the proof, that the log returned, if it failed validation
+    ///
+    /// Returns `None`, if the log answered, that it does not know the leaf.
+    pub fn proof(&self) -> Option<&AuditProof> {
+        self.proof.as_ref()
+    }
+}
+
+/// Self-contained evidence, that a log did not include an SCT within its MMD
+///
+/// The bundle contains everything needed to check it offline with [`MmdViolationEvidence::verify`],
+/// such that it can be handed to log operators and root programs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MmdViolationEvidence {
+    /// The PEM encoded certificate chain, that the SCT was issued for
+    chain: String,
+    sct: SignedCertificateTimestamp,
+    source: SctSource,
+    log: CtLogConfig,
+    /// Tree heads signed by the log after the MMD passed
+    sths: Vec<SignedTreeHead>,
+    failed_proofs: Vec<FailedProof>,
+}
+
+impl MmdViolationEvidence {
+    pub fn chain(&self) -> &str {
+        &self.chain
+    }
+
+    pub fn sct(&self) -> &SignedCertificateTimestamp {
+        &self.sct
+    }
+
+    pub fn source(&self) -> SctSource {
+        self.source
+    }
+
+    pub fn log(&self) -> &CtLogConfig {
+        &self.log
+    }
+
+    pub fn sths(&self) -> &[SignedTreeHead] {
+        &self.sths
+    }
+
+    pub fn failed_proofs(&self) -> &[FailedProof] {
+        &self.failed_proofs
+    }
+
+    /// Returns the time, by which the log had to include the SCT
+    pub fn deadline(&self) -> DateTime<Utc> {
+        millis_to_datetime(deadline_millis(&self.sct, &self.log))
+    }
+
+    /// Verify the evidence without network access
+    ///
+    /// Checks, that the log issued the SCT for the certificate, that every tree head is signed by
+    /// the log after the MMD passed and that a proof attempt failed against each of them.
+    /// Proofs returned by the log are validated again, they must fail against the leaf.
+    /// Note, that a proof is not signed by the log.
+    pub fn verify(&self) -> Result<(), EvidenceError> {
+        let chain = CertificateChain::from_pem_chain(&self.chain)?;
+        let log = CtLog::new(self.log.clone())?;
+
+        log.validate_sct(&chain, &self.sct, !self.source.is_final_cert())
+            .map_err(EvidenceError::InvalidSct)?;
+        let leaf = chain
+            .as_leaf(&self.sct, !self.source.is_final_cert())
+            .map_err(CertificateError::from)?;
+
+        if self.sths.is_empty() {
+            return Err(EvidenceError::MissingSth);
+        }
+
+        let deadline = deadline_millis(&self.sct, &self.log);
+        for sth in &self.sths {
+            log.validate_sth(sth)
+                .map_err(|err| EvidenceError::InvalidSth(sth.tree_size(), err))?;
+
+            if sth.timestamp() < deadline {
+                return Err(EvidenceError::SthBeforeDeadline {
+                    tree_size: sth.tree_size(),
+                    timestamp: millis_to_datetime(sth.timestamp()),
+                    deadline: millis_to_datetime(deadline),
+                });
+            }
+
+            let attempts = self
+                .failed_proofs
+                .iter()
+                .filter(|attempt| attempt.tree_size == sth.tree_size())
+                .collect::<Vec<_>>();
+            if attempts.is_empty() {
+                return Err(EvidenceError::MissingProofAttempt(sth.tree_size()));
+            }
+
+            for proof in attempts.iter().filter_map(|attempt| attempt.proof.as_ref()) {
+                if proof.validate(&TreeHead::from(sth), &leaf).is_ok() {
+                    return Err(EvidenceError::LeafIncluded(sth.tree_size()));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// An STH as observed from a [`VantagePoint`](crate::VantagePoint)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SthObservation {
+    vantage_point: String,
+    sth: SignedTreeHead,
+    observed_at: DateTime<Utc>,
+}
+
+impl SthObservation {
+    pub(crate) fn new(
+        vantage_point: &str,
+        sth: SignedTreeHead,
+        observed_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            vantage_point: vantage_point.to_string(),
+            sth,
+            observed_at,
+        }
+    }
+
+    /// Returns the name of the vantage point, through which the STH was observed
+    pub fn vantage_point(&self) -> &str {
+        &self.vantage_point
+    }
+
+    pub fn sth(&self) -> &SignedTreeHead {
+        &self.sth
+    }
+
+    pub fn observed_at(&self) -> DateTime<Utc> {
+        self.observed_at
+    }
+}
+
+/// The way in which two STHs of an [`InconsistencyReport`] contradict each other
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum InconsistencyKind {
+    /// Both STHs have the same tree size but different root hashes
+    ForkedRoot,
+    /// The tree of the smaller STH is not a prefix of the tree of the larger STH
+    ///
+    /// The proof is the one returned by the log, which fails to validate between the two STHs.
+    FailedConsistencyProof {
+        error: String,
+        proof: ConsistencyProof,
+    },
+}
+
+/// Self-contained evidence, that a log presented a split view of its tree
+///
+/// Both STHs are signed by the log, such that a report of a [`InconsistencyKind::ForkedRoot`] can
+/// be verified offline with [`InconsistencyReport::verify`] and handed to log operators and root
+/// programs. A report of a [`InconsistencyKind::FailedConsistencyProof`] can not, since the proof is
+/// not signed by the log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InconsistencyReport {
+    log: CtLogConfig,
+    first: SthObservation,
+    second: SthObservation,
+    #[serde(flatten)]
+    kind: InconsistencyKind,
+}
+
+impl InconsistencyReport {
+    pub(crate) fn new(
+        log: CtLogConfig,
+        first: SthObservation,
+        second: SthObservation,
+        kind: InconsistencyKind,
+    ) -> Self {
+        Self {
+            log,
+            first,
+            second,
+            kind,
+        }
+    }
+
+    pub fn log(&self) -> &CtLogConfig {
+        &self.log
+    }
+
+    pub fn first(&self) -> &SthObservation {
+        &self.first
+    }
+
+    pub fn second(&self) -> &SthObservation {
+        &self.second
+    }
+
+    pub fn kind(&self) -> &InconsistencyKind {
+        &self.kind
+    }
+
+    /// Verify the report without network access
+    ///
+    /// Checks, that both STHs are signed by the log and contradict each other as claimed.
+    /// For a failed consistency proof, the proof returned by the log is validated again.
+    /// Since the proof is not signed by the log, anyone could have produced it, so such a report
+    /// fails with [`EvidenceError::UnverifiableProof`] even if the proof fails again.
+    /// It can only be confirmed by fetching the proof from the log again.
+    pub fn verify(&self) -> Result<(), EvidenceError> {
+        let log = CtLog::new(self.log.clone())?;
+        for sth in [&self.first.sth, &self.second.sth] {
+            log.validate_sth(sth)
+                .map_err(|err| EvidenceError::InvalidSth(sth.tree_size(), err))?;
+        }
+
+        let (first, second) = match self.first.sth.tree_size() <= self.second.sth.tree_size() {
+            true => (&self.first.sth, &self.second.sth),
+            false => (&self.second.sth, &self.first.sth),
+        };
+        let contradicts = match &self.kind {
+            InconsistencyKind::ForkedRoot => {
+                first.tree_size() == second.tree_size() && first.root_hash() != second.root_hash()
+            }
+            InconsistencyKind::FailedConsistencyProof { proof, .. } => {
+                first.tree_size() != second.tree_size()
+                    && proof
+                        .validate(&TreeHead::from(first), &TreeHead::from(second))
+                        .is_err()
+            }
+        };
+        if !contradicts {
+            return Err(EvidenceError::NoInconsistency);
+        }
+
+        match self.kind {
+            InconsistencyKind::ForkedRoot => Ok(()),
+            InconsistencyKind::FailedConsistencyProof { .. } => {
+                Err(EvidenceError::UnverifiableProof)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum EvidenceError {
+    #[error("Invalid certificate chain: {0}")]
+    CertificateError(#[from] CertificateError),
+
+    #[error("Invalid log config: {0}")]
+    InvalidLog(#[from] CtLogConfigError),
+
+    #[error("The SCT was not issued by the log for this certificate: {0}")]
+    InvalidSct(SignatureValidationError),
+
+    #[error("The tree head of size {0} is not signed by the log: {1}")]
+    InvalidSth(u64, SignatureValidationError),
+
+    #[error(
+        "The tree head of size {tree_size} was signed at {timestamp}, before the MMD passed at {deadline}"
+    )]
+    SthBeforeDeadline {
+        tree_size: u64,
+        timestamp: DateTime<Utc>,
+        deadline: DateTime<Utc>,
+    },
+
+    #[error("The evidence contains no tree head signed after the MMD passed")]
+    MissingSth,
+
+    #[error("No failed proof attempt is recorded for the tree head of size {0}")]
+    MissingProofAttempt(u64),
+
+    #[error("A recorded proof shows, that the leaf is included in the tree head of size {0}")]
+    LeafIncluded(u64),
+
+    #[error("The tree heads do not contradict each other")]
+    NoInconsistency,
+
+    #[error(
+        "The consistency proof is not signed by the log, it needs to be fetched from the log again to verify the report"
+    )]
+    UnverifiableProof,
+}
+
+impl<S: ScannerImpl> Scanner<S> {
+    /// Collect [`MmdViolationEvidence`] for an SCT, that was not included after the MMD of its log passed
+    ///
+    /// An inclusion proof is requested against every known tree head of the log, that was signed
+    /// after the MMD passed.
+    /// Only answers of the log, that the leaf is absent, and proofs failing validation are recorded.
+    /// Other failures, such as timeouts, rate limits or server errors, are returned as errors,
+    /// since they are no evidence of a violation.
+    /// Returns `None`, if the MMD has not passed yet, no such tree head is known or the SCT turns
+    /// out to be included after all.
+    pub async fn collect_mmd_evidence(
+        &self,
+        chain: &CertificateChain,
+        sct: &SignedCertificateTimestamp,
+        source: SctSource,
+    ) -> Result<Option<MmdViolationEvidence>, ScannerError> {
+        let Some(log) = self.logs.get(&sct.log_id()) else {
+            return Ok(None);
+        };
+        let config = log.client().log().config();
+
+        let now = (self.time_source)();
+        let deadline = deadline_millis(sct, config);
+        if now < millis_to_datetime(deadline) {
+            return Ok(None);
+        }
+
+        let sths = log.sths_after(deadline).await;
+        if sths.is_empty() {
+            return Ok(None);
+        }
+
+        let leaf = chain
+            .as_leaf(sct, !source.is_final_cert())
+            .map_err(CertificateError::from)?;
+
+        let mut failed_proofs = vec![];
+        for sth in &sths {
+            let sth = sth.inner();
+            let failed_proof = match log.get_audit_proof(sct, sth, &leaf).await {
+                Ok(proof) => match proof.validate(&TreeHead::from(sth), &leaf) {
+                    Ok(()) => return Ok(None),
+                    Err(err) => FailedProof {
+                        tree_size: sth.tree_size(),
+                        attempted_at: now,
+                        error: err.to_string(),
+                        proof: Some(proof),
+                    },
+                },
+                Err(err) if err.is_missing_leaf() => FailedProof {
+                    tree_size: sth.tree_size(),
+                    attempted_at: now,
+                    error: err.to_string(),
+                    proof: None,
+                },
+                Err(err) => return Err(err),
+            };
+            failed_proofs.push(failed_proof);
+        }
+
+        Ok(Some(MmdViolationEvidence {
+            chain: chain.as_pem_chain(),
+            sct: sct.clone(),
+            source,
+            log: config.clone(),
+            sths: sths.into_iter().map(|sth| sth.inner().clone()).collect(),
+            failed_proofs,
+        }))
+    }
+}
+
+/// Returns the time in milliseconds, by which the log has to include the SCT
+fn deadline_millis(sct: &SignedCertificateTimestamp, log: &CtLogConfig) -> u64 {
+    sct.timestamp() + log.mmd() * 1000
+}
+
+fn millis_to_datetime(millis: u64) -> DateTime<Utc> {
+    DateTime::from_timestamp_millis(millis as i64).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{ARGON2025H2_REPLAY, get_scanner};
+    use luct_client::replay::{Fixture, ReplayClient};
+    use luct_core::v1::responses::{GetSthConsistencyResponse, GetSthResponse};
+
+    const CERT_CHAIN_GOOGLE_COM: &str = include_str!("../../testdata/google-chain.pem");
+    const ARGON2025H2: &str = r#"{
+        "description": "Google Argon",
+        "url": "https://ct.googleapis.com/logs/us1/argon2025h2/",
+        "key": "MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEr+TzlCzfpie1/rJhgxnIITojqKk9VK+8MZoc08HjtsLzD8e5yjsdeWVhIiWCVk6Y6KomKTYeKGBv6xVu93zQug==",
+        "mmd": 86400
+    }"#;
+    const ARGON2025H2_STH: &str = r#"{
+        "tree_size": 1329315675,
+        "timestamp": 1751738269891,
+        "sha256_root_hash": "NEFqldTJt2+wE/aaaQuXeADdWVV8IGbwhLublI7QaMY=",
+        "tree_head_signature": "BAMARjBEAiA9rna9/avaKTald7hHrldq8FfB4FDAaNyB44pplv71agIgeD0jj2AhLnvlaWavfFZ3BdUglauz36rFpGLYuLBs/O8="
+    }"#;
+
+    const ARGON2025H1: &str = r#"{
+        "description": "Google Argon",
+        "url": "https://ct.googleapis.com/logs/us1/argon2025h1/",
+        "key": "MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEIIKh+WdoqOTblJji4WiH5AltIDUzODyvFKrXCBjw/Rab0/98J4LUh7dOJEY7+66+yCNSICuqRAX+VPnV8R1Fmg==",
+        "mmd": 86400
+    }"#;
+    const GOOGLE_STH_CONSISTENCY_PROOF: &str =
+        include_str!("../../testdata/sth-consistency-proof.json");
+    const ARGON2025H1_STH2806: &str = r#"{
+        "tree_size": 1425614114,
+        "timestamp": 1751114416696,
+        "sha256_root_hash": "LHtW79pwJohJF5Yn/tyozEroOnho4u3JAGn7WeHSR54=",
+        "tree_head_signature": "BAMARzBFAiEAg4w8LlTFKd3KL6lo5Zde9OupHYNN0DDk8U54PenirI4CIHL8ucpkJw5zFLh8UvLA+Zf+f8Ms+tLsVtzHuqnO0qjm"
+    }"#;
+    const ARGON2025H1_STH2906: &str = r#"{
+        "tree_size": 1425633154,
+        "timestamp": 1751189445313,
+        "sha256_root_hash": "iH90iBSqmtLLTcCwu74RYyJ0rd3oXtLbXlBNqKcJUXA=",
+        "tree_head_signature": "BAMARjBEAiAA/UmelqZIfpd5vBs0CJZGx8kAqUhNppLX/rBVk15DWwIgbyecvj2CUl4YzAEWEoFmUwL9KkrZBZQcQgSNEFDqIgc="
+    }"#;
+
+    fn parse_sth(sth: &str) -> SignedTreeHead {
+        let sth: GetSthResponse = serde_json::from_str(sth).unwrap();
+        SignedTreeHead::V1(sth.try_into().unwrap())
+    }
+
+    fn get_evidence() -> MmdViolationEvidence {
+        let chain = CertificateChain::from_pem_chain(CERT_CHAIN_GOOGLE_COM).unwrap();
+        let sth = parse_sth(ARGON2025H2_STH);
+
+        MmdViolationEvidence {
+            chain: chain.as_pem_chain(),
+            sct: chain.cert().extract_scts().unwrap().remove(0),
+            source: SctSource::Embedded,
+            log: serde_json::from_str(ARGON2025H2).unwrap(),
+            failed_proofs: vec![FailedProof {
+                tree_size: sth.tree_size(),
+                attempted_at: millis_to_datetime(sth.timestamp()),
+                error: "Log returned no proof".to_string(),
+                proof: None,
+            }],
+            sths: vec![sth],
+        }
+    }
+
+    /// Returns the inclusion proof of the SCT of google.com against `ARGON2025H2_STH`
+    fn get_proof() -> AuditProof {
+        let fixture = std::fs::read_to_string(format!(
+            "{ARGON2025H2_REPLAY}/GET_ct_googleapis_com_logs_us1_argon2025h2_ct_v1_get_proof_by_hash_hash_zDC52Nq9h47HGKowLXp5zYOg_gofXGg9CxO6tj6yKtY__tree_si_2e9d289d359866f2.json"
+        ))
+        .unwrap();
+        let fixture: serde_json::Value = serde_json::from_str(&fixture).unwrap();
+        serde_json::from_str(fixture["body"].as_str().unwrap()).unwrap()
+    }
+
+    /// Returns the proof of [`get_proof`] with a different leaf index
+    fn tampered_proof() -> AuditProof {
+        let mut proof = serde_json::to_value(get_proof()).unwrap();
+        proof["leaf_index"] = serde_json::json!(912557349);
+        serde_json::from_value(proof).unwrap()
+    }
+
+    #[test]
+    fn verify_evidence() {
+        let evidence = get_evidence();
+        evidence.verify().unwrap();
+
+        // The bundle survives a roundtrip through its serialization
+        let json = serde_json::to_string(&evidence).unwrap();
+        let parsed: MmdViolationEvidence = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, evidence);
+        parsed.verify().unwrap();
+    }
+
+    #[tokio::test]
+    async fn collect_evidence() {
+        let chain = CertificateChain::from_pem_chain(CERT_CHAIN_GOOGLE_COM).unwrap();
+        let sct = chain.cert().extract_scts().unwrap().remove(0);
+        let now = DateTime::parse_from_rfc3339("2025-07-06T00:00:00Z")
+            .unwrap()
+            .to_utc();
+        let collect = async |response: Option<(u16, &str)>| {
+            let client = ReplayClient::load(ARGON2025H2_REPLAY).unwrap();
+            let client = match response {
+                Some((status, body)) => client.with_fixture(Fixture::get(
+                    "https://ct.googleapis.com/logs/us1/argon2025h2/ct/v1/get-proof-by-hash",
+                    &[
+                        ("hash", "zDC52Nq9h47HGKowLXp5zYOg/gofXGg9CxO6tj6yKtY="),
+                        ("tree_size", "1329315675"),
+                    ],
+                    status,
+                    body,
+                )),
+                None => client,
+            };
+            let scanner = get_scanner(client, move || now);
+            scanner.refresh_all_logs().await.unwrap();
+            scanner
+                .collect_mmd_evidence(&chain, &sct, SctSource::Embedded)
+                .await
+        };
+
+        // The SCT is included in the replayed tree head
+        assert_eq!(collect(None).await.unwrap(), None);
+
+        // The log answered, that it does not know the leaf
+        let evidence = collect(Some((404, ""))).await.unwrap().unwrap();
+        assert_eq!(evidence.failed_proofs().len(), 1);
+        assert_eq!(evidence.failed_proofs()[0].tree_size(), 1329315675);
+        assert_eq!(evidence.failed_proofs()[0].proof(), None);
+        evidence.verify().unwrap();
+
+        // The log returned a proof, that fails validation
+        let proof = serde_json::to_string(&tampered_proof()).unwrap();
+        let evidence = collect(Some((200, &proof))).await.unwrap().unwrap();
+        assert_eq!(evidence.failed_proofs()[0].proof(), Some(&tampered_proof()));
+        evidence.verify().unwrap();
+
+        // Server errors are no evidence
+        let err = collect(Some((503, ""))).await.unwrap_err();
+        assert!(err.is_transient());
+    }
+
+    #[test]
+    fn reject_invalid_evidence() {
+        // The tree head must be signed after the MMD passed
+        let mut evidence = get_evidence();
+        evidence.log = serde_json::from_str(&ARGON2025H2.replace("86400", "8640000")).unwrap();
+        assert!(matches!(
+            evidence.verify(),
+            Err(EvidenceError::SthBeforeDeadline { .. })
+        ));
+
+        let mut evidence = get_evidence();
+        evidence.failed_proofs.clear();
+        assert!(matches!(
+            evidence.verify(),
+            Err(EvidenceError::MissingProofAttempt(1329315675))
+        ));
+
+        let mut evidence = get_evidence();
+        evidence.sths.clear();
+        assert_eq!(evidence.verify(), Err(EvidenceError::MissingSth));
+
+        // A recorded proof must fail against the leaf
+        let mut evidence = get_evidence();
+        evidence.failed_proofs[0].proof = Some(tampered_proof());
+        evidence.verify().unwrap();
+        evidence.failed_proofs[0].proof = Some(get_proof());
+        assert_eq!(
+            evidence.verify(),
+            Err(EvidenceError::LeafIncluded(1329315675))
+        );
+
+        // The SCT must match the certificate
+        let mut evidence = get_evidence();
+        evidence.source = SctSource::Ocsp;
+        assert!(matches!(
+            evidence.verify(),
+            Err(EvidenceError::InvalidSct(_))
+        ));
+    }
+
+    #[test]
+    fn verify_inconsistency_report() {
+        let observe = |vantage_point, sth| {
+            SthObservation::new(
+                vantage_point,
+                parse_sth(sth),
+                DateTime::from_timestamp_millis(1751189445313).unwrap(),
+            )
+        };
+        let report = |proof: &str| {
+            let proof: GetSthConsistencyResponse = serde_json::from_str(proof).unwrap();
+            InconsistencyReport::new(
+                serde_json::from_str(ARGON2025H1).unwrap(),
+                observe("scanner", ARGON2025H1_STH2806),
+                observe("node", ARGON2025H1_STH2906),
+                InconsistencyKind::FailedConsistencyProof {
+                    error: "Failed to validate a consistency path".to_string(),
+                    proof: proof.try_into().unwrap(),
+                },
+            )
+        };
+
+        // The proof does not connect the two tree heads, but it is not signed by the log, so
+        // anyone could have tampered with it
+        let tampered = report(&GOOGLE_STH_CONSISTENCY_PROOF.replace(
+            "/qxhAu1l2bHdO41AWkZ1+D2xn8eqDXFsEZU99tz0Zwg=",
+            "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
+        ));
+        assert_eq!(tampered.verify(), Err(EvidenceError::UnverifiableProof));
+
+        let json = serde_json::to_string(&tampered).unwrap();
+        let parsed: InconsistencyReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, tampered);
+        assert_eq!(parsed.verify(), Err(EvidenceError::UnverifiableProof));
+
+        // The tree heads are consistent, which the genuine proof shows
+        let consistent = report(GOOGLE_STH_CONSISTENCY_PROOF);
+        assert_eq!(consistent.verify(), Err(EvidenceError::NoInconsistency));
+
+        // STHs of different sizes do not have forked roots
+        let mut forked = tampered.clone();
+        forked.kind = InconsistencyKind::ForkedRoot;
+        assert_eq!(forked.verify(), Err(EvidenceError::NoInconsistency));
+
+        // The STHs must be signed by the log
+        let mut forged = tampered.clone();
+        forged.log = serde_json::from_str(ARGON2025H2).unwrap();
+        assert!(matches!(
+            forged.verify(),
+            Err(EvidenceError::InvalidSth(1425614114, _))
+        ));
+    }
+}
@@ -0,0 +1,291 @@
+//! Detection of split views by comparing the STHs of a log as observed from multiple vantage points
+
+use crate::{
+    InconsistencyKind, InconsistencyReport, Scanner, ScannerImpl, SthObservation, log::ScannerLog,
+};
+use futures::future::join_all;
+use luct_client::{CtClient, NodeSthClient};
+use luct_core::{SignedTreeHead, tree::TreeHead};
+
+/// A path, through which the scanner observes the [`SignedTreeHeads`](SignedTreeHead) of logs
+#[derive(Debug, Clone)]
+pub enum VantagePoint<C> {
+    /// Fetch the STHs from the logs through the client, e.g. directly or through an OTLSP proxy
+    Log(C),
+    /// Fetch the STHs, that a `luct-node` has collected
+    Node(NodeSthClient<C>),
+}
+
+impl<S: ScannerImpl> Scanner<S> {
+    /// Add a [`VantagePoint`], from which [`Scanner::gossip_sths`] fetches STHs
+    pub fn add_vantage_point(
+        &mut self,
+        name: &str,
+        vantage_point: VantagePoint<S::Client>,
+    ) -> &mut Self {
+        self.vantage_points.push((name.to_string(), vantage_point));
+        self
+    }
+
+    /// Cross-check the STHs of all logs, as seen from all [`VantagePoints`](VantagePoint)
+    ///
+    /// The STHs are compared against each other and against the latest STH stored by the scanner.
+    /// Returns an [`InconsistencyReport`] for every pair of validly signed STHs, that have the same
+    /// tree size but different roots, or whose trees are not consistent with each other.
+    pub async fn gossip_sths(&self) -> Vec<InconsistencyReport> {
+        let reports = join_all(self.logs.values().map(|log| self.gossip_log_sths(log))).await;
+        reports.into_iter().flatten().collect()
+    }
+
+    async fn gossip_log_sths(&self, log: &ScannerLog<S>) -> Vec<InconsistencyReport> {
+        let ct_log = log.client().log();
+        let now = (self.time_source)();
+
+        let mut observations = vec![];
+        if let Some(sth) = log.get_latest_sth().await {
+            observations.push(SthObservation::new("scanner", sth.inner().clone(), now));
+        }
+
+        let fetched = join_all(self.vantage_points.iter().map(
+            |(name, vantage_point)| async move {
+                let sth = match vantage_point {
+                    VantagePoint::Log(client) => {
+                        let client = CtClient::from_log(ct_log.clone(), client.clone())
+                            .with_witness_policy(self.config.witness_policy.clone());
+                        if ct_log.config().is_tiling() {
+                            client.get_checkpoint().await.map(SignedTreeHead::from)
+                        } else {
+                            client.get_sth().await
+                        }
+                    }
+                    VantagePoint::Node(client) => client.get_sth(ct_log).await,
+                };
+                (name, sth)
+            },
+        ))
+        .await;
+
+        for (name, sth) in fetched {
+            match sth {
+                Ok(sth) => observations.push(SthObservation::new(name, sth, now)),
+                Err(err) => tracing::debug!(
+                    "Failed to fetch STH of log {} from vantage point {}: {}",
+                    ct_log.description(),
+                    name,
+                    err
+                ),
+            }
+        }
+
+        // Identical STHs seen from multiple vantage points need to be checked only once
+        observations.sort_by_key(|observation| {
+            (
+                observation.sth().tree_size(),
+                *observation.sth().root_hash(),
+            )
+        });
+        observations.dedup_by(|a, b| {
+            a.sth().tree_size() == b.sth().tree_size() && a.sth().root_hash() == b.sth().root_hash()
+        });
+
+        let mut reports = vec![];
+        for (idx, first) in observations.iter().enumerate() {
+            for second in &observations[idx + 1..] {
+                if let Some(kind) = self.compare_sths(log, first.sth(), second.sth()).await {
+                    tracing::warn!(
+                        "Log {} presented inconsistent STHs of size {} to {} and of size {} to {}",
+                        ct_log.description(),
+                        first.sth().tree_size(),
+                        first.vantage_point(),
+                        second.sth().tree_size(),
+                        second.vantage_point()
+                    );
+                    reports.push(InconsistencyReport::new(
+                        ct_log.config().clone(),
+                        first.clone(),
+                        second.clone(),
+                        kind,
+                    ));
+                }
+            }
+        }
+
+        reports
+    }
+
+    /// Returns the [`InconsistencyKind`], if `first` and `second` prove a split view of the log
+    ///
+    /// `first` must not be larger than `second`.
+    async fn compare_sths(
+        &self,
+        log: &ScannerLog<S>,
+        first: &SignedTreeHead,
+        second: &SignedTreeHead,
+    ) -> Option<InconsistencyKind> {
+        if first.tree_size() == second.tree_size() {
+            return (first.root_hash() != second.root_hash())
+                .then_some(InconsistencyKind::ForkedRoot);
+        }
+
+        let proof = match log.get_consistency_proof(first, second).await {
+            Ok(proof) => proof,
+            // Failing to fetch the proof is no evidence of a split view
+            Err(err) => {
+                tracing::debug!(
+                    "Failed to fetch consistency proof between STHs of size {} and {}: {}",
+                    first.tree_size(),
+                    second.tree_size(),
+                    err
+                );
+                return None;
+            }
+        };
+
+        match proof.validate(&TreeHead::from(first), &TreeHead::from(second)) {
+            Ok(()) => None,
+            Err(err) => Some(InconsistencyKind::FailedConsistencyProof {
+                error: err.to_string(),
+                proof,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EvidenceError, ScannerConfig, tests::TestScannerImpl};
+    use base64::{Engine, prelude::BASE64_STANDARD};
+    use chrono::DateTime;
+    use luct_client::replay::{Fixture, ReplayClient};
+    use luct_core::{CtLog, Signer, store::MemoryStore};
+    use p256::ecdsa::SigningKey;
+
+    const LOG_URL: &str = "https://ct.luct.dev/test/";
+    const GET_STH_URL: &str = "https://ct.luct.dev/test/ct/v1/get-sth";
+    const GET_STH_CONSISTENCY_URL: &str = "https://ct.luct.dev/test/ct/v1/get-sth-consistency";
+
+    fn get_key() -> SigningKey {
+        SigningKey::from_slice(&[0x42; 32]).unwrap()
+    }
+
+    /// Return a `get-sth` response of the test log, signed with its key
+    fn sign_sth(tree_size: u64, root_hash: [u8; 32]) -> String {
+        let timestamp: u64 = 1767225600000;
+
+        // RFC 6962 3.5 TreeHeadSignature of version v1 and signature type tree_hash
+        let mut tbs = vec![0, 1];
+        tbs.extend_from_slice(&timestamp.to_be_bytes());
+        tbs.extend_from_slice(&tree_size.to_be_bytes());
+        tbs.extend_from_slice(&root_hash);
+
+        // DigitallySigned with SHA-256 and ECDSA
+        let signature = get_key().sign(&tbs);
+        let mut digitally_signed = vec![4, 3];
+        digitally_signed.extend_from_slice(&(signature.len() as u16).to_be_bytes());
+        digitally_signed.extend_from_slice(&signature);
+
+        serde_json::json!({
+            "tree_size": tree_size,
+            "timestamp": timestamp,
+            "sha256_root_hash": BASE64_STANDARD.encode(root_hash),
+            "tree_head_signature": BASE64_STANDARD.encode(digitally_signed),
+        })
+        .to_string()
+    }
+
+    /// A client, through which the log serves the signed tree head `sth`
+    fn serve_sth(sth: &str) -> ReplayClient {
+        ReplayClient::default().with_fixture(Fixture::get(GET_STH_URL, &[], 200, sth))
+    }
+
+    /// Create a [`Scanner`] auditing the test log through `client` and refresh its STH
+    async fn get_scanner(client: ReplayClient) -> Scanner<TestScannerImpl> {
+        let mut scanner = Scanner::new(
+            ScannerConfig::builder().build().unwrap(),
+            MemoryStore::default(),
+            MemoryStore::default(),
+            client,
+            || DateTime::from_timestamp_millis(1767225600000).unwrap(),
+        );
+
+        let config = serde_json::json!({
+            "description": "Test log",
+            "url": LOG_URL,
+            "key": BASE64_STANDARD.encode(get_key().public_key()),
+            "mmd": 86400,
+        });
+        let log = CtLog::new(serde_json::from_value(config).unwrap()).unwrap();
+        scanner.add_log(&log, MemoryStore::default());
+        scanner.refresh_all_logs().await.unwrap();
+        scanner
+    }
+
+    #[tokio::test]
+    async fn report_forked_root() {
+        let sth = sign_sth(10, [1; 32]);
+        let forked = sign_sth(10, [2; 32]);
+
+        let mut scanner = get_scanner(serve_sth(&sth)).await;
+        scanner
+            .add_vantage_point("proxy", VantagePoint::Log(serve_sth(&forked)))
+            .add_vantage_point("node", VantagePoint::Log(serve_sth(&forked)))
+            .add_vantage_point("direct", VantagePoint::Log(serve_sth(&sth)));
+
+        // The forked STH is seen from two vantage points, but reported only once
+        let reports = scanner.gossip_sths().await;
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].kind(), &InconsistencyKind::ForkedRoot);
+        assert_eq!(reports[0].first().sth().root_hash(), &[1; 32]);
+        assert_eq!(reports[0].second().sth().root_hash(), &[2; 32]);
+        reports[0].verify().unwrap();
+    }
+
+    #[tokio::test]
+    async fn report_failed_consistency_proof() {
+        let sth = sign_sth(10, [1; 32]);
+        let larger = sign_sth(20, [2; 32]);
+
+        let proof = Fixture::get(
+            GET_STH_CONSISTENCY_URL,
+            &[("first", "10"), ("second", "20")],
+            200,
+            r#"{"consistency":["AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA="]}"#,
+        );
+        let mut scanner = get_scanner(serve_sth(&sth).with_fixture(proof)).await;
+        scanner.add_vantage_point("proxy", VantagePoint::Log(serve_sth(&larger)));
+
+        let reports = scanner.gossip_sths().await;
+        assert_eq!(reports.len(), 1);
+        assert!(matches!(
+            reports[0].kind(),
+            InconsistencyKind::FailedConsistencyProof { .. }
+        ));
+        assert_eq!(reports[0].first().vantage_point(), "scanner");
+        assert_eq!(reports[0].second().vantage_point(), "proxy");
+        assert_eq!(reports[0].verify(), Err(EvidenceError::UnverifiableProof));
+    }
+
+    #[tokio::test]
+    async fn ignore_fetch_errors() {
+        let sth = sign_sth(10, [1; 32]);
+        let larger = sign_sth(20, [2; 32]);
+
+        // The log fails to serve the consistency proof
+        let proof = Fixture::get(
+            GET_STH_CONSISTENCY_URL,
+            &[("first", "10"), ("second", "20")],
+            503,
+            "",
+        );
+        let unreachable =
+            ReplayClient::default().with_fixture(Fixture::get(GET_STH_URL, &[], 503, ""));
+
+        let mut scanner = get_scanner(serve_sth(&sth).with_fixture(proof)).await;
+        scanner
+            .add_vantage_point("proxy", VantagePoint::Log(serve_sth(&larger)))
+            .add_vantage_point("offline", VantagePoint::Log(unreachable));
+
+        assert!(scanner.gossip_sths().await.is_empty());
+    }
+}
@@ -6,18 +6,31 @@ use crate::log::{ScannerLog, builder::LogImpls};
 use chrono::{DateTime, Utc};
 use futures::future::try_join_all;
 use luct_client::Client;
-use luct_core::{CtLog, Fingerprint, LogId, store::SearchableStore, v1::SignedTreeHead};
+use luct_core::{CtLog, Fingerprint, LogId, SignedTreeHead, store::SearchableStore};
 use std::collections::BTreeMap;
 pub use {
     config::{ScannerConfig, ScannerConfigBuilder},
     error::ScannerError,
-    report::{Report, SctReport, SthReport},
+    evidence::{
+        EvidenceError, FailedProof, InconsistencyKind, InconsistencyReport, MmdViolationEvidence,
+        SthObservation,
+    },
+    gossip::VantagePoint,
+    pending::PendingSct,
+    policy::{
+        ApplePolicy, ChromePolicy, ClauseResult, CtPolicy, PolicyKind, PolicyResult, PolicySct,
+    },
+    report::{Report, SctReport, SctSource, SctTimestampError, SthReport},
     utils::Validated,
 };
 
 mod config;
 mod error;
+mod evidence;
+mod gossip;
 mod log;
+mod pending;
+mod policy;
 mod report;
 mod stats;
 mod sth;
@@ -33,6 +46,8 @@ pub trait ScannerImpl {
     type ReportStore: SearchableStore<Key = Fingerprint, Value = Report>;
     /// The [`Store`](luct_core::store::Store) use to store [`SignedTreeHeads`](SignedTreeHead)
     type SthStore: SearchableStore<Key = u64, Value = Validated<SignedTreeHead>>;
+    /// The [`Store`](luct_core::store::Store) used to queue [`PendingScts`](PendingSct), keyed by the hash of the SCT
+    type PendingStore: SearchableStore<Key = [u8; 32], Value = PendingSct>;
 }
 
 /// The scanner holds the state that is necessary to perform audits as well as the auditing logic
@@ -43,8 +58,11 @@ pub struct Scanner<S: ScannerImpl> {
     config: ScannerConfig,
     logs: BTreeMap<LogId, ScannerLog<S>>,
     report_store: S::ReportStore,
+    pending_store: S::PendingStore,
     client: S::Client,
+    vantage_points: Vec<(String, VantagePoint<S::Client>)>,
     time_source: Box<dyn Fn() -> DateTime<Utc>>,
+    policy: Box<dyn CtPolicy>,
 }
 
 #[allow(clippy::type_complexity)]
@@ -56,24 +74,34 @@ impl<S: ScannerImpl> Scanner<S> {
     pub fn new<F: Fn() -> DateTime<Utc> + 'static>(
         config: ScannerConfig,
         report_store: S::ReportStore,
+        pending_store: S::PendingStore,
         client: S::Client,
         time_source: F,
     ) -> Self {
         Self {
+            policy: config.policy.policy(),
             config,
             logs: BTreeMap::new(),
             report_store,
+            pending_store,
             client,
+            vantage_points: vec![],
             time_source: Box::new(time_source) as _,
         }
     }
 
+    /// Replace the [`CtPolicy`] selected in the [`ScannerConfig`]
+    pub fn set_policy(&mut self, policy: Box<dyn CtPolicy>) -> &mut Self {
+        self.policy = policy;
+        self
+    }
+
     pub fn add_log(&mut self, log: &CtLog, sth_store: S::SthStore) -> &mut Self {
         let impls = LogImpls {
             client: self.client.clone(),
             sth_store,
         };
-        let scanner_log = ScannerLog::new(log, impls);
+        let scanner_log = ScannerLog::new(log, impls, self.config.witness_policy.clone());
         let log_id = scanner_log.client().log().log_id().clone();
 
         self.logs.insert(log_id, scanner_log);
@@ -93,3 +121,59 @@ impl<S: ScannerImpl> Scanner<S> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use luct_client::replay::ReplayClient;
+    use luct_core::{CtLogConfig, store::MemoryStore};
+
+    pub(crate) const CERT_CHAIN_GOOGLE_COM: &str = include_str!("../../testdata/google-chain.pem");
+    pub(crate) const ARGON2025H2: &str = r#"{
+        "description": "Google Argon",
+        "url": "https://ct.googleapis.com/logs/us1/argon2025h2/",
+        "key": "MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEr+TzlCzfpie1/rJhgxnIITojqKk9VK+8MZoc08HjtsLzD8e5yjsdeWVhIiWCVk6Y6KomKTYeKGBv6xVu93zQug==",
+        "mmd": 86400
+    }"#;
+    pub(crate) const ARGON2025H2_REPLAY: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../testdata/replay/argon2025h2"
+    );
+
+    pub(crate) struct TestScannerImpl;
+
+    impl ScannerImpl for TestScannerImpl {
+        type Client = ReplayClient;
+        type ReportStore = MemoryStore<Fingerprint, Report>;
+        type SthStore = MemoryStore<u64, Validated<SignedTreeHead>>;
+        type PendingStore = MemoryStore<[u8; 32], PendingSct>;
+    }
+
+    /// Create a [`Scanner`] auditing the Argon2025h2 log, with a clock returning the time of `now`
+    pub(crate) fn get_scanner<F: Fn() -> DateTime<Utc> + 'static>(
+        client: ReplayClient,
+        now: F,
+    ) -> Scanner<TestScannerImpl> {
+        get_scanner_with_stores(client, now, MemoryStore::default(), MemoryStore::default())
+    }
+
+    /// Like [`get_scanner`], but sharing the report and pending stores with other scanners
+    pub(crate) fn get_scanner_with_stores<F: Fn() -> DateTime<Utc> + 'static>(
+        client: ReplayClient,
+        now: F,
+        report_store: MemoryStore<Fingerprint, Report>,
+        pending_store: MemoryStore<[u8; 32], PendingSct>,
+    ) -> Scanner<TestScannerImpl> {
+        let mut scanner = Scanner::new(
+            ScannerConfig::builder().build().unwrap(),
+            report_store,
+            pending_store,
+            client,
+            now,
+        );
+
+        let config: CtLogConfig = serde_json::from_str(ARGON2025H2).unwrap();
+        scanner.add_log(&CtLog::new(config).unwrap(), MemoryStore::default());
+        scanner
+    }
+}
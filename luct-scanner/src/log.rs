@@ -1,8 +1,9 @@
 use crate::{ScannerError, ScannerImpl, log::tiling::TileFetcher, utils::Validated};
 use luct_client::CtClient;
 use luct_core::{
+    MerkleTreeLeaf, SignedCertificateTimestamp, SignedTreeHead,
     store::{OrderedStoreRead, SearchableStoreRead, StoreWrite},
-    v1::{MerkleTreeLeaf, SignedCertificateTimestamp, SignedTreeHead},
+    tree::{AuditProof, ConsistencyProof},
 };
 use std::{
     fmt::{self, Debug},
@@ -56,11 +57,7 @@ impl<S: ScannerImpl> ScannerLog<S> {
     ) -> Result<u64, ScannerError> {
         match &self.tiles {
             Some(tiles) => Ok(tiles.check_sct_inclusion(sct, sth, leaf).await?),
-            None => Ok(self
-                .log
-                .client
-                .check_sct_inclusion_v1(sct, sth, leaf)
-                .await?),
+            None => Ok(self.log.client.check_sct_inclusion(sct, sth, leaf).await?),
         }
     }
 
@@ -84,15 +81,7 @@ impl<S: ScannerImpl> ScannerLog<S> {
                 old_sth.tree_size()
             );
 
-            match &self.tiles {
-                Some(tiles) => tiles.check_sth_consistency(&old_sth, &new_sth).await?,
-                None => {
-                    self.log
-                        .client
-                        .check_consistency_v1(&old_sth, &new_sth)
-                        .await?
-                }
-            };
+            self.check_sth_consistency(&old_sth, &new_sth).await?;
         };
 
         self.log
@@ -103,6 +92,56 @@ impl<S: ScannerImpl> ScannerLog<S> {
         Ok(new_sth)
     }
 
+    /// Checks, that the tree of `old_sth` is a prefix of the tree of `new_sth`
+    pub(crate) async fn check_sth_consistency(
+        &self,
+        old_sth: &SignedTreeHead,
+        new_sth: &SignedTreeHead,
+    ) -> Result<(), ScannerError> {
+        match &self.tiles {
+            Some(tiles) => tiles.check_sth_consistency(old_sth, new_sth).await?,
+            None => self.log.client.check_consistency(old_sth, new_sth).await?,
+        };
+
+        Ok(())
+    }
+
+    /// Fetches the inclusion proof of `leaf` against `sth` without validating it
+    pub(crate) async fn get_audit_proof(
+        &self,
+        sct: &SignedCertificateTimestamp,
+        sth: &SignedTreeHead,
+        leaf: &MerkleTreeLeaf,
+    ) -> Result<AuditProof, ScannerError> {
+        let proof = match &self.tiles {
+            Some(tiles) => tiles.get_audit_proof(sct, sth).await?,
+            None => self.log.client.fetch_audit_proof(sth, leaf).await?,
+        };
+
+        Ok(proof)
+    }
+
+    /// Fetches the consistency proof from `old_sth` to `new_sth` without validating it
+    ///
+    /// `old_sth` must be the smaller tree head.
+    pub(crate) async fn get_consistency_proof(
+        &self,
+        old_sth: &SignedTreeHead,
+        new_sth: &SignedTreeHead,
+    ) -> Result<ConsistencyProof, ScannerError> {
+        let proof = match &self.tiles {
+            Some(tiles) => tiles.get_consistency_proof(old_sth, new_sth).await?,
+            None => {
+                self.log
+                    .client
+                    .get_consistency_proof(old_sth, new_sth)
+                    .await?
+            }
+        };
+
+        Ok(proof)
+    }
+
     #[tracing::instrument(level = "trace")]
     pub(crate) async fn oldest_viable_sth(
         &self,
@@ -118,12 +157,25 @@ impl<S: ScannerImpl> ScannerLog<S> {
         Some(tree_head.1)
     }
 
+    /// Returns all stored STHs, that were signed at or after `timestamp`
+    pub(crate) async fn sths_after(&self, timestamp: u64) -> Vec<Validated<SignedTreeHead>> {
+        self.log
+            .sth_store
+            .filter(|_, sth| sth.timestamp() >= timestamp)
+            .await
+            .into_iter()
+            .map(|(_, sth)| sth)
+            .collect()
+    }
+
     #[tracing::instrument(level = "trace")]
     async fn fetch_sth(&self) -> Result<Validated<SignedTreeHead>, ScannerError> {
         tracing::debug!("Fetching new STH of log {}", self.log.name);
         match &self.tiles {
-            Some(_) => Ok(Validated::new(self.log.client.get_checkpoint().await?)),
-            None => Ok(Validated::new(self.log.client.get_sth_v1().await?)),
+            Some(_) => Ok(Validated::new(
+                self.log.client.get_checkpoint().await?.into(),
+            )),
+            None => Ok(Validated::new(self.log.client.get_sth().await?)),
         }
     }
 }
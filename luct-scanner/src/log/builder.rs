@@ -3,7 +3,7 @@ use crate::{
     log::{ScannerLog, ScannerLogInner, tiling::TileFetcher},
 };
 use luct_client::CtClient;
-use luct_core::CtLog;
+use luct_core::{CtLog, tiling::WitnessPolicy};
 use std::sync::Arc;
 
 #[derive(Debug, Clone)]
@@ -13,8 +13,9 @@ pub(crate) struct LogImpls<S: ScannerImpl> {
 }
 
 impl<S: ScannerImpl> ScannerLog<S> {
-    pub fn new(log: &CtLog, impls: LogImpls<S>) -> Self {
-        let client = CtClient::new(log.config().clone(), impls.client);
+    pub fn new(log: &CtLog, impls: LogImpls<S>, witness_policy: Option<WitnessPolicy>) -> Self {
+        let client =
+            CtClient::from_log(log.clone(), impls.client).with_witness_policy(witness_policy);
 
         let log = Arc::new(ScannerLogInner::<S> {
             name: log.description().to_owned(),
@@ -1,10 +1,10 @@
 use crate::{ScannerImpl, log::ScannerLogInner};
 use luct_client::TileFetchStore;
 use luct_core::{
+    MerkleTreeLeaf, SignedCertificateTimestamp, SignedTreeHead,
     store::MemoryStore,
     tiling::TilingError,
-    tree::{ProofValidationError, Tree, TreeHead},
-    v1::{MerkleTreeLeaf, SignedCertificateTimestamp, SignedTreeHead},
+    tree::{AuditProof, ConsistencyProof, ProofValidationError, Tree, TreeHead},
 };
 use luct_store::LruCacheStore;
 use std::{
@@ -44,6 +44,21 @@ impl<S: ScannerImpl> TileFetcher<S> {
         sth: &SignedTreeHead,
         leaf: &MerkleTreeLeaf,
     ) -> Result<u64, TilingError> {
+        let audit_proof = self.get_audit_proof(sct, sth).await?;
+
+        audit_proof
+            .validate(&TreeHead::from(sth), leaf)
+            .map_err(TilingError::AuditProofError)?;
+
+        Ok(audit_proof.index())
+    }
+
+    /// Generate the inclusion proof of the leaf of `sct` out of the tiles without validating it
+    pub(crate) async fn get_audit_proof(
+        &self,
+        sct: &SignedCertificateTimestamp,
+        sth: &SignedTreeHead,
+    ) -> Result<AuditProof, TilingError> {
         let Some(leaf_index) = sct.leaf_index() else {
             return Err(TilingError::LeafIndexMissing);
         };
@@ -59,17 +74,10 @@ impl<S: ScannerImpl> TileFetcher<S> {
         // Need to set the sth correctly for the async proof to work
         self.0.nodes().set_tree_size(tree_head.tree_size());
 
-        let audit_proof = self
-            .0
-            .get_audit_proof(&tree_head, *leaf_index)
+        self.0
+            .get_audit_proof(&tree_head, leaf_index)
             .await
-            .map_err(TilingError::AuditProofGenerationError)?;
-
-        audit_proof
-            .validate(&tree_head, leaf)
-            .map_err(TilingError::AuditProofError)?;
-
-        Ok(audit_proof.index())
+            .map_err(TilingError::AuditProofGenerationError)
     }
 
     pub(crate) async fn check_sth_consistency(
@@ -88,7 +96,7 @@ impl<S: ScannerImpl> TileFetcher<S> {
         }
 
         if old_sth.tree_size() == new_sth.tree_size() {
-            if old_sth.sha256_root_hash() == new_sth.sha256_root_hash() {
+            if old_sth.root_hash() == new_sth.root_hash() {
                 return Ok(());
             } else {
                 return Err(TilingError::ConsistencyProofError(
@@ -97,6 +105,21 @@ impl<S: ScannerImpl> TileFetcher<S> {
             }
         }
 
+        let consistency_proof = self.get_consistency_proof(old_sth, new_sth).await?;
+
+        consistency_proof
+            .validate(&TreeHead::from(old_sth), &TreeHead::from(new_sth))
+            .map_err(TilingError::ConsistencyProofError)?;
+
+        Ok(())
+    }
+
+    /// Generate the consistency proof from `old_sth` to `new_sth` out of the tiles without validating it
+    pub(crate) async fn get_consistency_proof(
+        &self,
+        old_sth: &SignedTreeHead,
+        new_sth: &SignedTreeHead,
+    ) -> Result<ConsistencyProof, TilingError> {
         let old_tree_head = TreeHead::from(old_sth);
         let new_tree_head = TreeHead::from(new_sth);
 
@@ -109,16 +132,9 @@ impl<S: ScannerImpl> TileFetcher<S> {
         // Need to set the sth correctly for the async proof to work
         self.0.nodes().set_tree_size(new_tree_head.tree_size());
 
-        let consistency_proof = self
-            .0
+        self.0
             .get_consistency_proof(&old_tree_head, &new_tree_head)
             .await
-            .map_err(TilingError::ConsistencyProofGenerationError)?;
-
-        consistency_proof
-            .validate(&old_tree_head, &new_tree_head)
-            .map_err(TilingError::ConsistencyProofError)?;
-
-        Ok(())
+            .map_err(TilingError::ConsistencyProofGenerationError)
     }
 }
@@ -0,0 +1,323 @@
+//! Deferred inclusion checks of SCTs, that are younger than the maximum merge delay of their log
+
+use crate::{Report, Scanner, ScannerImpl, SctSource};
+use chrono::{DateTime, Utc};
+use luct_core::{
+    CertificateChain, SignedCertificateTimestamp,
+    store::{Hashable, SearchableStoreRead, StoreRead, StoreWrite},
+};
+use luct_store::StringStoreValue;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, sync::Arc};
+
+/// An SCT, whose inclusion can not be verified before the MMD of its log has passed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingSct {
+    /// The PEM encoded certificate chain, that the SCT was issued for
+    chain: String,
+    sct: SignedCertificateTimestamp,
+    source: SctSource,
+    deadline: DateTime<Utc>,
+}
+
+impl PendingSct {
+    pub fn sct(&self) -> &SignedCertificateTimestamp {
+        &self.sct
+    }
+
+    pub fn source(&self) -> SctSource {
+        self.source
+    }
+
+    /// Returns the time, by which the log must have included the SCT
+    pub fn deadline(&self) -> DateTime<Utc> {
+        self.deadline
+    }
+}
+
+impl StringStoreValue for PendingSct {
+    fn serialize_value(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    fn deserialize_value(value: &str) -> Option<Self> {
+        serde_json::from_str(value).ok()
+    }
+}
+
+impl<S: ScannerImpl> Scanner<S> {
+    /// Re-check the inclusion of all pending SCTs, whose MMD has passed
+    ///
+    /// The cached [`Reports`](Report) of the affected certificates are updated and returned.
+    /// Reports of certificates, whose SCTs were not included in time, stay in the report store,
+    /// such that the violation is shown on the next visit.
+    /// SCTs, whose log could not be reached, stay queued.
+    pub async fn recheck_pending_scts(&self) -> Vec<Report> {
+        let now = (self.time_source)();
+
+        // Group the due SCTs by their certificate, such that each report is updated once
+        let mut due = BTreeMap::<String, Vec<([u8; 32], PendingSct)>>::new();
+        for (key, pending) in self
+            .pending_store
+            .filter(|_, pending| pending.deadline <= now)
+            .await
+        {
+            due.entry(pending.chain.clone())
+                .or_default()
+                .push((key, pending));
+        }
+
+        let mut reports = vec![];
+        for (chain, pending) in due {
+            let chain = CertificateChain::from_pem_chain(&chain).ok().map(Arc::new);
+            let report = match &chain {
+                Some(chain) => {
+                    self.report_store
+                        .get(chain.cert().fingerprint_sha256())
+                        .await
+                }
+                None => None,
+            };
+
+            // If the report has been evicted, the SCTs are checked on the next visit
+            let (Some(chain), Some(mut report)) = (chain, report) else {
+                for (key, _) in pending {
+                    self.pending_store.delete(key).await;
+                }
+                continue;
+            };
+
+            for (key, pending) in pending {
+                // Boxed, since the nested future otherwise exceeds the recursion limit of the compiler
+                let sct_report =
+                    Box::pin(self.collect_sct_report(pending.sct.clone(), pending.source, &chain))
+                        .await;
+
+                // Keep the SCT queued and its report untouched, if the log could not be reached
+                if sct_report.signature_validation_time.is_some() && sct_report.latest_sth.is_none()
+                {
+                    continue;
+                }
+
+                // Transient failures queue the SCT again
+                if sct_report.pending_until.is_none() {
+                    self.pending_store.delete(key).await;
+                }
+
+                match report
+                    .scts
+                    .iter_mut()
+                    .find(|report| report.is_for(&pending.sct, pending.source))
+                {
+                    Some(report) => *report = sct_report,
+                    None => report.scts.push(sct_report),
+                }
+            }
+
+            let report = self.evaluate_policy(report, now);
+            self.report_store
+                .insert(chain.cert().fingerprint_sha256(), report.clone())
+                .await;
+            reports.push(report);
+        }
+
+        reports
+    }
+
+    /// Queue an SCT to be re-checked once the MMD of its log has passed
+    pub(crate) async fn defer_sct(
+        &self,
+        sct: SignedCertificateTimestamp,
+        source: SctSource,
+        chain: &CertificateChain,
+        deadline: DateTime<Utc>,
+    ) {
+        tracing::debug!(
+            "Deferring inclusion check of SCT from log {} until {}",
+            sct.log_id(),
+            deadline
+        );
+
+        let key = sct.hash();
+        let pending = PendingSct {
+            chain: chain.as_pem_chain(),
+            sct,
+            source,
+            deadline,
+        };
+        self.pending_store.insert(key, pending).await;
+    }
+
+    /// Remove an SCT from the queue of pending SCTs
+    pub(crate) async fn resolve_sct(&self, sct: &SignedCertificateTimestamp) {
+        self.pending_store.delete(sct.hash()).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{
+        ARGON2025H2_REPLAY, CERT_CHAIN_GOOGLE_COM, TestScannerImpl, get_scanner_with_stores,
+    };
+    use luct_client::replay::{Fixture, ReplayClient};
+    use luct_core::{Fingerprint, store::MemoryStore};
+    use std::{cell::Cell, rc::Rc};
+
+    const PROOF_URL: &str =
+        "https://ct.googleapis.com/logs/us1/argon2025h2/ct/v1/get-proof-by-hash";
+    const PROOF_PARAMS: [(&str, &str); 2] = [
+        ("hash", "zDC52Nq9h47HGKowLXp5zYOg/gofXGg9CxO6tj6yKtY="),
+        ("tree_size", "1329315675"),
+    ];
+
+    fn date(date: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(date).unwrap().to_utc()
+    }
+
+    /// A client, to which Argon2025h2 responds with `status` instead of the inclusion proof
+    fn client_without_proof(status: u16) -> ReplayClient {
+        ReplayClient::load(ARGON2025H2_REPLAY)
+            .unwrap()
+            .with_fixture(Fixture::get(PROOF_URL, &PROOF_PARAMS, status, ""))
+    }
+
+    struct TestStores {
+        now: Rc<Cell<DateTime<Utc>>>,
+        report_store: MemoryStore<Fingerprint, Report>,
+        pending_store: MemoryStore<[u8; 32], PendingSct>,
+        fingerprint: Fingerprint,
+    }
+
+    impl TestStores {
+        fn scanner(&self, client: ReplayClient) -> Scanner<TestScannerImpl> {
+            let now = self.now.clone();
+            get_scanner_with_stores(
+                client,
+                move || now.get(),
+                self.report_store.clone(),
+                self.pending_store.clone(),
+            )
+        }
+    }
+
+    /// Scan google.com before the MMD of the Argon2025h2 SCT passed, while the log has not included it
+    async fn defer_sct() -> TestStores {
+        let stores = TestStores {
+            now: Rc::new(Cell::new(date("2025-06-02T12:00:00Z"))),
+            report_store: MemoryStore::default(),
+            pending_store: MemoryStore::default(),
+            fingerprint: CertificateChain::from_pem_chain(CERT_CHAIN_GOOGLE_COM)
+                .unwrap()
+                .cert()
+                .fingerprint_sha256(),
+        };
+
+        let report = stores
+            .scanner(client_without_proof(404))
+            .collect_report_pem(CERT_CHAIN_GOOGLE_COM)
+            .await
+            .unwrap();
+        assert_eq!(
+            report.scts[0].pending_until,
+            Some(date("2025-06-03T09:35:33.195Z"))
+        );
+        assert_eq!(report.scts[0].error_description, None);
+        assert_eq!(stores.pending_store.len().await, 1);
+
+        // The second log of the certificate is unknown to the scanner, so the report fails the
+        // policy and is not cached. Cache it, as if the second SCT had been audited.
+        stores
+            .report_store
+            .insert(stores.fingerprint.clone(), report)
+            .await;
+
+        // Nothing is due before the deadline
+        let scanner = stores.scanner(client_without_proof(404));
+        assert!(scanner.recheck_pending_scts().await.is_empty());
+        assert_eq!(stores.pending_store.len().await, 1);
+
+        stores.now.set(date("2025-06-04T00:00:00Z"));
+        stores
+    }
+
+    #[tokio::test]
+    async fn recheck_included_sct() {
+        let stores = defer_sct().await;
+
+        let scanner = stores.scanner(ReplayClient::load(ARGON2025H2_REPLAY).unwrap());
+        let reports = scanner.recheck_pending_scts().await;
+        assert_eq!(reports.len(), 1);
+
+        let argon = &reports[0].scts[0];
+        assert_eq!(argon.pending_until, None);
+        assert_eq!(argon.error_description, None);
+        assert_eq!(argon.index, Some(912557348));
+        assert_eq!(stores.pending_store.len().await, 0);
+        assert_eq!(
+            stores.report_store.get(stores.fingerprint.clone()).await,
+            Some(reports[0].clone())
+        );
+    }
+
+    #[tokio::test]
+    async fn recheck_violating_sct() {
+        let stores = defer_sct().await;
+
+        // A transient failure of the log keeps the SCT queued
+        let scanner = stores.scanner(client_without_proof(503));
+        let reports = scanner.recheck_pending_scts().await;
+        assert_eq!(reports[0].scts[0].error_description, None);
+        assert!(reports[0].scts[0].pending_until.is_some());
+        assert_eq!(stores.pending_store.len().await, 1);
+
+        let scanner = stores.scanner(client_without_proof(404));
+        let reports = scanner.recheck_pending_scts().await;
+        assert_eq!(reports.len(), 1);
+
+        let argon = &reports[0].scts[0];
+        assert_eq!(argon.pending_until, None);
+        assert!(
+            argon
+                .error_description
+                .as_ref()
+                .unwrap()
+                .starts_with("SCT was not included after the MMD of the log passed")
+        );
+        assert_eq!(stores.pending_store.len().await, 0);
+
+        // The failing report is kept, such that the violation is shown on the next visit
+        let cached = stores
+            .report_store
+            .get(stores.fingerprint.clone())
+            .await
+            .unwrap();
+        assert_eq!(cached, reports[0]);
+        assert!(cached.get_error().is_some());
+
+        let report = stores
+            .scanner(client_without_proof(404))
+            .collect_report_pem(CERT_CHAIN_GOOGLE_COM)
+            .await
+            .unwrap();
+        assert_eq!(report.scts[0].error_description, argon.error_description);
+    }
+
+    #[test]
+    fn pending_sct_roundtrip() {
+        let chain = CertificateChain::from_pem_chain(CERT_CHAIN_GOOGLE_COM).unwrap();
+        let sct = chain.cert().extract_scts().unwrap().remove(0);
+
+        let pending = PendingSct {
+            chain: chain.as_pem_chain(),
+            sct,
+            source: SctSource::Embedded,
+            deadline: DateTime::parse_from_rfc3339("2026-01-02T00:00:00Z")
+                .unwrap()
+                .to_utc(),
+        };
+
+        let value = pending.serialize_value();
+        assert_eq!(PendingSct::deserialize_value(&value), Some(pending));
+    }
+}
@@ -0,0 +1,482 @@
+//! Certificate transparency policies, that decide whether a certificate has been logged sufficiently
+
+use chrono::{DateTime, TimeDelta, Utc};
+use luct_core::{LogId, log_list::LogState};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeSet, fmt::Debug};
+
+/// A policy, that a certificate must comply with to be considered sufficiently logged
+///
+/// The policy is evaluated over the SCTs of a certificate, whose signatures have been validated.
+pub trait CtPolicy: Debug {
+    /// The name of the policy, as recorded in the [`PolicyResult`]
+    fn name(&self) -> &'static str;
+
+    /// Evaluate the policy for a certificate valid from `not_before` to `not_after`
+    ///
+    /// The `scts` are either all embedded into the certificate, or all delivered via the TLS
+    /// extension or a stapled OCSP response, as indicated by `embedded`.
+    fn evaluate(
+        &self,
+        not_before: DateTime<Utc>,
+        not_after: DateTime<Utc>,
+        scts: &[PolicySct],
+        embedded: bool,
+    ) -> PolicyResult;
+}
+
+/// Selects one of the built-in [`CtPolicies`](CtPolicy)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyKind {
+    /// See [`ChromePolicy`]
+    #[default]
+    Chrome,
+    /// See [`ApplePolicy`]
+    Apple,
+}
+
+impl PolicyKind {
+    pub(crate) fn policy(&self) -> Box<dyn CtPolicy> {
+        match self {
+            PolicyKind::Chrome => Box::new(ChromePolicy),
+            PolicyKind::Apple => Box::new(ApplePolicy),
+        }
+    }
+}
+
+/// An SCT with a validated signature, together with the information about its log
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicySct {
+    pub(crate) log_id: LogId,
+    pub(crate) operator: Option<String>,
+    pub(crate) rfc6962: bool,
+    pub(crate) timestamp: Option<DateTime<Utc>>,
+    pub(crate) state: Option<LogState>,
+}
+
+impl PolicySct {
+    pub fn log_id(&self) -> &LogId {
+        &self.log_id
+    }
+
+    /// The name of the operator of the log, if known
+    pub fn operator(&self) -> Option<&str> {
+        self.operator.as_deref()
+    }
+
+    /// Returns `true`, if the log implements RFC 6962 rather than the static-ct-api
+    pub fn is_rfc6962(&self) -> bool {
+        self.rfc6962
+    }
+
+    /// The time at which the SCT was issued
+    pub fn timestamp(&self) -> Option<DateTime<Utc>> {
+        self.timestamp
+    }
+
+    /// The current [`LogState`] of the log
+    ///
+    /// Logs that are not part of a log list have no state and are treated as usable.
+    pub fn state(&self) -> Option<&LogState> {
+        self.state.as_ref()
+    }
+
+    /// Returns `true`, if the log is currently qualified, usable or read-only
+    pub fn is_currently_approved(&self) -> bool {
+        match &self.state {
+            None => true,
+            Some(LogState::Qualified { timestamp }) => {
+                self.timestamp.is_none_or(|issued| issued >= *timestamp)
+            }
+            Some(LogState::Usable { .. } | LogState::Readonly { .. }) => true,
+            Some(
+                LogState::Pending { .. } | LogState::Retired { .. } | LogState::Rejected { .. },
+            ) => false,
+        }
+    }
+
+    /// Returns `true`, if the log is currently approved, or was approved when the SCT was issued
+    pub fn is_once_or_currently_approved(&self) -> bool {
+        match &self.state {
+            Some(LogState::Retired { timestamp }) => {
+                self.timestamp.is_some_and(|issued| issued < *timestamp)
+            }
+            _ => self.is_currently_approved(),
+        }
+    }
+}
+
+/// The result of evaluating a [`CtPolicy`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyResult {
+    pub(crate) policy: String,
+    pub(crate) clauses: Vec<ClauseResult>,
+}
+
+/// The result of a single clause of a [`CtPolicy`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClauseResult {
+    pub(crate) clause: String,
+    pub(crate) passed: bool,
+    pub(crate) detail: String,
+}
+
+impl PolicyResult {
+    pub fn new(policy: &str) -> Self {
+        Self {
+            policy: policy.to_string(),
+            clauses: vec![],
+        }
+    }
+
+    /// Record the result of a clause
+    pub fn clause(mut self, clause: &str, passed: bool, detail: String) -> Self {
+        self.clauses.push(ClauseResult {
+            clause: clause.to_string(),
+            passed,
+            detail,
+        });
+        self
+    }
+
+    pub fn policy(&self) -> &str {
+        &self.policy
+    }
+
+    pub fn clauses(&self) -> &[ClauseResult] {
+        &self.clauses
+    }
+
+    /// Returns `true`, if all clauses passed
+    pub fn passed(&self) -> bool {
+        self.clauses.iter().all(|clause| clause.passed)
+    }
+
+    /// Returns the first clause that failed, if any
+    pub fn failed_clause(&self) -> Option<&ClauseResult> {
+        self.clauses.iter().find(|clause| !clause.passed)
+    }
+}
+
+impl ClauseResult {
+    pub fn clause(&self) -> &str {
+        &self.clause
+    }
+
+    pub fn passed(&self) -> bool {
+        self.passed
+    }
+
+    pub fn detail(&self) -> &str {
+        &self.detail
+    }
+}
+
+/// The number of SCTs from distinct logs, that a certificate with the given lifetime requires
+///
+/// Only embedded SCTs depend on the lifetime, delivered SCTs always require 2 logs.
+pub(crate) fn required_scts(
+    not_before: DateTime<Utc>,
+    not_after: DateTime<Utc>,
+    embedded: bool,
+) -> usize {
+    match not_after - not_before {
+        lifetime if embedded && lifetime > TimeDelta::days(180) => 3,
+        _ => 2,
+    }
+}
+
+/// The Chrome CT policy
+///
+/// Requires embedded SCTs from 2 distinct logs for certificates valid for at most 180 days and
+/// from 3 logs otherwise. These logs must be once or currently approved and operated by at least
+/// 2 distinct operators. At least one SCT must come from a currently approved log and one from an
+/// RFC 6962 log.
+/// SCTs delivered via TLS or OCSP require 2 currently approved logs regardless of the lifetime.
+///
+/// See https://googlechrome.github.io/CertificateTransparency/ct_policy.html
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ChromePolicy;
+
+impl CtPolicy for ChromePolicy {
+    fn name(&self) -> &'static str {
+        "chrome"
+    }
+
+    fn evaluate(
+        &self,
+        not_before: DateTime<Utc>,
+        not_after: DateTime<Utc>,
+        scts: &[PolicySct],
+        embedded: bool,
+    ) -> PolicyResult {
+        let approved = match embedded {
+            true => distinct_logs(scts, PolicySct::is_once_or_currently_approved),
+            false => distinct_logs(scts, PolicySct::is_currently_approved),
+        };
+        let result = PolicyResult::new(self.name());
+        let result = currently_approved_clause(result, &approved);
+        let result = log_count_clause(
+            result,
+            &approved,
+            required_scts(not_before, not_after, embedded),
+        );
+        let result = operator_clause(result, &approved);
+
+        let rfc6962 = approved.iter().filter(|sct| sct.rfc6962).count();
+        result.clause(
+            "rfc6962_log",
+            rfc6962 >= 1,
+            format!("{rfc6962} SCTs from RFC 6962 logs, expected at least 1"),
+        )
+    }
+}
+
+/// The Apple CT policy
+///
+/// Requires embedded SCTs from 2 distinct logs for certificates valid for at most 180 days and
+/// from 3 logs otherwise. These logs must be once or currently approved and operated by at least
+/// 2 distinct operators. At least one SCT must come from a currently approved log.
+/// SCTs delivered via TLS or OCSP require 2 currently approved logs regardless of the lifetime.
+///
+/// See https://support.apple.com/en-us/103214
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ApplePolicy;
+
+impl CtPolicy for ApplePolicy {
+    fn name(&self) -> &'static str {
+        "apple"
+    }
+
+    fn evaluate(
+        &self,
+        not_before: DateTime<Utc>,
+        not_after: DateTime<Utc>,
+        scts: &[PolicySct],
+        embedded: bool,
+    ) -> PolicyResult {
+        let approved = match embedded {
+            true => distinct_logs(scts, PolicySct::is_once_or_currently_approved),
+            false => distinct_logs(scts, PolicySct::is_currently_approved),
+        };
+        let result = PolicyResult::new(self.name());
+        let result = currently_approved_clause(result, &approved);
+        let result = log_count_clause(
+            result,
+            &approved,
+            required_scts(not_before, not_after, embedded),
+        );
+        operator_clause(result, &approved)
+    }
+}
+
+/// Returns the SCTs matching the `filter`, keeping only the first SCT of each log
+fn distinct_logs(scts: &[PolicySct], filter: impl Fn(&PolicySct) -> bool) -> Vec<&PolicySct> {
+    let mut seen = BTreeSet::new();
+    scts.iter()
+        .filter(|sct| filter(sct))
+        .filter(|sct| seen.insert(&sct.log_id))
+        .collect()
+}
+
+fn currently_approved_clause(result: PolicyResult, scts: &[&PolicySct]) -> PolicyResult {
+    let approved = scts
+        .iter()
+        .filter(|sct| sct.is_currently_approved())
+        .count();
+    result.clause(
+        "currently_approved_log",
+        approved >= 1,
+        format!("{approved} SCTs from currently approved logs, expected at least 1"),
+    )
+}
+
+fn log_count_clause(result: PolicyResult, scts: &[&PolicySct], expected: usize) -> PolicyResult {
+    result.clause(
+        "distinct_logs",
+        scts.len() >= expected,
+        format!(
+            "{} SCTs from distinct approved logs, expected at least {expected}",
+            scts.len()
+        ),
+    )
+}
+
+fn operator_clause(result: PolicyResult, scts: &[&PolicySct]) -> PolicyResult {
+    // Logs of unknown operators can not show operator diversity
+    let operators = scts
+        .iter()
+        .filter_map(|sct| sct.operator.as_deref())
+        .collect::<BTreeSet<_>>()
+        .len();
+    result.clause(
+        "distinct_operators",
+        operators >= 2,
+        format!("SCTs from {operators} distinct operators, expected at least 2"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOT_BEFORE: &str = "2026-01-01T00:00:00Z";
+
+    fn date(date: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(date).unwrap().to_utc()
+    }
+
+    fn get_sct(log: u8, operator: &str, state: LogState) -> PolicySct {
+        PolicySct {
+            log_id: LogId::try_from(format!("1.3.6.1.4.1.{log}").as_str()).unwrap(),
+            operator: Some(operator.to_string()),
+            rfc6962: true,
+            timestamp: Some(date(NOT_BEFORE)),
+            state: Some(state),
+        }
+    }
+
+    fn usable() -> LogState {
+        LogState::Usable {
+            timestamp: date("2025-01-01T00:00:00Z"),
+        }
+    }
+
+    fn failed(result: &PolicyResult) -> Vec<&str> {
+        result
+            .clauses()
+            .iter()
+            .filter(|clause| !clause.passed())
+            .map(|clause| clause.clause())
+            .collect()
+    }
+
+    #[test]
+    fn chrome_policy() {
+        let not_after = date("2026-03-01T00:00:00Z");
+        let scts = vec![
+            get_sct(1, "Google", usable()),
+            get_sct(2, "Sectigo", usable()),
+        ];
+
+        let result = ChromePolicy.evaluate(date(NOT_BEFORE), not_after, &scts, true);
+        assert!(result.passed(), "{result:?}");
+        assert_eq!(result.policy(), "chrome");
+
+        // A longer lived certificate requires a third SCT
+        let not_after = date("2027-01-01T00:00:00Z");
+        let result = ChromePolicy.evaluate(date(NOT_BEFORE), not_after, &scts, true);
+        assert_eq!(failed(&result), vec!["distinct_logs"]);
+
+        // Two SCTs from the same log only count once
+        let scts = [scts, vec![get_sct(2, "Sectigo", usable())]].concat();
+        let result = ChromePolicy.evaluate(date(NOT_BEFORE), not_after, &scts, true);
+        assert_eq!(failed(&result), vec!["distinct_logs"]);
+    }
+
+    #[test]
+    fn delivered_scts() {
+        let not_after = date("2027-01-01T00:00:00Z");
+        let scts = vec![
+            get_sct(1, "Google", usable()),
+            get_sct(2, "Sectigo", usable()),
+        ];
+
+        // Delivered SCTs require 2 logs regardless of the lifetime
+        let result = ChromePolicy.evaluate(date(NOT_BEFORE), not_after, &scts, false);
+        assert!(result.passed(), "{result:?}");
+        let result = ApplePolicy.evaluate(date(NOT_BEFORE), not_after, &scts, false);
+        assert!(result.passed(), "{result:?}");
+
+        // Delivered SCTs are only accepted from currently approved logs
+        let retired = LogState::Retired {
+            timestamp: date("2026-02-01T00:00:00Z"),
+        };
+        let scts = [scts, vec![get_sct(3, "Cloudflare", retired)]].concat();
+        let result = ChromePolicy.evaluate(date(NOT_BEFORE), not_after, &scts, true);
+        assert!(result.passed(), "{result:?}");
+
+        let mut scts = scts;
+        scts[1].state = scts[2].state.clone();
+        let result = ChromePolicy.evaluate(date(NOT_BEFORE), not_after, &scts, false);
+        assert_eq!(failed(&result), vec!["distinct_logs", "distinct_operators"]);
+        let result = ApplePolicy.evaluate(date(NOT_BEFORE), not_after, &scts, false);
+        assert_eq!(failed(&result), vec!["distinct_logs", "distinct_operators"]);
+    }
+
+    #[test]
+    fn require_operator_diversity() {
+        let not_after = date("2026-03-01T00:00:00Z");
+        let scts = vec![
+            get_sct(1, "Google", usable()),
+            get_sct(2, "Google", usable()),
+        ];
+
+        let result = ChromePolicy.evaluate(date(NOT_BEFORE), not_after, &scts, true);
+        assert_eq!(failed(&result), vec!["distinct_operators"]);
+
+        // Unknown operators do not count as distinct
+        let mut scts = scts;
+        scts[1].operator = None;
+        scts[1].state = None;
+        let result = ApplePolicy.evaluate(date(NOT_BEFORE), not_after, &scts, true);
+        assert_eq!(failed(&result), vec!["distinct_operators"]);
+    }
+
+    #[test]
+    fn require_rfc6962_log() {
+        let not_after = date("2026-03-01T00:00:00Z");
+        let mut scts = vec![
+            get_sct(1, "Google", usable()),
+            get_sct(2, "Sectigo", usable()),
+        ];
+        scts.iter_mut().for_each(|sct| sct.rfc6962 = false);
+
+        let result = ChromePolicy.evaluate(date(NOT_BEFORE), not_after, &scts, true);
+        assert_eq!(failed(&result), vec!["rfc6962_log"]);
+
+        // Apple does not require RFC 6962 logs
+        let result = ApplePolicy.evaluate(date(NOT_BEFORE), not_after, &scts, true);
+        assert!(result.passed(), "{result:?}");
+    }
+
+    #[test]
+    fn enforce_log_state_at_issuance() {
+        let not_after = date("2026-03-01T00:00:00Z");
+
+        // SCTs issued before the log was retired still count, but not as currently approved
+        let retired = LogState::Retired {
+            timestamp: date("2026-02-01T00:00:00Z"),
+        };
+        let scts = vec![
+            get_sct(1, "Google", retired.clone()),
+            get_sct(2, "Sectigo", retired.clone()),
+        ];
+        let result = ChromePolicy.evaluate(date(NOT_BEFORE), not_after, &scts, true);
+        assert_eq!(failed(&result), vec!["currently_approved_log"]);
+
+        let scts = [scts, vec![get_sct(3, "Cloudflare", usable())]].concat();
+        let result = ChromePolicy.evaluate(date(NOT_BEFORE), not_after, &scts, true);
+        assert!(result.passed(), "{result:?}");
+
+        // SCTs issued after retirement, before qualification or by pending logs do not count
+        let retired = LogState::Retired {
+            timestamp: date("2025-12-01T00:00:00Z"),
+        };
+        let qualified = LogState::Qualified {
+            timestamp: date("2026-01-02T00:00:00Z"),
+        };
+        let pending = LogState::Pending {
+            timestamp: date("2025-01-01T00:00:00Z"),
+        };
+        let scts = vec![
+            get_sct(1, "Google", retired),
+            get_sct(2, "Sectigo", qualified),
+            get_sct(3, "DigiCert", pending),
+            get_sct(4, "Cloudflare", usable()),
+        ];
+        let result = ChromePolicy.evaluate(date(NOT_BEFORE), not_after, &scts, true);
+        assert_eq!(failed(&result), vec!["distinct_logs", "distinct_operators"]);
+    }
+}
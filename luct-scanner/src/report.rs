@@ -1,8 +1,9 @@
-use crate::Validated;
+use crate::{Validated, policy::PolicyResult};
 use chrono::{DateTime, Local, Utc};
-use luct_core::{CertificateChain, LogId, v1::SignedTreeHead};
+use luct_core::{CertificateChain, LogId, SignedCertificateTimestamp, SignedTreeHead};
 use luct_store::StringStoreValue;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 use web_time::UNIX_EPOCH;
 
 mod evaluate;
@@ -22,6 +23,8 @@ pub struct Report {
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub(crate) scts: Vec<SctReport>,
     #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub(crate) policy: Option<PolicyResult>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
     pub(crate) error_description: Option<String>,
 }
 
@@ -30,6 +33,11 @@ impl Report {
         self.error_description.clone()
     }
 
+    /// Returns the result of the last [`CtPolicy`](crate::CtPolicy) evaluation
+    pub fn policy(&self) -> Option<&PolicyResult> {
+        self.policy.as_ref()
+    }
+
     pub(crate) fn error_description(mut self, err: String) -> Self {
         self.error_description = Some(err);
         self
@@ -50,6 +58,7 @@ impl From<&CertificateChain> for Report {
             not_before,
             not_after,
             scts: vec![],
+            policy: None,
             error_description: None,
         }
     }
@@ -69,6 +78,8 @@ impl StringStoreValue for Report {
 pub struct SctReport {
     pub(crate) log_id: String,
     #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub(crate) timestamp: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
     pub(crate) signature_validation_time: Option<DateTime<Local>>,
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub(crate) log_name: Option<String>,
@@ -78,23 +89,61 @@ pub struct SctReport {
     pub(crate) index: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub(crate) inclusion_proof: Option<SthReport>,
+    /// The MMD of the log has not passed yet, such that the inclusion is checked later
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub(crate) pending_until: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub(crate) source: SctSource,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub(crate) timestamp_error: Option<SctTimestampError>,
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub(crate) error_description: Option<String>,
 }
 
 impl SctReport {
-    pub(crate) fn new(log_id: LogId) -> Self {
+    pub(crate) fn new(log_id: LogId, source: SctSource) -> Self {
         Self {
             log_id: log_id.to_string(),
+            timestamp: None,
             signature_validation_time: None,
             log_name: None,
             latest_sth: None,
             index: None,
             inclusion_proof: None,
+            pending_until: None,
+            source,
+            timestamp_error: None,
             error_description: None,
         }
     }
 
+    /// Returns how the SCT was delivered
+    pub fn source(&self) -> SctSource {
+        self.source
+    }
+
+    /// Returns the deadline of the deferred inclusion check, if the SCT is younger than the MMD
+    pub fn pending_until(&self) -> Option<DateTime<Utc>> {
+        self.pending_until
+    }
+
+    /// Returns the problem with the SCT's timestamp found during the last evaluation
+    pub fn timestamp_error(&self) -> Option<&SctTimestampError> {
+        self.timestamp_error.as_ref()
+    }
+
+    /// Returns `true`, if this report belongs to `sct`, delivered via `source`
+    pub(crate) fn is_for(&self, sct: &SignedCertificateTimestamp, source: SctSource) -> bool {
+        self.source == source
+            && self.log_id == sct.log_id().to_string()
+            && self.timestamp == DateTime::from_timestamp_millis(sct.timestamp() as i64)
+    }
+
+    pub(crate) fn timestamp(mut self, timestamp: DateTime<Utc>) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
     pub(crate) fn signature_validation_time(mut self, time: DateTime<Local>) -> Self {
         self.signature_validation_time = Some(time);
         self
@@ -120,6 +169,11 @@ impl SctReport {
         self
     }
 
+    pub(crate) fn defer_until(mut self, deadline: DateTime<Utc>) -> Self {
+        self.pending_until = Some(deadline);
+        self
+    }
+
     pub(crate) fn error_description(mut self, err: String) -> Self {
         self.error_description = Some(err);
         self
@@ -130,6 +184,59 @@ impl SctReport {
     }
 }
 
+/// The way an SCT was delivered alongside the certificate
+///
+/// See RFC 6962 3.3
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SctSource {
+    /// Embedded into the certificate, issued over the precertificate
+    #[default]
+    Embedded,
+    /// Delivered in the `signed_certificate_timestamp` TLS extension
+    TlsExtension,
+    /// Delivered in a stapled OCSP response
+    Ocsp,
+}
+
+impl SctSource {
+    /// Returns `true`, if the SCT was issued over the final certificate rather than a precertificate
+    pub fn is_final_cert(&self) -> bool {
+        *self != SctSource::Embedded
+    }
+}
+
+/// The timestamp of an SCT is inconsistent with the certificate or the log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Error)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SctTimestampError {
+    #[error(
+        "The certificate expires at {not_after}, which is outside of the log's temporal interval from {start_inclusive} until {end_exclusive}"
+    )]
+    OutsideTemporalInterval {
+        not_after: DateTime<Utc>,
+        start_inclusive: DateTime<Utc>,
+        end_exclusive: DateTime<Utc>,
+    },
+
+    #[error("The SCT was issued at {timestamp}, which is in the future")]
+    InFuture { timestamp: DateTime<Utc> },
+
+    #[error(
+        "The SCT was issued at {timestamp}, before the certificate became valid at {not_before}"
+    )]
+    BeforeNotBefore {
+        timestamp: DateTime<Utc>,
+        not_before: DateTime<Utc>,
+    },
+
+    #[error("The SCT was issued at {timestamp}, after the log was retired at {retired}")]
+    AfterRetirement {
+        timestamp: DateTime<Utc>,
+        retired: DateTime<Utc>,
+    },
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SthReport {
     height: u64,
@@ -153,5 +260,3 @@ impl From<&Validated<SignedTreeHead>> for SthReport {
         }
     }
 }
-
-// TODO: Tests for policy evaluation
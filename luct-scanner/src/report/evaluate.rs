@@ -1,45 +1,60 @@
-use crate::{Report, Scanner, ScannerImpl};
+use crate::{
+    PolicyResult, PolicySct, Report, Scanner, ScannerImpl, SctReport, SctTimestampError,
+    policy::required_scts,
+};
 use chrono::{DateTime, TimeDelta, Utc};
+use luct_core::{
+    LogId,
+    log_list::{LogMetadata, LogState},
+};
 use web_time::Duration;
 
+/// How far an SCT timestamp may be ahead of the local clock
+const CLOCK_SKEW_TOLERANCE: TimeDelta = TimeDelta::minutes(5);
+
 impl<S: ScannerImpl> Scanner<S> {
     pub(crate) fn evaluate_policy(
         &self,
         mut report: Report,
         current_time: DateTime<Utc>,
     ) -> Report {
-        // TODO: Check that expiration date matches logs expiration bracket?
-
-        // Calculate the number of scts we expect
-        let num_expected_scts = match report.not_after - report.not_before {
-            time if time <= TimeDelta::days(180) => 2,
-            _ => 3,
-        };
+        for sct in report.scts.iter_mut() {
+            sct.timestamp_error = self
+                .check_timestamp(sct, report.not_before, report.not_after, current_time)
+                .err();
+        }
 
-        // Calculate the number of scts that the report contains from known logs
-        // TODO: Make sure that the logs are from different operators
-        // TODO: Use log ids here and check that logs are not retired. This has better UI, as it might
-        // also recognize retired logs
-        let num_scts_from_known_logs = report
+        // Embedded SCTs and SCTs delivered via TLS or OCSP are evaluated separately.
+        // The certificate complies, if either set satisfies the policy.
+        let embedded_result = self.evaluate_sct_set(&report, true);
+        let delivered_result = report
             .scts
             .iter()
-            // NOTE: Having a signature that passed validation means the log is known
-            .filter(|sct| sct.signature_validation_time.is_some())
-            .count();
+            .any(|sct| sct.source.is_final_cert())
+            .then(|| self.evaluate_sct_set(&report, false));
+        let (result, embedded) = match delivered_result {
+            Some(result) if !embedded_result.passed() && result.passed() => (result, false),
+            _ => (embedded_result, true),
+        };
+        let failed_clause = result.failed_clause().cloned();
+        report.policy = Some(result);
 
-        // Check that we have enough SCTs from known logs
-        if num_scts_from_known_logs < num_expected_scts {
+        if let Some(clause) = failed_clause {
             return report.error_description(format!(
-                "Insufficient number of SCTs from known logs. Expected {} but got {}",
-                num_expected_scts, num_scts_from_known_logs
+                "Certificate does not comply with the {} CT policy: {}",
+                self.policy.name(),
+                clause.detail
             ));
         }
 
+        let num_expected_scts = required_scts(report.not_before, report.not_after, embedded);
+
         let mut fresh_inclusion_proofs = 0;
         let mut old_inclusion_proofs = 0;
+        let mut pending_inclusion_proofs = 0;
         for sct in report.scts.iter_mut() {
             // Scts with error cannot be valid
-            if sct.error_description.is_some() {
+            if sct.error_description.is_some() || sct.timestamp_error.is_some() {
                 continue;
             }
 
@@ -57,6 +72,17 @@ impl<S: ScannerImpl> Scanner<S> {
                 continue;
             }
 
+            // Only the SCTs of the set, that satisfied the policy, count towards the proofs
+            if sct.source.is_final_cert() == embedded {
+                continue;
+            }
+
+            // The inclusion of SCTs younger than the MMD is checked later
+            if sct.pending_until.is_some() {
+                pending_inclusion_proofs += 1;
+                continue;
+            }
+
             // Check whether the proofs are old or fresh
             let Some(inclusion_proof) = &sct.inclusion_proof else {
                 // Could not find an inclusion proof for this SCT
@@ -72,7 +98,9 @@ impl<S: ScannerImpl> Scanner<S> {
             }
         }
 
-        if old_inclusion_proofs == 0 && fresh_inclusion_proofs < num_expected_scts {
+        if old_inclusion_proofs == 0
+            && fresh_inclusion_proofs + pending_inclusion_proofs < num_expected_scts
+        {
             return report.error_description(
                 "Insufficient number of inclusion proofs with fresh sths could be verified!"
                     .to_string(),
@@ -81,9 +109,189 @@ impl<S: ScannerImpl> Scanner<S> {
 
         report
     }
+
+    /// Evaluate the policy over either the embedded or the delivered SCTs of the `report`
+    fn evaluate_sct_set(&self, report: &Report, embedded: bool) -> PolicyResult {
+        // NOTE: Having a signature that passed validation means the log is known
+        let scts = report
+            .scts
+            .iter()
+            .filter(|sct| sct.source.is_final_cert() != embedded)
+            .filter(|sct| sct.signature_validation_time.is_some())
+            .filter(|sct| sct.timestamp_error.is_none())
+            .filter_map(|sct| self.policy_sct(sct))
+            .collect::<Vec<_>>();
+
+        self.policy
+            .evaluate(report.not_before, report.not_after, &scts, embedded)
+    }
+
+    /// Check the timestamp of an SCT with a validated signature against the certificate and its log
+    fn check_timestamp(
+        &self,
+        sct: &SctReport,
+        not_before: DateTime<Utc>,
+        not_after: DateTime<Utc>,
+        current_time: DateTime<Utc>,
+    ) -> Result<(), SctTimestampError> {
+        let Some(timestamp) = sct.timestamp else {
+            return Ok(());
+        };
+        if sct.signature_validation_time.is_none() {
+            return Ok(());
+        }
+
+        let metadata = LogId::try_from(sct.log_id.as_str())
+            .ok()
+            .and_then(|log_id| self.logs.get(&log_id))
+            .and_then(|log| log.client().log().metadata());
+
+        check_sct_timestamp(
+            timestamp,
+            not_before,
+            not_after,
+            sct.source.is_final_cert(),
+            metadata,
+            current_time,
+        )
+    }
+
+    /// Collect the information about the log of an [`SctReport`] needed by the [`CtPolicy`](crate::CtPolicy)
+    fn policy_sct(&self, sct: &SctReport) -> Option<PolicySct> {
+        let log_id = LogId::try_from(sct.log_id.as_str()).ok()?;
+        let log = self.logs.get(&log_id)?.client().log();
+
+        let metadata = log.metadata();
+
+        Some(PolicySct {
+            operator: metadata.map(|metadata| metadata.operator().to_string()),
+            state: metadata.and_then(|metadata| metadata.state().cloned()),
+            rfc6962: !log.config().is_tiling(),
+            timestamp: sct.timestamp,
+            log_id,
+        })
+    }
+}
+
+fn check_sct_timestamp(
+    timestamp: DateTime<Utc>,
+    not_before: DateTime<Utc>,
+    not_after: DateTime<Utc>,
+    final_cert: bool,
+    metadata: Option<&LogMetadata>,
+    current_time: DateTime<Utc>,
+) -> Result<(), SctTimestampError> {
+    // Sharded logs only accept certificates expiring within their temporal interval
+    if let Some(interval) = metadata.and_then(|metadata| metadata.temporal_interval())
+        && !interval.contains(not_after)
+    {
+        return Err(SctTimestampError::OutsideTemporalInterval {
+            not_after,
+            start_inclusive: interval.start_inclusive(),
+            end_exclusive: interval.end_exclusive(),
+        });
+    }
+
+    if timestamp > current_time + CLOCK_SKEW_TOLERANCE {
+        return Err(SctTimestampError::InFuture { timestamp });
+    }
+
+    // A log can only have seen the final certificate after it was issued.
+    // Precertificates are logged before issuance, so their SCTs may predate `not_before`.
+    if final_cert && timestamp < not_before {
+        return Err(SctTimestampError::BeforeNotBefore {
+            timestamp,
+            not_before,
+        });
+    }
+
+    if let Some(LogState::Retired { timestamp: retired }) =
+        metadata.and_then(|metadata| metadata.state())
+        && timestamp > *retired
+    {
+        return Err(SctTimestampError::AfterRetirement {
+            timestamp,
+            retired: *retired,
+        });
+    }
+
+    Ok(())
 }
 
 fn time_delta_from_duration(duration: Duration) -> TimeDelta {
     TimeDelta::new(duration.as_secs() as i64, duration.subsec_nanos())
         .expect("Failed to translate duration into timedelta")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(date: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(date).unwrap().to_utc()
+    }
+
+    fn metadata() -> LogMetadata {
+        serde_json::from_value(serde_json::json!({
+            "operator": "Google",
+            "state": { "retired": { "timestamp": "2026-03-01T00:00:00Z" } },
+            "temporal_interval": {
+                "start_inclusive": "2026-01-01T00:00:00Z",
+                "end_exclusive": "2026-07-01T00:00:00Z"
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn check_sct_timestamps() {
+        let metadata = metadata();
+        let now = date("2026-04-01T00:00:00Z");
+        let not_before = date("2026-02-01T00:00:00Z");
+        let not_after = date("2026-05-01T00:00:00Z");
+        let check = |timestamp: &str, not_after, final_cert| {
+            check_sct_timestamp(
+                date(timestamp),
+                not_before,
+                not_after,
+                final_cert,
+                Some(&metadata),
+                now,
+            )
+        };
+
+        assert_eq!(check("2026-02-01T00:00:00Z", not_after, true), Ok(()));
+        assert!(matches!(
+            check("2026-02-01T00:00:00Z", date("2026-07-01T00:00:00Z"), false),
+            Err(SctTimestampError::OutsideTemporalInterval { .. })
+        ));
+        assert!(matches!(
+            check("2026-04-02T00:00:00Z", not_after, false),
+            Err(SctTimestampError::InFuture { .. })
+        ));
+        assert!(matches!(
+            check("2026-03-02T00:00:00Z", not_after, false),
+            Err(SctTimestampError::AfterRetirement { .. })
+        ));
+
+        // Only SCTs over the final certificate must not predate it
+        assert_eq!(check("2026-01-31T00:00:00Z", not_after, false), Ok(()));
+        assert!(matches!(
+            check("2026-01-31T00:00:00Z", not_after, true),
+            Err(SctTimestampError::BeforeNotBefore { .. })
+        ));
+
+        // Without metadata, only the certificate is checked
+        assert_eq!(
+            check_sct_timestamp(
+                date("2026-03-02T00:00:00Z"),
+                not_before,
+                date("2027-01-01T00:00:00Z"),
+                true,
+                None,
+                now,
+            ),
+            Ok(())
+        );
+    }
+}
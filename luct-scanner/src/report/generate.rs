@@ -1,10 +1,9 @@
-use crate::{Report, Scanner, ScannerError, ScannerImpl, SctReport, SthReport};
-use chrono::DateTime;
+use crate::{Report, Scanner, ScannerError, ScannerImpl, SctReport, SctSource, SthReport};
+use chrono::{DateTime, TimeDelta};
 use futures::future::join_all;
 use luct_core::{
-    CertificateChain, LogId,
+    CertificateChain, LogId, SignedCertificateTimestamp,
     store::{StoreRead, StoreWrite},
-    v1::{self, SignedCertificateTimestamp},
 };
 use std::sync::Arc;
 use web_time::{SystemTime, UNIX_EPOCH};
@@ -24,29 +23,49 @@ impl<S: ScannerImpl> Scanner<S> {
     pub async fn collect_report(
         &self,
         chain: Arc<CertificateChain>,
+    ) -> Result<Report, ScannerError> {
+        self.collect_report_with_scts(chain, vec![], vec![]).await
+    }
+
+    /// Collect a report, that also audits SCTs delivered via the TLS extension or a stapled OCSP response
+    ///
+    /// Unlike embedded SCTs, these are issued over the final certificate.
+    /// See [`SignedCertificateTimestamp::from_tls_extension`] and
+    /// [`Certificate::extract_ocsp_scts`](luct_core::Certificate::extract_ocsp_scts).
+    pub async fn collect_report_with_scts(
+        &self,
+        chain: Arc<CertificateChain>,
+        tls_scts: Vec<SignedCertificateTimestamp>,
+        ocsp_scts: Vec<SignedCertificateTimestamp>,
     ) -> Result<Report, ScannerError> {
         let cert = chain.cert();
         let cert_fp = cert.fingerprint_sha256();
 
+        let delivered_scts = tls_scts
+            .into_iter()
+            .map(|sct| (SctSource::TlsExtension, sct))
+            .chain(ocsp_scts.into_iter().map(|sct| (SctSource::Ocsp, sct)))
+            .collect::<Vec<_>>();
+
         let report = match self.report_store.get(cert_fp.clone()).await {
             Some(report) => {
                 tracing::debug!("Found report for {} in cache", cert_fp.to_string());
 
                 // We need to update the report. If we fail, this is likely because the report
                 // does no longer serialize, e.g. due to an update
-                match self.update_report(report, &chain).await {
+                match self.update_report(report, &chain, &delivered_scts).await {
                     Err(()) => {
                         tracing::info!(
                             "Found an invalid report (likely generated by outdated version). Will generate fresh report"
                         );
-                        self.create_report(chain).await
+                        self.create_report(chain, delivered_scts).await
                     }
                     Ok(report) => report,
                 }
             }
             None => {
                 tracing::debug!("Could not find report for {} in cache", cert_fp.to_string());
-                self.create_report(chain).await
+                self.create_report(chain, delivered_scts).await
             }
         };
 
@@ -58,12 +77,16 @@ impl<S: ScannerImpl> Scanner<S> {
         Ok(report)
     }
 
-    async fn create_report(&self, chain: Arc<CertificateChain>) -> Report {
+    async fn create_report(
+        &self,
+        chain: Arc<CertificateChain>,
+        delivered_scts: Vec<(SctSource, SignedCertificateTimestamp)>,
+    ) -> Report {
         let cert = chain.cert();
 
         let mut report = Report::from(chain.as_ref());
 
-        let embedded_scts = match cert.extract_scts_v1() {
+        let embedded_scts = match cert.extract_scts() {
             Err(err) => {
                 return report
                     .error_description(format!("Failed to parse SCTs from certificate:s {}", err));
@@ -74,7 +97,9 @@ impl<S: ScannerImpl> Scanner<S> {
         let sct_reports = join_all(
             embedded_scts
                 .into_iter()
-                .map(|sct| self.collect_embedded_sct_report(sct, &chain)),
+                .map(|sct| (SctSource::Embedded, sct))
+                .chain(delivered_scts)
+                .map(|(source, sct)| self.collect_sct_report(sct, source, &chain)),
         )
         .await;
 
@@ -82,13 +107,19 @@ impl<S: ScannerImpl> Scanner<S> {
         report
     }
 
-    async fn collect_embedded_sct_report(
+    pub(crate) async fn collect_sct_report(
         &self,
         sct: SignedCertificateTimestamp,
+        source: SctSource,
         chain: &Arc<CertificateChain>,
     ) -> SctReport {
         let now = SystemTime::now();
-        let report = SctReport::new(sct.log_id());
+        let as_precert = !source.is_final_cert();
+        let report = SctReport::new(sct.log_id(), source);
+        let report = match DateTime::from_timestamp_millis(sct.timestamp() as i64) {
+            Some(timestamp) => report.timestamp(timestamp),
+            None => report,
+        };
 
         // Find the log this sct belongs to
         let Some(log) = self.logs.get(&sct.log_id()) else {
@@ -98,7 +129,7 @@ impl<S: ScannerImpl> Scanner<S> {
         let report = report.log_name(log_name);
 
         // Validate the signature
-        if let Err(err) = log.client().log().validate_sct_v1(chain, &sct, true) {
+        if let Err(err) = log.client().log().validate_sct(chain, &sct, as_precert) {
             return report.error_description(format!("Failed to validate signature: {}", err));
         };
         let report = report.signature_validation_time(
@@ -118,7 +149,7 @@ impl<S: ScannerImpl> Scanner<S> {
         };
         let report = report.latest_sth(SthReport::from(&fresh_sth));
 
-        let leaf = match chain.as_leaf_v1(&sct, true) {
+        let leaf = match chain.as_leaf(&sct, as_precert) {
             Err(err) => {
                 return report.error_description(err.to_string());
             }
@@ -129,7 +160,22 @@ impl<S: ScannerImpl> Scanner<S> {
         let oldest_sth = log.oldest_viable_sth(&sct).await.unwrap_or(fresh_sth);
         let report = match log.check_sct_inclusion(&sct, &oldest_sth, &leaf).await {
             Ok(index) => report.index(index),
-            Err(err) => return report.error_description(err.to_string()),
+            Err(err) => {
+                // The log is not required to include the SCT before its MMD has passed.
+                // A transient failure after the MMD is no evidence of a violation either.
+                let mmd = TimeDelta::seconds(log.client().log().config().mmd() as i64);
+                let deadline = report.timestamp.map(|timestamp| timestamp + mmd);
+                return match deadline {
+                    Some(deadline) if (self.time_source)() < deadline || err.is_transient() => {
+                        self.defer_sct(sct, source, chain, deadline).await;
+                        report.defer_until(deadline)
+                    }
+                    Some(_) => report.error_description(format!(
+                        "SCT was not included after the MMD of the log passed: {err}"
+                    )),
+                    None => report.error_description(err.to_string()),
+                };
+            }
         };
 
         report.inclusion_proof(SthReport::from(&oldest_sth))
@@ -139,16 +185,35 @@ impl<S: ScannerImpl> Scanner<S> {
         &self,
         mut report: Report,
         chain: &Arc<CertificateChain>,
+        delivered_scts: &[(SctSource, SignedCertificateTimestamp)],
     ) -> Result<Report, ()> {
+        // Pending SCTs are checked again, once their deadline has passed
+        let scts = chain
+            .cert()
+            .extract_scts()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|sct| (SctSource::Embedded, sct))
+            .chain(delivered_scts.iter().cloned())
+            .collect::<Vec<_>>();
+
         let new_sct_reports = join_all(
             report
                 .scts
                 .drain(..)
-                .map(|sct_report| self.update_sct_report(sct_report, chain)),
+                .map(|sct_report| self.update_sct_report(sct_report, chain, &scts)),
         )
         .await;
+        let mut sct_reports = new_sct_reports.into_iter().collect::<Result<Vec<_>, _>>()?;
+
+        // The server may deliver SCTs, that were not part of the cached report
+        let unknown_scts = delivered_scts
+            .iter()
+            .filter(|(source, sct)| !sct_reports.iter().any(|report| report.is_for(sct, *source)))
+            .map(|(source, sct)| self.collect_sct_report(sct.clone(), *source, chain));
+        sct_reports.extend(join_all(unknown_scts).await);
 
-        report.scts = new_sct_reports.into_iter().collect::<Result<_, _>>()?;
+        report.scts = sct_reports;
         Ok(report)
     }
 
@@ -156,12 +221,22 @@ impl<S: ScannerImpl> Scanner<S> {
         &self,
         report: SctReport,
         chain: &Arc<CertificateChain>,
+        scts: &[(SctSource, SignedCertificateTimestamp)],
     ) -> Result<SctReport, ()> {
         let now = SystemTime::now();
 
+        if let Some(deadline) = report.pending_until
+            && deadline <= (self.time_source)()
+            && let Some((source, sct)) = scts
+                .iter()
+                .find(|(source, sct)| report.is_for(sct, *source))
+        {
+            self.resolve_sct(sct).await;
+            return Ok(self.collect_sct_report(sct.clone(), *source, chain).await);
+        }
+
         // Find the log this sct belongs to
-        let log_id = v1::LogId::try_from(report.log_id.as_str())?;
-        let log_id = LogId::V1(log_id);
+        let log_id = LogId::try_from(report.log_id.as_str())?;
 
         let Some(log) = self.logs.get(&log_id) else {
             return Ok(report.error_description("Unknown log id".to_string()));
@@ -179,3 +254,45 @@ impl<S: ScannerImpl> Scanner<S> {
         Ok(report.latest_sth(SthReport::from(&fresh_sth)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{ARGON2025H2_REPLAY, CERT_CHAIN_GOOGLE_COM, get_scanner};
+    use chrono::DateTime;
+    use luct_client::replay::ReplayClient;
+
+    #[tokio::test]
+    async fn collect_report() {
+        let now = DateTime::parse_from_rfc3339("2025-07-06T00:00:00Z")
+            .unwrap()
+            .to_utc();
+        let scanner = get_scanner(ReplayClient::load(ARGON2025H2_REPLAY).unwrap(), move || now);
+
+        let report = scanner
+            .collect_report_pem(CERT_CHAIN_GOOGLE_COM)
+            .await
+            .unwrap();
+        assert_eq!(report.scts.len(), 2);
+
+        // The SCT of Argon2025h2 is included in the replayed tree head
+        let argon = &report.scts[0];
+        assert_eq!(argon.error_description, None);
+        assert_eq!(argon.index, Some(912557348));
+        assert_eq!(argon.inclusion_proof.as_ref().unwrap().height, 1329315675);
+        assert!(argon.signature_validation_time.is_some());
+
+        // The second log is not known to the scanner
+        assert_eq!(
+            report.scts[1].error_description.as_deref(),
+            Some("Unknown log id")
+        );
+
+        // Only one of the logs could be audited, which is not enough for the policy
+        assert!(
+            report
+                .get_error()
+                .unwrap()
+                .contains("1 SCTs from distinct approved logs")
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{Scanner, ScannerError, ScannerImpl, Validated, log::ScannerLog};
-use luct_core::{Certificate, v1::SignedTreeHead};
+use luct_core::{Certificate, SignedTreeHead};
 use web_time::{Duration, SystemTime, UNIX_EPOCH};
 
 impl<S: ScannerImpl> Scanner<S> {
@@ -10,10 +10,7 @@ mod switch;
 #[cfg(not(any(target_arch = "wasm32", target_arch = "wasm64")))]
 pub use file::FilesystemStore;
 pub use lru::LruCacheStore;
-use luct_core::{
-    Fingerprint,
-    v1::{SignedCertificateTimestamp, SignedTreeHead},
-};
+use luct_core::{Fingerprint, SignedCertificateTimestamp, SignedTreeHead, v1};
 pub use meta_cache::MetadataCacheStore;
 pub use switch::StoreSwitch;
 
@@ -110,6 +107,36 @@ impl StringStoreValue for String {
     }
 }
 
+impl StringStoreValue for Vec<u8> {
+    fn serialize_value(&self) -> String {
+        hex::encode(self)
+    }
+
+    fn deserialize_value(value: &str) -> Option<Self> {
+        hex::decode(value).ok()
+    }
+}
+
+impl StringStoreValue for v1::SignedTreeHead {
+    fn serialize_value(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    fn deserialize_value(value: &str) -> Option<Self> {
+        serde_json::from_str(value).ok()
+    }
+}
+
+impl StringStoreValue for v1::SignedCertificateTimestamp {
+    fn serialize_value(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    fn deserialize_value(value: &str) -> Option<Self> {
+        serde_json::from_str(value).ok()
+    }
+}
+
 impl StringStoreValue for SignedTreeHead {
     fn serialize_value(&self) -> String {
         serde_json::to_string(self).unwrap()
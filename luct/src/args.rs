@@ -26,6 +26,10 @@ pub(crate) struct Args {
     #[arg(short, long)]
     pub(crate) update_sths: bool,
 
+    /// Fetch the latest log list and verify its signature against the configured `log_list_key`
+    #[arg(long)]
+    pub(crate) update_log_list: bool,
+
     /// Do not use the SCT cache when validating
     #[arg(long)]
     pub(crate) no_cache: bool,
@@ -33,6 +37,15 @@ pub(crate) struct Args {
     /// Output the certificate chain to a file
     #[arg(long, value_name = "DESTINATION")]
     pub(crate) output_certificate: Option<PathBuf>,
+
+    /// Export evidence of SCTs, that were not included after the MMD of their log passed, into a directory
+    #[arg(long, value_name = "DIRECTORY")]
+    pub(crate) mmd_evidence: Option<PathBuf>,
+
+    /// Cross-check the STHs of all logs from multiple vantage points and export detected
+    /// inconsistencies into a directory
+    #[arg(long, value_name = "DIRECTORY")]
+    pub(crate) gossip_sths: Option<PathBuf>,
 }
 
 pub(crate) fn get_workdir(args: &Args, config: &CliConfig) -> PathBuf {
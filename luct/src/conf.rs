@@ -1,6 +1,8 @@
 use config::{Config as Conf, Environment, File};
+use luct_client::{rate_limit::RateLimitConfig, retry::DEFAULT_MAX_ATTEMPTS};
+use luct_core::{CtLogConfig, tiling::WitnessPolicy};
 use luct_otlsp::OtlspClientConfig;
-use luct_scanner::ScannerConfig;
+use luct_scanner::{PolicyKind, ScannerConfig};
 use serde::{Deserialize, Serialize};
 use std::{path::PathBuf, time::Duration};
 use url::Url;
@@ -18,14 +20,55 @@ pub(crate) struct CliConfig {
     #[serde(default = "default_none")]
     pub(crate) log_list: Option<PathBuf>,
 
+    /// Logs, that are not part of the public log lists, e.g. private logs of a test CA
+    ///
+    /// Each custom log must name its `operator`.
+    #[serde(default)]
+    pub(crate) custom_logs: Vec<CtLogConfig>,
+
+    /// Only use the `custom_logs` and ignore the logs of the public log lists
+    #[serde(default = "default_false")]
+    pub(crate) exclude_public_logs: bool,
+
+    /// Path to Apple's log list, whose logs are added to the ones of the log list
+    #[serde(default = "default_none")]
+    pub(crate) apple_log_list: Option<PathBuf>,
+
+    /// Path to the PEM encoded public key, that signs the log list
+    #[serde(default = "default_none")]
+    pub(crate) log_list_key: Option<PathBuf>,
+
     #[serde(default = "default_none")]
     pub(crate) otlsp_url: Option<Url>,
 
+    /// STH endpoints of luct-nodes, that are used as vantage points to detect split views of logs
+    #[serde(default)]
+    pub(crate) sth_gossip_nodes: Vec<Url>,
+
     #[serde(default = "default_sth_freshness_threshold")]
     pub(crate) sth_freshness_threshold: u64,
 
     #[serde(default = "default_sth_update_threshold")]
     pub(crate) sth_update_threshold: u64,
+
+    #[serde(default = "default_none")]
+    pub(crate) witness_policy: Option<WitnessPolicy>,
+
+    #[serde(default = "default_host_requests_per_second")]
+    pub(crate) host_requests_per_second: u32,
+
+    #[serde(default = "default_host_request_burst")]
+    pub(crate) host_request_burst: u32,
+
+    #[serde(default = "default_max_requests_in_flight")]
+    pub(crate) max_requests_in_flight: usize,
+
+    /// The maximum number of attempts of a request to a log, including the first one
+    #[serde(default = "default_max_attempts")]
+    pub(crate) max_attempts: u32,
+
+    #[serde(default)]
+    pub(crate) ct_policy: PolicyKind,
 }
 
 fn default_false() -> bool {
@@ -50,6 +93,22 @@ fn default_sth_update_threshold() -> u64 {
     8 * 60 * 60
 }
 
+fn default_host_requests_per_second() -> u32 {
+    RateLimitConfig::DEFAULT_REQUESTS_PER_SECOND
+}
+
+fn default_host_request_burst() -> u32 {
+    RateLimitConfig::DEFAULT_BURST
+}
+
+fn default_max_requests_in_flight() -> usize {
+    RateLimitConfig::DEFAULT_MAX_IN_FLIGHT
+}
+
+fn default_max_attempts() -> u32 {
+    DEFAULT_MAX_ATTEMPTS
+}
+
 impl CliConfig {
     pub(crate) fn parse() -> eyre::Result<Self> {
         let config = Conf::builder()
@@ -78,6 +137,8 @@ impl TryFrom<&CliConfig> for ScannerConfig {
             .validate_cert_chain(config.validate_cert_chain)
             .sth_freshness_threshold(Duration::from_secs(config.sth_freshness_threshold))
             .sth_update_threshold(Duration::from_secs(config.sth_update_threshold))
+            .witness_policy(config.witness_policy.clone())
+            .policy(config.ct_policy)
             .build()
             .map_err(|err| err.to_string())?;
 
@@ -98,3 +159,18 @@ impl TryFrom<&CliConfig> for OtlspClientConfig {
         Ok(config)
     }
 }
+
+impl TryFrom<&CliConfig> for RateLimitConfig {
+    type Error = String;
+
+    fn try_from(config: &CliConfig) -> Result<Self, Self::Error> {
+        let config = RateLimitConfig::builder()
+            .requests_per_second(config.host_requests_per_second)
+            .burst(config.host_request_burst)
+            .max_in_flight(config.max_requests_in_flight)
+            .build()
+            .map_err(|err| err.to_string())?;
+
+        Ok(config)
+    }
+}
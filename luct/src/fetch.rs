@@ -1,11 +1,16 @@
 use eyre::{Context, Report};
-use luct_core::{Certificate, CertificateChain};
-use rustls::pki_types::ServerName;
-use rustls_platform_verifier::BuilderVerifierExt as _;
+use luct_core::{Certificate, CertificateChain, SignedCertificateTimestamp};
+use rustls::{
+    DigitallySignedStruct, Error as TlsError, SignatureScheme,
+    client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+    pki_types::{CertificateDer, ServerName, UnixTime},
+};
+use rustls_platform_verifier::Verifier;
 use std::{
     io::{Read, Write},
-    net::TcpStream,
-    sync::Arc,
+    net::{SocketAddr, TcpStream},
+    sync::{Arc, Mutex},
+    time::Duration,
 };
 use url::Url;
 
@@ -16,19 +21,34 @@ use url::Url;
 
 // NOTE: This code is copied and adapted from https://github.com/robjtede/inspect-cert-chain/blob/main/src/fetch.rs
 
-pub(crate) fn fetch_cert_chain(url: &str) -> eyre::Result<CertificateChain> {
+/// A certificate chain together with the SCTs the server delivered alongside it
+pub(crate) struct FetchedChain {
+    pub(crate) chain: CertificateChain,
+    /// SCTs from the `signed_certificate_timestamp` TLS extension
+    pub(crate) tls_scts: Vec<SignedCertificateTimestamp>,
+    /// SCTs from the stapled OCSP response
+    pub(crate) ocsp_scts: Vec<SignedCertificateTimestamp>,
+}
+
+pub(crate) fn fetch_cert_chain(url: &str) -> eyre::Result<FetchedChain> {
     let url = Url::parse(url).with_context(|| format!("failed to parse url: \"{url}\""))?;
 
     let server_name = ServerName::try_from(url.domain().unwrap())
         .with_context(|| format!("failed to convert given host (\"{url}\") to server name"))?
         .to_owned();
 
-    let config = rustls::ClientConfig::builder_with_provider(rustls_rustcrypto::provider().into())
+    let provider = Arc::new(rustls_rustcrypto::provider());
+    let verifier = Arc::new(OcspRecorder {
+        inner: Verifier::new(provider.clone())?,
+        ocsp_response: Mutex::new(vec![]),
+    });
+    let config = rustls::ClientConfig::builder_with_provider(provider)
         .with_protocol_versions(&[&rustls::version::TLS13])?
-        .with_platform_verifier()?
+        .dangerous()
+        .with_custom_certificate_verifier(verifier.clone())
         .with_no_client_auth();
 
-    let mut conn = rustls::ClientConnection::new(Arc::new(config), server_name)?;
+    let mut conn = rustls::ClientConnection::new(Arc::new(config), server_name.clone())?;
 
     let sock_addr = url.socket_addrs(|| None)?;
     let mut sock = TcpStream::connect(sock_addr[0])
@@ -78,5 +98,413 @@ Accept-Encoding: identity
         })
         .unwrap();
 
-    Ok(chain)
+    let ocsp_response = verifier.ocsp_response.lock().unwrap().clone();
+    let ocsp_scts = match ocsp_response.is_empty() {
+        true => vec![],
+        false => chain
+            .cert()
+            .extract_ocsp_scts(&ocsp_response)
+            .unwrap_or_else(|err| {
+                tracing::warn!("Ignoring malformed stapled OCSP response: {err}");
+                vec![]
+            }),
+    };
+    tracing::debug!(
+        "Found {} SCTs in the stapled OCSP response",
+        ocsp_scts.len()
+    );
+
+    let tls_scts = fetch_tls_scts(sock_addr[0], server_name, chain.cert()).unwrap_or_else(|err| {
+        tracing::warn!("Failed to fetch SCTs from the TLS extension: {err:#}");
+        vec![]
+    });
+    tracing::debug!("Found {} SCTs in the TLS extension", tls_scts.len());
+
+    Ok(FetchedChain {
+        chain,
+        tls_scts,
+        ocsp_scts,
+    })
+}
+
+/// Extension type of the `signed_certificate_timestamp` TLS extension, see RFC 6962 3.3.1
+const SCT_EXTENSION: u16 = 18;
+
+const HANDSHAKE_RECORD: u8 = 22;
+const ALERT_RECORD: u8 = 21;
+const CLIENT_HELLO: u8 = 1;
+const SERVER_HELLO: u8 = 2;
+const CERTIFICATE: u8 = 11;
+
+/// Fetch the SCTs, that the server delivers in the `signed_certificate_timestamp` TLS extension
+///
+/// rustls neither requests nor exposes this extension, and adding it to the rustls handshake
+/// is not possible, since the ClientHello is covered by the handshake transcript.
+/// Instead, a second TLS 1.2 handshake requesting the extension is started and aborted after the
+/// Certificate message, since both the ServerHello carrying the extension and the certificate are
+/// sent in plaintext.
+///
+/// The SCTs are issued over the certificate served on this second connection.
+/// If it serves a different leaf than `cert`, e.g. behind a load balancer, the SCTs are discarded.
+fn fetch_tls_scts(
+    addr: SocketAddr,
+    server_name: ServerName<'_>,
+    cert: &Certificate,
+) -> eyre::Result<Vec<SignedCertificateTimestamp>> {
+    let ServerName::DnsName(host) = server_name else {
+        eyre::bail!("The TLS extension can only be requested for DNS names");
+    };
+
+    let mut random = [0; 32];
+    rustls_rustcrypto::provider()
+        .secure_random
+        .fill(&mut random)
+        .map_err(|_| eyre::eyre!("failed to generate client random"))?;
+
+    let mut sock = TcpStream::connect_timeout(&addr, Duration::from_secs(10))
+        .wrap_err_with(|| format!("failed to connect to {addr}"))?;
+    sock.set_read_timeout(Some(Duration::from_secs(10)))?;
+    sock.write_all(&client_hello(host.as_ref(), random))
+        .wrap_err("failed to write ClientHello")?;
+
+    let extension = read_sct_extension(&mut sock, cert)?;
+    let scts = match extension {
+        Some(extension) => SignedCertificateTimestamp::from_tls_extension(&extension)
+            .wrap_err("failed to parse SCTs from the TLS extension")?,
+        None => vec![],
+    };
+
+    Ok(scts)
+}
+
+/// Build a TLS 1.2 ClientHello record, that requests the `signed_certificate_timestamp` extension
+fn client_hello(host: &str, random: [u8; 32]) -> Vec<u8> {
+    // ECDHE suites with AES-GCM and ChaCha20-Poly1305
+    const CIPHER_SUITES: [u16; 6] = [0xc02b, 0xc02f, 0xc02c, 0xc030, 0xcca9, 0xcca8];
+    // x25519, secp256r1 and secp384r1
+    const GROUPS: [u16; 3] = [0x001d, 0x0017, 0x0018];
+    // ECDSA, RSA-PSS and RSA PKCS#1 with SHA-256, SHA-384 and SHA-512
+    const SIGNATURE_SCHEMES: [u16; 8] = [
+        0x0403, 0x0804, 0x0401, 0x0503, 0x0805, 0x0501, 0x0806, 0x0601,
+    ];
+
+    let mut server_name = vec![0];
+    put_u16_prefixed(&mut server_name, host.as_bytes());
+
+    let mut extensions = vec![];
+    put_extension(&mut extensions, 0, &u16_prefixed(&server_name));
+    put_extension(&mut extensions, 10, &u16_prefixed(&u16_list(&GROUPS)));
+    put_extension(&mut extensions, 11, &[1, 0]);
+    put_extension(
+        &mut extensions,
+        13,
+        &u16_prefixed(&u16_list(&SIGNATURE_SCHEMES)),
+    );
+    put_extension(&mut extensions, SCT_EXTENSION, &[]);
+    put_extension(&mut extensions, 0xff01, &[0]);
+
+    let mut body = vec![3, 3];
+    body.extend_from_slice(&random);
+    body.push(0);
+    put_u16_prefixed(&mut body, &u16_list(&CIPHER_SUITES));
+    body.extend_from_slice(&[1, 0]);
+    put_u16_prefixed(&mut body, &extensions);
+
+    let mut handshake = vec![CLIENT_HELLO];
+    handshake.extend_from_slice(&(body.len() as u32).to_be_bytes()[1..]);
+    handshake.extend_from_slice(&body);
+
+    let mut record = vec![HANDSHAKE_RECORD, 3, 1];
+    put_u16_prefixed(&mut record, &handshake);
+    record
+}
+
+/// Read the ServerHello and the Certificate message and return the content of the
+/// `signed_certificate_timestamp` extension
+///
+/// Returns `None`, if the server did not send the extension or the leaf of the Certificate message
+/// is not `cert`.
+fn read_sct_extension(reader: impl Read, cert: &Certificate) -> eyre::Result<Option<Vec<u8>>> {
+    let mut handshake = HandshakeReader {
+        reader,
+        buffer: vec![],
+    };
+
+    let hello = handshake.next_message(SERVER_HELLO)?;
+    let extension = parse_server_hello(&hello)?;
+
+    // The leaf is the first certificate of the list
+    let certificate = handshake.next_message(CERTIFICATE)?;
+    let mut certificates = Parser(&certificate);
+    certificates.take(3)?;
+    let length = certificates.u24()?;
+    let leaf = Certificate::from_der(certificates.take(length)?)
+        .wrap_err("failed to parse the leaf certificate")?;
+
+    if &leaf != cert {
+        tracing::warn!("Discarding SCTs from the TLS extension, since they cover a different leaf");
+        return Ok(None);
+    }
+
+    Ok(extension)
+}
+
+/// Reads the handshake messages of the server, that may be fragmented over multiple records
+struct HandshakeReader<R> {
+    reader: R,
+    buffer: Vec<u8>,
+}
+
+impl<R: Read> HandshakeReader<R> {
+    /// Read the next handshake message, which must be of type `expected`, and return its body
+    fn next_message(&mut self, expected: u8) -> eyre::Result<Vec<u8>> {
+        let length = loop {
+            if self.buffer.len() >= 4 {
+                let length =
+                    u32::from_be_bytes([0, self.buffer[1], self.buffer[2], self.buffer[3]]);
+                if self.buffer.len() >= 4 + length as usize {
+                    break length as usize;
+                }
+            }
+
+            let mut header = [0; 5];
+            self.reader
+                .read_exact(&mut header)
+                .wrap_err("failed to read TLS record")?;
+            let mut fragment = vec![0; u16::from_be_bytes([header[3], header[4]]) as usize];
+            self.reader
+                .read_exact(&mut fragment)
+                .wrap_err("failed to read TLS record")?;
+
+            match header[0] {
+                HANDSHAKE_RECORD => self.buffer.extend_from_slice(&fragment),
+                ALERT_RECORD => eyre::bail!("The server sent the alert {fragment:?}"),
+                content_type => eyre::bail!("Unexpected TLS record of type {content_type}"),
+            }
+        };
+
+        if self.buffer[0] != expected {
+            eyre::bail!(
+                "Expected handshake message {expected}, got handshake message {}",
+                self.buffer[0]
+            );
+        }
+
+        let message = self.buffer[4..4 + length].to_vec();
+        self.buffer.drain(..4 + length);
+        Ok(message)
+    }
+}
+
+/// Return the content of the `signed_certificate_timestamp` extension of the ServerHello
+fn parse_server_hello(hello: &[u8]) -> eyre::Result<Option<Vec<u8>>> {
+    let mut hello = Parser(hello);
+
+    // Version and random
+    hello.take(34)?;
+    let session_id = hello.u8()? as usize;
+    hello.take(session_id)?;
+    // Cipher suite and compression method
+    hello.take(3)?;
+
+    if hello.0.is_empty() {
+        return Ok(None);
+    }
+    let length = hello.u16()? as usize;
+    let mut extensions = Parser(hello.take(length)?);
+    while !extensions.0.is_empty() {
+        let extension_type = extensions.u16()?;
+        let length = extensions.u16()? as usize;
+        let data = extensions.take(length)?;
+        if extension_type == SCT_EXTENSION {
+            return Ok(Some(data.to_vec()));
+        }
+    }
+
+    Ok(None)
+}
+
+struct Parser<'a>(&'a [u8]);
+
+impl<'a> Parser<'a> {
+    fn take(&mut self, length: usize) -> eyre::Result<&'a [u8]> {
+        if self.0.len() < length {
+            eyre::bail!("The handshake message is truncated");
+        }
+        let (data, rest) = self.0.split_at(length);
+        self.0 = rest;
+        Ok(data)
+    }
+
+    fn u8(&mut self) -> eyre::Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> eyre::Result<u16> {
+        let data = self.take(2)?;
+        Ok(u16::from_be_bytes([data[0], data[1]]))
+    }
+
+    fn u24(&mut self) -> eyre::Result<usize> {
+        let data = self.take(3)?;
+        Ok(u32::from_be_bytes([0, data[0], data[1], data[2]]) as usize)
+    }
+}
+
+fn u16_list(values: &[u16]) -> Vec<u8> {
+    values
+        .iter()
+        .flat_map(|value| value.to_be_bytes())
+        .collect()
+}
+
+fn u16_prefixed(data: &[u8]) -> Vec<u8> {
+    let mut buf = vec![];
+    put_u16_prefixed(&mut buf, data);
+    buf
+}
+
+fn put_u16_prefixed(buf: &mut Vec<u8>, data: &[u8]) {
+    buf.extend_from_slice(&(data.len() as u16).to_be_bytes());
+    buf.extend_from_slice(data);
+}
+
+fn put_extension(buf: &mut Vec<u8>, extension_type: u16, data: &[u8]) {
+    buf.extend_from_slice(&extension_type.to_be_bytes());
+    put_u16_prefixed(buf, data);
+}
+
+/// Wraps the platform [`Verifier`] and records the stapled OCSP response
+#[derive(Debug)]
+struct OcspRecorder {
+    inner: Verifier,
+    ocsp_response: Mutex<Vec<u8>>,
+}
+
+impl ServerCertVerifier for OcspRecorder {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, TlsError> {
+        *self.ocsp_response.lock().unwrap() = ocsp_response.to_vec();
+        self.inner
+            .verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, TlsError> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, TlsError> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEAF: &[u8] = include_bytes!("../../testdata/google-issuer.der");
+    const OTHER_CERT: &str = include_str!("../../testdata/google-cert.pem");
+
+    fn handshake_message(message_type: u8, body: &[u8]) -> Vec<u8> {
+        let mut handshake = vec![message_type];
+        handshake.extend_from_slice(&(body.len() as u32).to_be_bytes()[1..]);
+        handshake.extend_from_slice(body);
+        handshake
+    }
+
+    /// Returns the ServerHello with `extensions`, followed by the Certificate message with `LEAF`
+    fn server_hello(extensions: &[u8]) -> Vec<u8> {
+        let mut body = vec![3, 3];
+        body.extend_from_slice(&[0x42; 32]);
+        body.extend_from_slice(&[1, 0x17]);
+        body.extend_from_slice(&[0xc0, 0x2f, 0]);
+        put_u16_prefixed(&mut body, extensions);
+
+        let mut certificates = (LEAF.len() as u32).to_be_bytes()[1..].to_vec();
+        certificates.extend_from_slice(LEAF);
+        let mut certificate = (certificates.len() as u32).to_be_bytes()[1..].to_vec();
+        certificate.extend_from_slice(&certificates);
+
+        [
+            handshake_message(SERVER_HELLO, &body),
+            handshake_message(CERTIFICATE, &certificate),
+        ]
+        .concat()
+    }
+
+    fn records(handshake: &[u8], fragment_size: usize) -> Vec<u8> {
+        let mut records = vec![];
+        for fragment in handshake.chunks(fragment_size) {
+            records.extend_from_slice(&[HANDSHAKE_RECORD, 3, 3]);
+            put_u16_prefixed(&mut records, fragment);
+        }
+        records
+    }
+
+    #[test]
+    fn read_server_flight() {
+        let leaf = Certificate::from_der(LEAF).unwrap();
+        let mut extensions = vec![];
+        put_extension(&mut extensions, 0xff01, &[0]);
+        put_extension(&mut extensions, SCT_EXTENSION, &[0, 3, 0, 1, 0x42]);
+
+        let hello = server_hello(&extensions);
+        let extension = read_sct_extension(records(&hello, 16).as_slice(), &leaf).unwrap();
+        assert_eq!(extension, Some(vec![0, 3, 0, 1, 0x42]));
+
+        let hello = server_hello(&[]);
+        assert_eq!(
+            read_sct_extension(records(&hello, 100).as_slice(), &leaf).unwrap(),
+            None
+        );
+
+        // SCTs over a different leaf than the one of the chain are discarded
+        let other = Certificate::from_pem(OTHER_CERT).unwrap();
+        let hello = server_hello(&extensions);
+        assert_eq!(
+            read_sct_extension(records(&hello, 100).as_slice(), &other).unwrap(),
+            None
+        );
+
+        // Truncated and rejected handshakes
+        let hello = server_hello(&extensions);
+        assert!(read_sct_extension(&records(&hello, 100)[..40], &leaf).is_err());
+        assert!(read_sct_extension(&records(&hello, 100)[..200], &leaf).is_err());
+        assert!(read_sct_extension([ALERT_RECORD, 3, 3, 0, 2, 2, 40].as_slice(), &leaf).is_err());
+    }
+
+    #[test]
+    fn request_sct_extension() {
+        let hello = client_hello("google.com", [0x42; 32]);
+        assert_eq!(&hello[..3], &[HANDSHAKE_RECORD, 3, 1]);
+        assert_eq!(
+            u16::from_be_bytes([hello[3], hello[4]]) as usize,
+            hello.len() - 5
+        );
+        assert_eq!(hello[5], CLIENT_HELLO);
+
+        // The SCT extension is requested with empty content
+        let sct_extension = [SCT_EXTENSION.to_be_bytes(), [0, 0]].concat();
+        assert!(hello.windows(4).any(|window| window == sct_extension));
+    }
 }
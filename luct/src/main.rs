@@ -1,25 +1,39 @@
 #![forbid(unsafe_code)]
+// NOTE: The nested client wrappers exceed the default limit when computing the layout of futures
+#![recursion_limit = "256"]
 
 use crate::{
     args::{Args, get_workdir, log_list_path},
     conf::CliConfig,
-    fetch::fetch_cert_chain,
+    fetch::{FetchedChain, fetch_cert_chain},
 };
 use chrono::DateTime;
 use clap::Parser;
 use eyre::Context;
-use luct_client::deduplication::RequestDeduplicationClient;
+use luct_client::{
+    Client, LogListUpdater, NodeSthClient,
+    cache::CachingClient,
+    deduplication::RequestDeduplicationClient,
+    rate_limit::{RateLimitClient, RateLimitConfig},
+    retry::RetryClient,
+};
 use luct_core::{
-    Fingerprint,
-    log_list::v3::LogList,
+    CertificateChain, Fingerprint, SignedCertificateTimestamp, SignedTreeHead,
+    log_list::{
+        LogListKey, apple::AppleLogList, merge_active_logs, merge_logs, v3::LogList,
+        with_custom_logs,
+    },
     store::{StoreRead, MemoryStore},
-    v1::SignedTreeHead,
 };
 use luct_otlsp::{OtlspClient, OtlspClientConfig};
-use luct_scanner::{Report, Scanner, ScannerConfig, ScannerImpl, Validated};
+use luct_scanner::{
+    EvidenceError, InconsistencyReport, PendingSct, PolicyKind, Report, Scanner, ScannerConfig,
+    ScannerImpl, SctSource, Validated, VantagePoint,
+};
 use luct_store::{FilesystemStore, StoreSwitch};
-use std::{sync::Arc, time::SystemTime};
+use std::{path::Path, sync::Arc, time::SystemTime};
 use tracing_subscriber::EnvFilter;
+use url::Url;
 
 mod args;
 mod conf;
@@ -35,10 +49,16 @@ const LOG_LIST: &str = include_str!("../log_list.json");
 struct CliScannerImpl;
 
 impl ScannerImpl for CliScannerImpl {
-    type Client = RequestDeduplicationClient<OtlspClient>;
+    type Client = RequestDeduplicationClient<
+        CachingClient<
+            RetryClient<RateLimitClient<OtlspClient>>,
+            FilesystemStore<[u8; 32], Vec<u8>>,
+        >,
+    >;
     type ReportStore =
         StoreSwitch<MemoryStore<Fingerprint, Report>, FilesystemStore<Fingerprint, Report>>;
     type SthStore = FilesystemStore<u64, Validated<SignedTreeHead>>;
+    type PendingStore = FilesystemStore<[u8; 32], PendingSct>;
 }
 
 #[tokio::main(flavor = "current_thread")]
@@ -56,6 +76,15 @@ async fn main() -> eyre::Result<()> {
     let log_list_path = log_list_path(&args, &config);
     tracing::debug!("Workdir: {:?}, log list path: {:?}", workdir, log_list_path);
 
+    let client_config = OtlspClientConfig::try_from(&config).map_err(|err| eyre::eyre!(err))?;
+    let rate_limit_config = RateLimitConfig::try_from(&config).map_err(|err| eyre::eyre!(err))?;
+
+    let client = RetryClient::new(RateLimitClient::new(
+        OtlspClient::new(client_config),
+        rate_limit_config.clone(),
+    ))
+    .with_max_attempts(config.max_attempts);
+
     let log_list = match log_list_path {
         None => LOG_LIST.to_string(),
         Some(log_list) => {
@@ -65,7 +94,42 @@ async fn main() -> eyre::Result<()> {
 
     let log_list: LogList = serde_json::from_str(&log_list)
         .with_context(|| "failed to parse log list json file".to_string())?;
-    let logs = log_list.currently_active_logs();
+    let log_list = load_updated_log_list(&config, &workdir, log_list);
+    let log_list = if args.update_log_list {
+        update_log_list(&config, &workdir, client.clone(), log_list).await?
+    } else {
+        log_list
+    };
+    let (logs, known_logs) = match &config.apple_log_list {
+        None => (log_list.currently_active_logs(), log_list.all_logs()),
+        Some(path) => {
+            let apple_log_list =
+                std::fs::read_to_string(path).with_context(|| "failed to read apple log list")?;
+            let apple_log_list: AppleLogList = serde_json::from_str(&apple_log_list)
+                .with_context(|| "failed to parse apple log list json file".to_string())?;
+            // The list of the selected policy comes first, such that its metadata is kept
+            let logs = match config.ct_policy {
+                PolicyKind::Chrome => merge_active_logs(
+                    log_list.currently_active_logs(),
+                    &log_list.all_logs(),
+                    [apple_log_list.currently_active_logs()],
+                ),
+                PolicyKind::Apple => merge_active_logs(
+                    apple_log_list.currently_active_logs(),
+                    &apple_log_list.all_logs(),
+                    [log_list.currently_active_logs()],
+                ),
+            };
+            let known_logs = merge_logs([log_list.all_logs(), apple_log_list.all_logs()]);
+            (logs, known_logs)
+        }
+    };
+    let logs = with_custom_logs(
+        logs,
+        &known_logs,
+        &config.custom_logs,
+        config.exclude_public_logs,
+    )?;
     tracing::info!("Imported {} logs", logs.len());
 
     let report_cache = if args.no_cache {
@@ -80,13 +144,18 @@ async fn main() -> eyre::Result<()> {
     };
 
     let scanner_config = ScannerConfig::try_from(&config).map_err(|err| eyre::eyre!(err))?;
-    let client_config = OtlspClientConfig::try_from(&config).map_err(|err| eyre::eyre!(err))?;
-
-    let client = RequestDeduplicationClient::new(OtlspClient::new(client_config));
+    let client = scanner_client(client, &workdir);
     let time_source = || DateTime::from(SystemTime::now());
 
-    let mut scanner =
-        Scanner::<CliScannerImpl>::new(scanner_config, report_cache, client, time_source);
+    let pending_store = FilesystemStore::new(workdir.join("pending"));
+
+    let mut scanner = Scanner::<CliScannerImpl>::new(
+        scanner_config,
+        report_cache,
+        pending_store,
+        client,
+        time_source,
+    );
     tracing::info!("Initialized scanner");
 
     for log in logs {
@@ -98,7 +167,26 @@ async fn main() -> eyre::Result<()> {
         scanner.refresh_all_logs().await?;
     }
 
-    let chain = fetch_cert_chain(&args.source)?;
+    if let Some(directory) = &args.gossip_sths {
+        add_vantage_points(&mut scanner, &config, &workdir, &rate_limit_config)?;
+        for report in scanner.gossip_sths().await {
+            export_inconsistency_report(&report, directory)?;
+        }
+    }
+
+    let rechecked = scanner.recheck_pending_scts().await;
+    if !rechecked.is_empty() {
+        tracing::info!(
+            "Re-checked pending SCTs of {} certificates",
+            rechecked.len()
+        );
+    }
+
+    let FetchedChain {
+        chain,
+        tls_scts,
+        ocsp_scts,
+    } = fetch_cert_chain(&args.source)?;
     println!("Fingerprint: {}", chain.cert().fingerprint_sha256());
 
     if let Some(destination) = args.output_certificate {
@@ -106,13 +194,245 @@ async fn main() -> eyre::Result<()> {
         std::fs::write(destination, chain_str).expect("failed to output pem chain")
     }
 
+    let chain = Arc::new(chain);
     let report = scanner
-        .collect_report(Arc::new(chain))
+        .collect_report_with_scts(chain.clone(), tls_scts.clone(), ocsp_scts.clone())
         .await
         .with_context(|| format!("failed to collext leads for {}", args.source))?;
 
     let report = serde_json::to_string_pretty(&report).unwrap();
     println!("Finished report: {}", report);
 
+    if let Some(directory) = args.mmd_evidence {
+        let embedded_scts = chain
+            .cert()
+            .extract_scts()
+            .with_context(|| "failed to extract embedded SCTs")?;
+        let scts = embedded_scts
+            .into_iter()
+            .map(|sct| (sct, SctSource::Embedded))
+            .chain(
+                tls_scts
+                    .into_iter()
+                    .map(|sct| (sct, SctSource::TlsExtension)),
+            )
+            .chain(ocsp_scts.into_iter().map(|sct| (sct, SctSource::Ocsp)));
+
+        for (sct, source) in scts {
+            export_mmd_evidence(&scanner, &chain, &sct, source, &directory).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Wrap a client into the caching and deduplicating clients used by the scanner
+fn scanner_client(
+    client: RetryClient<RateLimitClient<OtlspClient>>,
+    workdir: &Path,
+) -> <CliScannerImpl as ScannerImpl>::Client {
+    RequestDeduplicationClient::new(CachingClient::new(
+        client,
+        FilesystemStore::new(workdir.join("tile")),
+    ))
+}
+
+/// Add the vantage points, from which the STHs of the logs are cross-checked
+fn add_vantage_points(
+    scanner: &mut Scanner<CliScannerImpl>,
+    config: &CliConfig,
+    workdir: &Path,
+    rate_limit_config: &RateLimitConfig,
+) -> eyre::Result<()> {
+    let vantage_client = |proxy_url: Option<Url>| -> eyre::Result<_> {
+        let client_config = OtlspClientConfig::builder()
+            .agent(USER_AGENT.to_string())
+            .proxy_url(proxy_url)
+            .build()
+            .map_err(|err| eyre::eyre!(err))?;
+        let client = RetryClient::new(RateLimitClient::new(
+            OtlspClient::new(client_config),
+            rate_limit_config.clone(),
+        ))
+        .with_max_attempts(config.max_attempts);
+        Ok(scanner_client(client, workdir))
+    };
+
+    scanner.add_vantage_point("direct", VantagePoint::Log(vantage_client(None)?));
+    if let Some(otlsp_url) = &config.otlsp_url {
+        scanner.add_vantage_point(
+            "otlsp",
+            VantagePoint::Log(vantage_client(Some(otlsp_url.clone()))?),
+        );
+    }
+    for node in &config.sth_gossip_nodes {
+        let client = NodeSthClient::new(vantage_client(None)?, node.clone());
+        scanner.add_vantage_point(node.as_str(), VantagePoint::Node(client));
+    }
+
+    Ok(())
+}
+
+/// Write an [`InconsistencyReport`] into `directory`
+fn export_inconsistency_report(report: &InconsistencyReport, directory: &Path) -> eyre::Result<()> {
+    let path = directory.join(format!(
+        "{}-{}-{}.json",
+        file_name(report.log().description()),
+        report.first().sth().tree_size(),
+        report.second().sth().tree_size()
+    ));
+
+    std::fs::create_dir_all(directory).with_context(|| "failed to create report directory")?;
+    std::fs::write(&path, serde_json::to_string_pretty(report).unwrap())
+        .with_context(|| format!("failed to write inconsistency report to {}", path.display()))?;
+
+    // Only reports of forked roots can be verified offline
+    match report.verify() {
+        Ok(()) => println!(
+            "Log {} presented inconsistent STHs to {} and {}, report written to {}",
+            report.log().description(),
+            report.first().vantage_point(),
+            report.second().vantage_point(),
+            path.display()
+        ),
+        Err(EvidenceError::UnverifiableProof) => println!(
+            "Log {} returned a consistency proof, that fails between the STHs seen by {} and {}, \
+            report written to {}. The proof is not signed by the log, so the report can only be \
+            confirmed by fetching the proof from the log again",
+            report.log().description(),
+            report.first().vantage_point(),
+            report.second().vantage_point(),
+            path.display()
+        ),
+        Err(err) => tracing::warn!(
+            "The inconsistency report written to {} does not verify: {}",
+            path.display(),
+            err
+        ),
+    }
+
+    Ok(())
+}
+
+/// Turn a log description into a string, that can be used as a file name
+fn file_name(description: &str) -> String {
+    description.replace(['/', ' ', '\'', '"'], "_")
+}
+
+/// Use the log list previously fetched into the workdir, if it is newer than `log_list`
+///
+/// The stored list is only used, if its stored signature is still valid under the configured
+/// `log_list_key`, such that the list can not be replaced by anyone with access to the workdir.
+fn load_updated_log_list(config: &CliConfig, workdir: &Path, log_list: LogList) -> LogList {
+    let updated = read_log_list_key(config)
+        .and_then(|key| {
+            let Some(key) = key else {
+                return Ok(None);
+            };
+            let data = std::fs::read(workdir.join("log_list.json"))?;
+            let signature = std::fs::read(workdir.join("log_list.sig"))?;
+            key.validate(&data, &signature)
+                .map_err(|err| eyre::eyre!("invalid signature: {err}"))?;
+
+            Ok(Some(serde_json::from_slice::<LogList>(&data)?))
+        })
+        .unwrap_or_else(|err| {
+            tracing::warn!("Ignoring the log list stored in the workdir: {:?}", err);
+            None
+        });
+
+    match updated {
+        Some(updated) if updated.timestamp() > log_list.timestamp() => {
+            tracing::debug!("Using updated log list from {}", updated.timestamp());
+            updated
+        }
+        _ => log_list,
+    }
+}
+
+/// Read the key, that signs the log list, if `log_list_key` is configured
+fn read_log_list_key(config: &CliConfig) -> eyre::Result<Option<LogListKey>> {
+    let Some(key_path) = &config.log_list_key else {
+        return Ok(None);
+    };
+
+    let key = std::fs::read_to_string(key_path)
+        .with_context(|| format!("failed to read log list key from {}", key_path.display()))?;
+    let key =
+        LogListKey::from_pem(&key).map_err(|err| eyre::eyre!("invalid log list key: {err}"))?;
+
+    Ok(Some(key))
+}
+
+/// Fetch a newer log list, verify its signature and store it in the workdir
+///
+/// The signature is stored alongside the list, such that it can be verified again when loading it.
+async fn update_log_list<C: Client>(
+    config: &CliConfig,
+    workdir: &Path,
+    client: C,
+    log_list: LogList,
+) -> eyre::Result<LogList> {
+    let key = read_log_list_key(config)?
+        .ok_or_else(|| eyre::eyre!("updating the log list requires log_list_key to be set"))?;
+
+    let updater = LogListUpdater::new(client, key);
+    let Some((updated, data, signature)) = updater
+        .update(&log_list)
+        .await
+        .with_context(|| "failed to update log list")?
+    else {
+        println!("Log list from {} is up to date", log_list.timestamp());
+        return Ok(log_list);
+    };
+
+    std::fs::create_dir_all(workdir).with_context(|| "failed to create workdir")?;
+    std::fs::write(workdir.join("log_list.json"), data)
+        .with_context(|| "failed to store updated log list")?;
+    std::fs::write(workdir.join("log_list.sig"), signature)
+        .with_context(|| "failed to store signature of updated log list")?;
+    println!("Updated log list to {}", updated.timestamp());
+
+    Ok(updated)
+}
+
+/// Write evidence to `directory`, if the log did not include the SCT after its MMD passed
+async fn export_mmd_evidence(
+    scanner: &Scanner<CliScannerImpl>,
+    chain: &CertificateChain,
+    sct: &SignedCertificateTimestamp,
+    source: SctSource,
+    directory: &Path,
+) -> eyre::Result<()> {
+    let evidence = match scanner.collect_mmd_evidence(chain, sct, source).await {
+        Ok(Some(evidence)) => evidence,
+        Ok(None) => return Ok(()),
+        Err(err) if err.is_transient() => {
+            tracing::warn!(
+                "Could not check the inclusion of the SCT from log {}: {err}",
+                sct.log_id()
+            );
+            return Ok(());
+        }
+        Err(err) => return Err(err).with_context(|| "failed to collect MMD violation evidence"),
+    };
+
+    let log_name = scanner
+        .logs()
+        .find(|log| log.log_id() == &sct.log_id())
+        .map(|log| file_name(log.description()))
+        .unwrap_or_default();
+    let path = directory.join(format!("{}-{}.json", log_name, sct.timestamp()));
+
+    std::fs::create_dir_all(directory).with_context(|| "failed to create evidence directory")?;
+    std::fs::write(&path, serde_json::to_string_pretty(&evidence).unwrap())
+        .with_context(|| format!("failed to write evidence to {}", path.display()))?;
+    println!(
+        "Log {} did not include SCT after its MMD passed at {}, evidence written to {}",
+        log_name,
+        evidence.deadline(),
+        path.display()
+    );
+
     Ok(())
 }